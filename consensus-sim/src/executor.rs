@@ -0,0 +1,208 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use dusk_consensus::commons::{Database, TimeoutSet};
+use dusk_consensus::config::ConsensusParams;
+use dusk_consensus::errors::{HeaderError, OperationError};
+use dusk_consensus::operations::{
+    CallParams, Operations, Output, VerificationOutput, Voter,
+};
+use node_data::bls::PublicKeyBytes;
+use node_data::ledger::{Block, Fault, Hash, Header, InvalidFault};
+use node_data::StepName;
+use sha3::{Digest, Sha3_256};
+
+/// Block gas limit reported to the generators
+const BLOCK_GAS_LIMIT: u64 = 5_000_000_000;
+
+/// Number of elapsed times the average step time is computed on
+const ELAPSED_TIMES: usize = 5;
+
+/// Candidate blocks seen by any provisioner, by hash.
+///
+/// They are ordered, so that looking them up by anything but their hash
+/// gives the same result on every run.
+pub type Candidates = Arc<Mutex<BTreeMap<Hash, Block>>>;
+
+/// Hashes of the faults already included in an accepted block
+pub type IncludedFaults = Arc<Mutex<HashSet<[u8; 32]>>>;
+
+/// Executor of a simulated provisioner.
+///
+/// Blocks carry no transactions and the state root only depends on the
+/// height, so that honest provisioners always agree on it. A byzantine
+/// executor derives a different state root, so that the candidates it
+/// generates are voted invalid and it votes against the honest ones.
+pub struct SimExecutor {
+    byzantine: bool,
+    included_faults: IncludedFaults,
+    elapsed: Mutex<HashMap<StepName, VecDeque<Duration>>>,
+    params: ConsensusParams,
+}
+
+impl SimExecutor {
+    pub fn new(
+        byzantine: bool,
+        included_faults: IncludedFaults,
+        params: ConsensusParams,
+    ) -> Self {
+        Self {
+            byzantine,
+            included_faults,
+            elapsed: Mutex::default(),
+            params,
+        }
+    }
+
+    /// Returns the base timeouts of the next round, from the average time
+    /// the steps took in the latest rounds, as the node does.
+    pub fn base_timeouts(&self) -> TimeoutSet {
+        let elapsed = self.elapsed.lock().expect("lock to be acquired");
+        let (min, max) =
+            (self.params.min_step_timeout, self.params.max_step_timeout);
+        [
+            StepName::Proposal,
+            StepName::Validation,
+            StepName::Ratification,
+        ]
+        .into_iter()
+        .map(|step| {
+            let timeout = match elapsed.get(&step) {
+                Some(times) if !times.is_empty() => {
+                    times.iter().sum::<Duration>() / times.len() as u32
+                }
+                _ => max,
+            };
+            (step, timeout.clamp(min, max))
+        })
+        .collect()
+    }
+
+    fn verification_output(&self, height: u64) -> VerificationOutput {
+        let mut hasher = Sha3_256::new();
+        match self.byzantine {
+            true => hasher.update(b"byzantine state"),
+            false => hasher.update(b"state"),
+        }
+        hasher.update(height.to_le_bytes());
+
+        VerificationOutput {
+            state_root: hasher.finalize().into(),
+            event_hash: [0u8; 32],
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Operations for SimExecutor {
+    async fn verify_candidate_header(
+        &self,
+        candidate_header: &Header,
+        expected_generator: &PublicKeyBytes,
+    ) -> Result<(u8, Vec<Voter>, Vec<Voter>), HeaderError> {
+        if candidate_header.generator_bls_pubkey != *expected_generator {
+            return Err(HeaderError::InvalidBlockSignature(
+                "unexpected generator".into(),
+            ));
+        }
+        Ok((0, vec![], vec![]))
+    }
+
+    async fn verify_faults(
+        &self,
+        block_height: u64,
+        faults: &[Fault],
+    ) -> Result<(), OperationError> {
+        let included =
+            self.included_faults.lock().expect("lock to be acquired");
+        for fault in faults {
+            fault
+                .validate(block_height)
+                .map_err(OperationError::InvalidFaults)?;
+            if included.contains(&fault.hash()) {
+                return Err(OperationError::InvalidFaults(
+                    InvalidFault::Other("fault already included".into()),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    async fn verify_state_transition(
+        &self,
+        blk: &Block,
+        _voters: &[Voter],
+    ) -> Result<VerificationOutput, OperationError> {
+        Ok(self.verification_output(blk.header().height))
+    }
+
+    async fn execute_state_transition(
+        &self,
+        params: CallParams,
+    ) -> Result<Output, OperationError> {
+        Ok(Output {
+            txs: vec![],
+            verification_output: self.verification_output(params.round),
+            discarded_txs: vec![],
+        })
+    }
+
+    async fn add_step_elapsed_time(
+        &self,
+        _round: u64,
+        step_name: StepName,
+        elapsed: Duration,
+    ) -> Result<(), OperationError> {
+        let mut times = self.elapsed.lock().expect("lock to be acquired");
+        let times = times.entry(step_name).or_default();
+        if times.len() == ELAPSED_TIMES {
+            times.pop_front();
+        }
+        times.push_back(elapsed);
+        Ok(())
+    }
+
+    async fn get_block_gas_limit(&self) -> u64 {
+        BLOCK_GAS_LIMIT
+    }
+}
+
+/// Database of a simulated provisioner, sharing the candidates with the
+/// others so that the winning block can be fetched by hash.
+pub struct SimDatabase {
+    candidates: Candidates,
+    last_iter: (Hash, u8),
+}
+
+impl SimDatabase {
+    pub fn new(candidates: Candidates) -> Self {
+        Self {
+            candidates,
+            last_iter: Default::default(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Database for SimDatabase {
+    async fn store_candidate_block(&mut self, b: Block) {
+        let mut candidates =
+            self.candidates.lock().expect("lock to be acquired");
+        candidates.insert(b.header().hash, b);
+    }
+
+    async fn get_last_iter(&self) -> (Hash, u8) {
+        self.last_iter
+    }
+
+    async fn store_last_iter(&mut self, data: (Hash, u8)) {
+        self.last_iter = data;
+    }
+}
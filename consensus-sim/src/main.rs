@@ -0,0 +1,136 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+mod executor;
+mod network;
+mod report;
+mod simulation;
+
+use std::time::Duration;
+
+use clap::Parser;
+use dusk_consensus::config::ConsensusParams;
+use tracing_subscriber::EnvFilter;
+
+use crate::network::{NetworkConfig, Partition};
+use crate::simulation::SimConfig;
+
+/// Simulates many provisioners running the consensus in a single process,
+/// on a virtual clock.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// Number of provisioners, all with the same stake
+    #[arg(short = 'n', long, default_value_t = 64)]
+    provisioners: usize,
+
+    /// Number of blocks to accept
+    #[arg(short, long, default_value_t = 100)]
+    rounds: u64,
+
+    /// Seed of the consensus keys and of the network randomness
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Number of provisioners never coming online
+    #[arg(long, default_value_t = 0)]
+    offline: usize,
+
+    /// Number of byzantine provisioners, generating invalid candidates and
+    /// equivocating their validation votes
+    #[arg(long, default_value_t = 0)]
+    byzantine: usize,
+
+    /// Minimum delay of a message, in milliseconds
+    #[arg(long, default_value_t = 200)]
+    latency: u64,
+
+    /// Maximum random delay added to the latency, in milliseconds
+    #[arg(long, default_value_t = 300)]
+    jitter: u64,
+
+    /// Probability of a message to be lost
+    #[arg(long, default_value_t = 0.0)]
+    drop_rate: f64,
+
+    /// Time window, in seconds from the start, in which the provisioners are
+    /// split in two halves. It can be repeated
+    #[arg(long = "partition", value_name = "FROM..TO")]
+    partitions: Vec<Partition>,
+
+    /// Virtual time, in seconds, after which a round not producing a block is
+    /// considered halted
+    #[arg(long, default_value_t = 3600)]
+    round_timeout: u64,
+
+    /// Total credits of both the Validation and the Ratification committees
+    #[arg(long, default_value_t = MAINNET.validation_committee_credits)]
+    committee_credits: usize,
+
+    /// Lower bound of the step timeouts, in seconds
+    #[arg(long, default_value_t = MAINNET.min_step_timeout.as_secs())]
+    min_step_timeout: u64,
+
+    /// Upper bound of the step timeouts, in seconds
+    #[arg(long, default_value_t = MAINNET.max_step_timeout.as_secs())]
+    max_step_timeout: u64,
+
+    /// Increase of a step timeout after it expires, in seconds
+    #[arg(long, default_value_t = MAINNET.timeout_increase.as_secs())]
+    timeout_increase: u64,
+
+    /// Iteration from which the emergency mode is enabled
+    #[arg(long, default_value_t = MAINNET.emergency_mode_iteration_threshold)]
+    emergency_threshold: u8,
+
+    /// Minimum time between two blocks, in seconds
+    #[arg(long, default_value_t = MAINNET.minimum_block_time)]
+    minimum_block_time: u64,
+}
+
+const MAINNET: ConsensusParams = ConsensusParams::MAINNET;
+
+impl From<Args> for SimConfig {
+    fn from(args: Args) -> Self {
+        Self {
+            provisioners: args.provisioners,
+            rounds: args.rounds,
+            seed: args.seed,
+            offline: args.offline,
+            byzantine: args.byzantine,
+            network: NetworkConfig {
+                latency: Duration::from_millis(args.latency),
+                jitter: Duration::from_millis(args.jitter),
+                drop_rate: args.drop_rate,
+                partitions: args.partitions,
+            },
+            params: ConsensusParams {
+                validation_committee_credits: args.committee_credits,
+                ratification_committee_credits: args.committee_credits,
+                min_step_timeout: Duration::from_secs(args.min_step_timeout),
+                max_step_timeout: Duration::from_secs(args.max_step_timeout),
+                timeout_increase: Duration::from_secs(args.timeout_increase),
+                emergency_mode_iteration_threshold: args.emergency_threshold,
+                minimum_block_time: args.minimum_block_time,
+                ..MAINNET
+            },
+            round_timeout: Duration::from_secs(args.round_timeout),
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new("warn,consensus_sim=info"));
+    tracing_subscriber::fmt().with_env_filter(filter).init();
+
+    let config = SimConfig::from(Args::parse());
+    let report =
+        simulation::runtime(config.seed)?.block_on(simulation::run(config))?;
+
+    println!("{report}");
+    Ok(())
+}
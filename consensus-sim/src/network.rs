@@ -0,0 +1,261 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::ops::Range;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use dusk_consensus::signer::{sign_message, ConsensusSigner};
+use node_data::message::payload::{Candidate, GetResource, InvParam, Vote};
+use node_data::message::{AsyncQueue, Message, Payload};
+use rand::rngs::StdRng;
+use rand::Rng;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{self, Instant};
+use tracing::warn;
+
+use crate::executor::Candidates;
+
+/// Conditions of the simulated network
+#[derive(Debug, Clone, Default)]
+pub struct NetworkConfig {
+    /// Minimum delay of a message
+    pub latency: Duration,
+    /// Maximum random delay added to the latency
+    pub jitter: Duration,
+    /// Probability of a message to be lost
+    pub drop_rate: f64,
+    /// Time windows in which the network is partitioned
+    pub partitions: Vec<Partition>,
+}
+
+/// Time window, relative to the start of the simulation, in which the
+/// provisioners are split in two halves not reaching each other.
+#[derive(Debug, Clone)]
+pub struct Partition(pub Range<Duration>);
+
+impl FromStr for Partition {
+    type Err = String;
+
+    /// Parses a partition from its window in seconds, as `FROM..TO`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (from, to) = s
+            .split_once("..")
+            .ok_or_else(|| format!("invalid partition {s}, use FROM..TO"))?;
+        let secs = |v: &str| {
+            v.trim()
+                .parse()
+                .map(Duration::from_secs)
+                .map_err(|e| format!("invalid partition bound {v}: {e}"))
+        };
+        Ok(Self(secs(from)?..secs(to)?))
+    }
+}
+
+/// Provisioner attached to the simulated network
+pub struct Peer {
+    pub inbound: AsyncQueue<Message>,
+    pub signer: Arc<dyn ConsensusSigner>,
+    pub online: bool,
+    pub byzantine: bool,
+}
+
+/// Network delivering the messages of the provisioners to each other, with
+/// latency, losses and partitions.
+///
+/// Delays are timers of the Tokio runtime, so they are advanced by the
+/// virtual clock of the simulation. Randomness is drawn from a single
+/// seeded generator.
+pub struct Network {
+    config: NetworkConfig,
+    peers: Vec<Peer>,
+    rng: Mutex<StdRng>,
+    start: Instant,
+    candidates: Candidates,
+    quorums: mpsc::UnboundedSender<Message>,
+    sent: AtomicU64,
+    dropped: AtomicU64,
+    /// Highest iteration a message was sent for, by round
+    max_iteration: Mutex<(u64, u8)>,
+}
+
+impl Network {
+    pub fn new(
+        config: NetworkConfig,
+        peers: Vec<Peer>,
+        rng: StdRng,
+        candidates: Candidates,
+        quorums: mpsc::UnboundedSender<Message>,
+    ) -> Self {
+        Self {
+            config,
+            peers,
+            rng: Mutex::new(rng),
+            start: Instant::now(),
+            candidates,
+            quorums,
+            sent: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            max_iteration: Mutex::default(),
+        }
+    }
+
+    /// Spawns the task routing the messages sent by a provisioner.
+    ///
+    /// Quorums are also reported to the simulation, which accepts the
+    /// winning blocks.
+    pub fn spawn_router(
+        self: &Arc<Self>,
+        from: usize,
+        outbound: AsyncQueue<Message>,
+    ) -> JoinHandle<()> {
+        let network = self.clone();
+        tokio::spawn(async move {
+            while let Ok(msg) = outbound.recv().await {
+                network.route(from, msg).await;
+            }
+        })
+    }
+
+    /// Returns the number of messages sent and the number of messages lost
+    pub fn stats(&self) -> (u64, u64) {
+        (
+            self.sent.load(Ordering::Relaxed),
+            self.dropped.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Returns the highest iteration reached by the provisioners in the
+    /// given round
+    pub fn max_iteration(&self, round: u64) -> u8 {
+        match *self.max_iteration.lock().expect("lock to be acquired") {
+            (r, iteration) if r == round => iteration,
+            _ => 0,
+        }
+    }
+
+    async fn route(&self, from: usize, msg: Message) {
+        self.track_iteration(&msg);
+
+        match &msg.payload {
+            Payload::Quorum(_) => {
+                let _ = self.quorums.send(msg.clone());
+                self.broadcast(from, msg);
+            }
+            Payload::Validation(_) if self.peers[from].byzantine => {
+                self.equivocate(from, msg).await;
+            }
+            Payload::Candidate(_)
+            | Payload::Validation(_)
+            | Payload::Ratification(_) => self.broadcast(from, msg),
+            Payload::GetResource(res) => self.serve(from, res),
+            _ => {}
+        }
+    }
+
+    fn track_iteration(&self, msg: &Message) {
+        let mut max = self.max_iteration.lock().expect("lock to be acquired");
+        let (round, iteration) = (msg.header.round, msg.header.iteration);
+        if round > max.0 || (round == max.0 && iteration > max.1) {
+            *max = (round, iteration);
+        }
+    }
+
+    fn broadcast(&self, from: usize, msg: Message) {
+        for to in (0..self.peers.len()).filter(|to| *to != from) {
+            self.send(from, to, msg.clone());
+        }
+    }
+
+    fn send(&self, from: usize, to: usize, msg: Message) {
+        if !self.peers[to].online || self.partitioned(from, to) {
+            return;
+        }
+        self.sent.fetch_add(1, Ordering::Relaxed);
+
+        let delay = {
+            let mut rng = self.rng.lock().expect("lock to be acquired");
+            if rng.gen_bool(self.config.drop_rate) {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+            self.config.latency + self.config.jitter.mul_f64(rng.gen())
+        };
+
+        let inbound = self.peers[to].inbound.clone();
+        tokio::spawn(async move {
+            time::sleep(delay).await;
+            inbound.try_send(msg);
+        });
+    }
+
+    fn partitioned(&self, from: usize, to: usize) -> bool {
+        let elapsed = self.start.elapsed();
+        let half = self.peers.len() / 2;
+        self.config
+            .partitions
+            .iter()
+            .any(|p| p.0.contains(&elapsed) && (from < half) != (to < half))
+    }
+
+    /// Sends a validation vote to half of the peers and a conflicting one,
+    /// signed with the same key, to the other half.
+    async fn equivocate(&self, from: usize, msg: Message) {
+        let Payload::Validation(validation) = &msg.payload else {
+            return;
+        };
+
+        let mut conflicting = validation.clone();
+        conflicting.vote = match validation.vote {
+            Vote::Valid(hash) => Vote::Invalid(hash),
+            Vote::Invalid(hash) => Vote::Valid(hash),
+            _ => Vote::Valid([0u8; 32]),
+        };
+        let signer = self.peers[from].signer.as_ref();
+        if let Err(err) = sign_message(signer, &mut conflicting).await {
+            warn!(event = "cannot sign conflicting vote", ?err);
+            return self.broadcast(from, msg);
+        }
+        let conflicting = Message::from(conflicting);
+
+        for to in (0..self.peers.len()).filter(|to| *to != from) {
+            match to % 2 {
+                0 => self.send(from, to, msg.clone()),
+                _ => self.send(from, to, conflicting.clone()),
+            }
+        }
+    }
+
+    /// Serves the requests of candidates missed by a provisioner.
+    fn serve(&self, from: usize, res: &GetResource) {
+        for inv in &res.get_inv().inv_list {
+            let InvParam::HashAndIteration(prev_block_hash, iteration) =
+                inv.param
+            else {
+                continue;
+            };
+
+            let candidate = {
+                let candidates =
+                    self.candidates.lock().expect("lock to be acquired");
+                candidates
+                    .values()
+                    .find(|b| {
+                        b.header().prev_block_hash == prev_block_hash
+                            && b.header().iteration == iteration
+                    })
+                    .cloned()
+            };
+
+            if let Some(candidate) = candidate {
+                self.send(from, from, Candidate { candidate }.into());
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use dusk_consensus::config::ConsensusParams;
+
+/// Outcome of a round of the simulation
+#[derive(Debug, Clone)]
+pub struct RoundStats {
+    pub round: u64,
+    /// Virtual time elapsed since the previous block was accepted
+    pub block_time: Duration,
+    /// Iteration of the accepted block
+    pub iteration: u8,
+    /// Highest iteration reached by any provisioner during the round
+    pub max_iteration: u8,
+    /// Number of faults included in the accepted block
+    pub faults: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub rounds: Vec<RoundStats>,
+    /// Round that did not produce any block, if any
+    pub halted_at: Option<u64>,
+    pub messages_sent: u64,
+    pub messages_dropped: u64,
+    /// Consensus parameters the simulation ran with
+    pub params: ConsensusParams,
+    /// Whether the runtime was seeded, making the run reproducible
+    pub seeded: bool,
+}
+
+impl Report {
+    /// Returns the number of rounds that entered the emergency mode
+    pub fn emergency_entries(&self) -> usize {
+        self.rounds
+            .iter()
+            .filter(|r| self.params.is_emergency_iter(r.max_iteration))
+            .count()
+    }
+
+    /// Returns the block times, sorted
+    fn block_times(&self) -> Vec<Duration> {
+        let mut times: Vec<_> =
+            self.rounds.iter().map(|r| r.block_time).collect();
+        times.sort();
+        times
+    }
+}
+
+/// Returns the value at the given percentile of the sorted values
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let index = (sorted.len() * p / 100).min(sorted.len() - 1);
+    sorted[index]
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "blocks accepted: {}", self.rounds.len())?;
+        if let Some(round) = self.halted_at {
+            writeln!(f, "halted at round: {round}")?;
+        }
+
+        let times = self.block_times();
+        if !times.is_empty() {
+            let total: Duration = times.iter().sum();
+            writeln!(
+                f,
+                "block time: avg {:.2?}, min {:.2?}, p50 {:.2?}, p90 {:.2?}, \
+                 max {:.2?}",
+                total / times.len() as u32,
+                times[0],
+                percentile(&times, 50),
+                percentile(&times, 90),
+                times[times.len() - 1],
+            )?;
+        }
+
+        let mut iterations = BTreeMap::new();
+        for stats in &self.rounds {
+            *iterations.entry(stats.iteration).or_insert(0usize) += 1;
+        }
+        writeln!(f, "blocks by iteration:")?;
+        for (iteration, count) in iterations {
+            writeln!(f, "  {iteration:>3}: {count}")?;
+        }
+
+        let faults: usize = self.rounds.iter().map(|r| r.faults).sum();
+        writeln!(f, "emergency mode entries: {}", self.emergency_entries())?;
+        writeln!(f, "faults included: {faults}")?;
+        write!(
+            f,
+            "messages sent: {}, dropped: {}",
+            self.messages_sent, self.messages_dropped
+        )?;
+        if !self.seeded {
+            write!(
+                f,
+                "\nruntime not seeded, runs of the same seed may differ: \
+                 build with RUSTFLAGS=\"--cfg tokio_unstable\" to reproduce"
+            )?;
+        }
+        Ok(())
+    }
+}
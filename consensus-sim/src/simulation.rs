@@ -0,0 +1,410 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use dusk_consensus::clock::Clock;
+use dusk_consensus::commons::RoundUpdate;
+use dusk_consensus::config::ConsensusParams;
+use dusk_consensus::consensus::Consensus;
+use dusk_consensus::equivocation::EquivocationRegistry;
+use dusk_consensus::queue::MsgRegistry;
+use dusk_consensus::signer::{ConsensusSigner, InMemorySigner};
+use dusk_consensus::user::provisioners::{Provisioners, DUSK};
+use execution_core::signatures::bls::SecretKey as BlsSecretKey;
+use node_data::ledger::{Block, Header};
+use node_data::message::payload::Vote;
+use node_data::message::{AsyncQueue, Message, Payload};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use tokio::runtime::Runtime;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::time::{self, Instant};
+use tracing::{info, warn};
+
+use crate::executor::{Candidates, IncludedFaults, SimDatabase, SimExecutor};
+use crate::network::{Network, NetworkConfig, Peer};
+use crate::report::{Report, RoundStats};
+
+/// Timestamp of the genesis block, the virtual clock starts from
+const GENESIS_TIMESTAMP: u64 = 1_700_000_000;
+
+/// Stake of every provisioner
+const STAKE: u64 = 1_000 * DUSK;
+
+/// Capacity of the message queues of a provisioner
+const QUEUE_SIZE: usize = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    /// Number of provisioners, all with the same stake
+    pub provisioners: usize,
+    /// Number of blocks to accept
+    pub rounds: u64,
+    /// Seed of the consensus keys and of the network randomness
+    pub seed: u64,
+    /// Number of provisioners never coming online
+    pub offline: usize,
+    /// Number of byzantine provisioners
+    pub byzantine: usize,
+    pub network: NetworkConfig,
+    /// Consensus parameters run by every provisioner
+    pub params: ConsensusParams,
+    /// Virtual time after which a round not producing a block is
+    /// considered halted
+    pub round_timeout: Duration,
+}
+
+type SimConsensus = Consensus<SimExecutor, SimDatabase>;
+
+struct SimNode {
+    signer: Arc<dyn ConsensusSigner>,
+    executor: Arc<SimExecutor>,
+    consensus: Arc<SimConsensus>,
+}
+
+/// Builds the runtime a simulation runs on.
+///
+/// It runs all the provisioners on a single thread with a paused clock, which
+/// is advanced to the next timer as soon as every task is idle. Rounds are
+/// then driven by the virtual time only, regardless of the speed of the
+/// host, which is also why the CPU time spent verifying signatures is not
+/// accounted for.
+pub fn runtime(seed: u64) -> io::Result<Runtime> {
+    let mut builder = tokio::runtime::Builder::new_current_thread();
+    builder.enable_all().start_paused(true);
+
+    // Makes the order in which `select!` polls its branches reproducible
+    #[cfg(tokio_unstable)]
+    builder.rng_seed(tokio::runtime::RngSeed::from_bytes(&seed.to_le_bytes()));
+    #[cfg(not(tokio_unstable))]
+    let _ = seed;
+
+    builder.build()
+}
+
+/// Runs a simulation, returning the report of the accepted blocks.
+///
+/// It must be run on the [`runtime`] of the simulation, whose paused clock
+/// the consensus reads the time from.
+///
+/// At every round, all the online provisioners spin the consensus on top of
+/// the same tip. The first valid quorum produced by any of them accepts the
+/// winning candidate, which becomes the tip of the next round for everyone:
+/// blocks are assumed to be propagated instantly.
+pub async fn run(config: SimConfig) -> Result<Report> {
+    let n = config.provisioners;
+    if n == 0 || config.offline + config.byzantine > n {
+        bail!("offline and byzantine provisioners exceed the provisioners");
+    }
+    if !(0.0..=1.0).contains(&config.network.drop_rate) {
+        bail!("drop rate must be between 0 and 1");
+    }
+    if let Err(err) = config.params.validate() {
+        bail!("invalid consensus parameters: {err}");
+    }
+    let params = config.params;
+
+    let clock = Clock::virtual_from(GENESIS_TIMESTAMP);
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let candidates = Candidates::default();
+    let included_faults = IncludedFaults::default();
+
+    let mut provisioners = Provisioners::empty();
+    let mut nodes = vec![];
+    let mut peers = vec![];
+    let mut outbounds = vec![];
+    for i in 0..n {
+        let sk = BlsSecretKey::random(&mut rng);
+        let signer: Arc<dyn ConsensusSigner> =
+            Arc::new(InMemorySigner::new(sk));
+        provisioners.add_member_with_value(signer.public_key().clone(), STAKE);
+
+        let online = i < n - config.offline;
+        let byzantine = online && i >= n - config.offline - config.byzantine;
+
+        let inbound = AsyncQueue::bounded(QUEUE_SIZE, "inbound");
+        let outbound = AsyncQueue::bounded(QUEUE_SIZE, "outbound");
+
+        let executor = Arc::new(SimExecutor::new(
+            byzantine,
+            included_faults.clone(),
+            params,
+        ));
+        let db = SimDatabase::new(candidates.clone());
+        let consensus = Consensus::new(
+            inbound.clone(),
+            outbound.clone(),
+            Arc::new(Mutex::new(MsgRegistry::default())),
+            Arc::new(Mutex::new(EquivocationRegistry::new(params))),
+            executor.clone(),
+            Arc::new(Mutex::new(db)),
+        );
+
+        peers.push(Peer {
+            inbound,
+            signer: signer.clone(),
+            online,
+            byzantine,
+        });
+        if online {
+            outbounds.push((i, outbound));
+            nodes.push(SimNode {
+                signer,
+                executor,
+                consensus: Arc::new(consensus),
+            });
+        }
+    }
+    let provisioners = Arc::new(provisioners);
+
+    let (quorum_tx, mut quorum_rx) = mpsc::unbounded_channel();
+    let network = Arc::new(Network::new(
+        config.network.clone(),
+        peers,
+        rng,
+        candidates.clone(),
+        quorum_tx,
+    ));
+    let routers: Vec<_> = outbounds
+        .into_iter()
+        .map(|(i, outbound)| network.spawn_router(i, outbound))
+        .collect();
+
+    let genesis = Header {
+        timestamp: GENESIS_TIMESTAMP,
+        ..Default::default()
+    };
+    let genesis = Block::new(genesis, vec![], vec![])?;
+    let mut tip = genesis.header().clone();
+
+    let mut report = Report {
+        params,
+        seeded: cfg!(tokio_unstable),
+        ..Default::default()
+    };
+    let mut accepted_at = Instant::now();
+    for _ in 0..config.rounds {
+        let round = tip.height + 1;
+
+        let mut spins = vec![];
+        for node in &nodes {
+            let ru = RoundUpdate::new(
+                node.signer.clone(),
+                &tip,
+                params,
+                node.executor.base_timeouts(),
+                vec![],
+            )
+            .with_clock(clock);
+            let consensus = node.consensus.clone();
+            let provisioners = provisioners.clone();
+            let (cancel_tx, cancel_rx) = oneshot::channel();
+            let handle = tokio::spawn(async move {
+                let _ = consensus.spin(ru, provisioners, cancel_rx).await;
+            });
+            spins.push((cancel_tx, handle));
+        }
+
+        let winner = time::timeout(
+            config.round_timeout,
+            wait_winner(&mut quorum_rx, round, &candidates),
+        )
+        .await;
+
+        for (cancel_tx, handle) in spins {
+            let _ = cancel_tx.send(0);
+            let _ = handle.await;
+        }
+
+        let Ok(Some(block)) = winner else {
+            warn!(event = "round halted", round);
+            report.halted_at = Some(round);
+            break;
+        };
+
+        let stats = RoundStats {
+            round,
+            block_time: accepted_at.elapsed(),
+            iteration: block.header().iteration,
+            max_iteration: network.max_iteration(round),
+            faults: block.faults().len(),
+        };
+        info!(
+            event = "block accepted",
+            round,
+            block_time = ?stats.block_time,
+            iter = stats.iteration,
+            max_iter = stats.max_iteration,
+            faults = stats.faults,
+        );
+        report.rounds.push(stats);
+        accepted_at = Instant::now();
+
+        included_faults
+            .lock()
+            .expect("lock to be acquired")
+            .extend(block.faults().iter().map(|f| f.hash()));
+        candidates.lock().expect("lock to be acquired").clear();
+
+        tip = block.header().clone();
+    }
+
+    for router in routers {
+        router.abort();
+    }
+    (report.messages_sent, report.messages_dropped) = network.stats();
+
+    Ok(report)
+}
+
+/// Waits for the first valid quorum of the round, returning the winning
+/// candidate with its attestation.
+async fn wait_winner(
+    quorums: &mut mpsc::UnboundedReceiver<Message>,
+    round: u64,
+    candidates: &Candidates,
+) -> Option<Block> {
+    while let Some(msg) = quorums.recv().await {
+        let Payload::Quorum(quorum) = &msg.payload else {
+            continue;
+        };
+        if msg.header.round != round || quorum.att.result.failed() {
+            continue;
+        }
+        let Vote::Valid(hash) = quorum.vote() else {
+            continue;
+        };
+
+        let candidate = candidates
+            .lock()
+            .expect("lock to be acquired")
+            .get(hash)
+            .cloned();
+        match candidate {
+            Some(mut block) => {
+                block.set_attestation(quorum.att);
+                return Some(block);
+            }
+            None => warn!(event = "quorum for unknown candidate", round),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn honest_network_accepts_blocks() {
+        let config = SimConfig {
+            provisioners: 8,
+            rounds: 3,
+            seed: 1,
+            offline: 0,
+            byzantine: 0,
+            network: NetworkConfig {
+                latency: Duration::from_millis(100),
+                jitter: Duration::from_millis(100),
+                ..Default::default()
+            },
+            params: ConsensusParams::default(),
+            round_timeout: Duration::from_secs(600),
+        };
+
+        let report = runtime(config.seed)
+            .unwrap()
+            .block_on(run(config))
+            .expect("simulation should run");
+
+        assert_eq!(report.halted_at, None);
+        assert_eq!(report.rounds.len(), 3);
+        assert_eq!(report.emergency_entries(), 0);
+        for stats in &report.rounds {
+            assert_eq!(stats.iteration, 0);
+        }
+    }
+
+    #[test]
+    fn devnet_params_shorten_block_time() {
+        let params = ConsensusParams {
+            validation_committee_credits: 8,
+            ratification_committee_credits: 8,
+            min_step_timeout: Duration::from_secs(1),
+            max_step_timeout: Duration::from_secs(5),
+            minimum_block_time: 2,
+            ..Default::default()
+        };
+        let config = SimConfig {
+            provisioners: 8,
+            rounds: 3,
+            seed: 1,
+            offline: 0,
+            byzantine: 0,
+            network: NetworkConfig {
+                latency: Duration::from_millis(100),
+                jitter: Duration::from_millis(100),
+                ..Default::default()
+            },
+            params,
+            round_timeout: Duration::from_secs(600),
+        };
+
+        let report = runtime(config.seed)
+            .unwrap()
+            .block_on(run(config))
+            .expect("simulation should run");
+
+        assert_eq!(report.halted_at, None);
+        assert_eq!(report.rounds.len(), 3);
+        let mainnet_block_time =
+            Duration::from_secs(ConsensusParams::MAINNET.minimum_block_time);
+        for stats in &report.rounds {
+            assert!(stats.block_time < mainnet_block_time);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(tokio_unstable),
+        ignore = "the runtime is seeded with `--cfg tokio_unstable` only"
+    )]
+    fn same_seed_same_report() {
+        let config = || SimConfig {
+            provisioners: 8,
+            rounds: 3,
+            seed: 7,
+            offline: 0,
+            byzantine: 2,
+            network: NetworkConfig {
+                latency: Duration::from_millis(100),
+                jitter: Duration::from_millis(100),
+                drop_rate: 0.05,
+                ..Default::default()
+            },
+            params: ConsensusParams::default(),
+            round_timeout: Duration::from_secs(600),
+        };
+        let simulate = || {
+            let config = config();
+            runtime(config.seed)
+                .unwrap()
+                .block_on(run(config))
+                .expect("simulation should run")
+        };
+
+        let (a, b) = (simulate(), simulate());
+        assert_eq!(format!("{:?}", a.rounds), format!("{:?}", b.rounds));
+        assert_eq!(a.halted_at, b.halted_at);
+        assert_eq!(a.messages_sent, b.messages_sent);
+        assert_eq!(a.messages_dropped, b.messages_dropped);
+    }
+}
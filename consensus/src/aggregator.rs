@@ -4,7 +4,7 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use crate::config::is_emergency_iter;
+use crate::config::ConsensusParams;
 use crate::user::cluster::Cluster;
 use crate::user::committee::Committee;
 use dusk_bytes::Serializable;
@@ -80,12 +80,13 @@ impl<V: StepVote> Aggregator<V> {
         &mut self,
         committee: &Committee,
         v: &V,
+        params: &ConsensusParams,
     ) -> Result<(StepVotes, bool), AggregatorError<V>> {
         let sign_info = v.sign_info();
 
         let iter = v.header().iteration;
 
-        let emergency = is_emergency_iter(iter);
+        let emergency = params.is_emergency_iter(iter);
 
         let msg_step = v.get_step();
         let vote = v.vote();
@@ -233,16 +234,16 @@ mod tests {
     use super::*;
     use crate::aggregator::Aggregator;
     use crate::commons::RoundUpdate;
+    use crate::signer::{ConsensusSigner, InMemorySigner};
     use crate::user::committee::Committee;
     use crate::user::provisioners::{Provisioners, DUSK};
     use crate::user::sortition::Config;
     use dusk_bytes::DeserializableSlice;
-    use execution_core::signatures::bls::{
-        PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
-    };
+    use execution_core::signatures::bls::SecretKey as BlsSecretKey;
     use hex::FromHex;
     use node_data::ledger::{Header, Seed};
     use std::collections::HashMap;
+    use std::sync::Arc;
 
     impl<V> Aggregator<V> {
         pub fn get_total(&self, step: u8, vote: Vote) -> Option<usize> {
@@ -253,8 +254,8 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_collect_votes() {
+    #[tokio::test]
+    async fn test_collect_votes() {
         let sks = [
             "7f6f2ccdb23f2abb7b69278e947c01c6160a31cf02c19d06d0f6e5ab1d768b15",
             "611830d3641a68f94a690dcc25d1f4b0dac948325ac18f6dd32564371735f32c",
@@ -287,20 +288,20 @@ mod tests {
         // Create provisioners
         // Also populate a vector of headers
         let mut p = Provisioners::empty();
+        let params = ConsensusParams::default();
         let mut input = vec![];
         let mut tip_header = Header::default();
         tip_header.height = 0;
 
         for secret_key in sks {
-            let pubkey_bls =
-                node_data::bls::PublicKey::new(BlsPublicKey::from(&secret_key));
+            let signer = Arc::new(InMemorySigner::new(secret_key));
 
-            p.add_member_with_value(pubkey_bls.clone(), 1000 * DUSK);
+            p.add_member_with_value(signer.public_key().clone(), 1000 * DUSK);
 
             let ru = RoundUpdate::new(
-                pubkey_bls,
-                secret_key,
+                signer,
                 &tip_header,
+                params,
                 HashMap::new(),
                 vec![],
             );
@@ -309,7 +310,9 @@ mod tests {
                 init_vote.clone(),
                 &ru,
                 iteration,
-            );
+            )
+            .await
+            .expect("signing should succeed");
 
             // Message headers to be used in test for voting for hash:
             // block_hash
@@ -355,8 +358,9 @@ mod tests {
             // Last member's vote should reach the quorum
             if i == winning_index {
                 // (hash, sv) is only returned in case we reach the quorum
-                let (sv, quorum_reached) =
-                    a.collect_vote(&c, msg).expect("failed to reach quorum");
+                let (sv, quorum_reached) = a
+                    .collect_vote(&c, msg, &params)
+                    .expect("failed to reach quorum");
 
                 assert!(quorum_reached, "quorum should be reached");
 
@@ -372,7 +376,7 @@ mod tests {
 
             println!("Collecting vote for index {i}");
             // Check collected votes
-            let (_, quorum_reached) = a.collect_vote(&c, msg).unwrap();
+            let (_, quorum_reached) = a.collect_vote(&c, msg, &params).unwrap();
 
             assert!(!quorum_reached, "quorum should not be reached yet");
 
@@ -384,7 +388,7 @@ mod tests {
 
             if i == 0 {
                 // Ensure a duplicated vote is discarded
-                match a.collect_vote(&c, msg) {
+                match a.collect_vote(&c, msg, &params) {
                     Err(AggregatorError::DuplicatedVote) => {}
                     _ => panic!("Vote should be discarded"),
                 }
@@ -392,7 +396,7 @@ mod tests {
                 // Ensure a conflicting vote is discarded
                 let mut wrong_msg = msg.clone();
                 wrong_msg.vote = Vote::Invalid(block_hash);
-                match a.collect_vote(&c, &wrong_msg) {
+                match a.collect_vote(&c, &wrong_msg, &params) {
                     Err(AggregatorError::ConflictingVote(m)) => {
                         assert_eq!(&m, msg)
                     }
@@ -0,0 +1,44 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use node_data::get_current_timestamp;
+use tokio::time::Instant;
+
+/// Clock the consensus reads the current time from.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Clock {
+    /// The system time
+    #[default]
+    System,
+    /// The Tokio clock, starting from the given timestamp at the given
+    /// instant
+    Virtual { start: u64, instant: Instant },
+}
+
+impl Clock {
+    /// Returns a clock following the Tokio clock, starting from the given
+    /// timestamp.
+    ///
+    /// This is meant for simulations running on a paused Tokio clock, where
+    /// the system time does not follow the time advanced by the runtime. It
+    /// must be created from within the runtime it is used on.
+    pub fn virtual_from(start_timestamp: u64) -> Self {
+        Self::Virtual {
+            start: start_timestamp,
+            instant: Instant::now(),
+        }
+    }
+
+    /// Returns the current timestamp in seconds.
+    pub fn now(&self) -> u64 {
+        match self {
+            Self::System => get_current_timestamp(),
+            Self::Virtual { start, instant } => {
+                start + instant.elapsed().as_secs()
+            }
+        }
+    }
+}
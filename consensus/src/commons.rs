@@ -9,26 +9,30 @@
 
 use node_data::ledger::*;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use std::time::Duration;
 
-use execution_core::signatures::bls::SecretKey as BlsSecretKey;
 use node_data::bls::PublicKey;
-use node_data::message::{AsyncQueue, Message, Payload};
+use node_data::message::{AsyncQueue, ConsensusHeader, Message, Payload};
 use node_data::StepName;
 
+use crate::clock::Clock;
+use crate::config::ConsensusParams;
 use crate::operations::Voter;
+use crate::signer::ConsensusSigner;
+use crate::slashing_protection::SlashingProtection;
 
 pub type TimeoutSet = HashMap<StepName, Duration>;
 
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Debug)]
 pub struct RoundUpdate {
     // Current round number of the ongoing consensus
     pub round: u64,
 
     // This provisioner consensus keys
     pub pubkey_bls: PublicKey,
-    pub secret_key: BlsSecretKey,
+    pub signer: Arc<dyn ConsensusSigner>,
 
     seed: Seed,
     hash: [u8; 32],
@@ -37,27 +41,61 @@ pub struct RoundUpdate {
     timestamp: u64,
 
     pub base_timeouts: TimeoutSet,
+
+    // Parameters of the network the consensus runs on
+    pub params: ConsensusParams,
+
+    // Guards the messages signed with the consensus keys
+    pub slashing_protection: SlashingProtection,
+
+    // Clock the timestamps of the round are read from
+    pub clock: Clock,
 }
 
 impl RoundUpdate {
     pub fn new(
-        pubkey_bls: PublicKey,
-        secret_key: BlsSecretKey,
+        signer: Arc<dyn ConsensusSigner>,
         tip_header: &Header,
+        params: ConsensusParams,
         base_timeouts: TimeoutSet,
         att_voters: Vec<Voter>,
     ) -> Self {
         let round = tip_header.height + 1;
         RoundUpdate {
             round,
-            pubkey_bls,
-            secret_key,
+            pubkey_bls: signer.public_key().clone(),
+            signer,
             att: tip_header.att,
             hash: tip_header.hash,
             seed: tip_header.seed,
             timestamp: tip_header.timestamp,
             base_timeouts,
+            params,
             att_voters,
+            slashing_protection: SlashingProtection::default(),
+            clock: Clock::default(),
+        }
+    }
+
+    pub fn with_slashing_protection(
+        mut self,
+        slashing_protection: SlashingProtection,
+    ) -> Self {
+        self.slashing_protection = slashing_protection;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns the header of the messages signed for the given iteration.
+    pub fn consensus_header(&self, iteration: u8) -> ConsensusHeader {
+        ConsensusHeader {
+            prev_block_hash: self.hash,
+            round: self.round,
+            iteration,
         }
     }
 
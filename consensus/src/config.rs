@@ -7,6 +7,7 @@
 use std::time::Duration;
 
 use node_data::message::{MESSAGE_MAX_FAILED_ITERATIONS, MESSAGE_MAX_ITER};
+use node_data::StepName;
 
 /// Maximum number of iterations Consensus runs per a single round.
 pub const CONSENSUS_MAX_ITER: u8 = MESSAGE_MAX_ITER;
@@ -30,6 +31,10 @@ pub const MAX_STEP_TIMEOUT: Duration = Duration::from_secs(40);
 pub const TIMEOUT_INCREASE: Duration = Duration::from_secs(2);
 pub const MINIMUM_BLOCK_TIME: u64 = 10;
 
+/// Faults of double candidates carrying their whole header are not yet
+/// scheduled on mainnet
+pub const CANDIDATE_FAULT_ACTIVATION_HEIGHT: u64 = u64::MAX;
+
 // Returns `floor(value/2) + 1`
 pub fn majority(value: usize) -> usize {
     value / 2 + 1
@@ -41,34 +46,126 @@ pub fn supermajority(value: usize) -> usize {
     sm.ceil() as usize
 }
 
-/// Returns the quorum of a Ratification committee
-pub fn ratification_quorum() -> usize {
-    supermajority(RATIFICATION_COMMITTEE_CREDITS)
+/// Returns if the next iteration generator needs to be excluded
+pub fn exclude_next_generator(iter: u8) -> bool {
+    iter < CONSENSUS_MAX_ITER - 1
 }
 
-/// Returns the quorum of a Validation committee
-pub fn validation_quorum() -> usize {
-    supermajority(VALIDATION_COMMITTEE_CREDITS)
+/// Consensus parameters that can differ from one network to another.
+///
+/// The constants above are the values of the mainnet, which are used by
+/// default. Private networks can override them from their chain config, but
+/// every provisioner of a network must run with the same parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsensusParams {
+    /// Total credits of the Validation committee
+    pub validation_committee_credits: usize,
+    /// Total credits of the Ratification committee
+    pub ratification_committee_credits: usize,
+    /// Maximum number of transactions included in a block
+    pub max_number_of_transactions: usize,
+    /// Maximum size of a block, in bytes
+    pub max_block_size: usize,
+    /// Lower bound of the step timeouts
+    pub min_step_timeout: Duration,
+    /// Upper bound of the step timeouts
+    pub max_step_timeout: Duration,
+    /// Increase of a step timeout after it expires
+    pub timeout_increase: Duration,
+    /// Iteration from which the emergency mode is enabled
+    pub emergency_mode_iteration_threshold: u8,
+    /// Minimum time between two blocks, in seconds
+    pub minimum_block_time: u64,
+    /// Height from which faults of double candidates are produced and
+    /// accepted
+    pub candidate_fault_activation_height: u64,
 }
 
-/// Returns the number of credits beyond the quorum for a Validation committee
-pub fn validation_extra() -> usize {
-    VALIDATION_COMMITTEE_CREDITS - validation_quorum()
-}
+impl ConsensusParams {
+    /// Parameters of the mainnet
+    pub const MAINNET: Self = Self {
+        validation_committee_credits: VALIDATION_COMMITTEE_CREDITS,
+        ratification_committee_credits: RATIFICATION_COMMITTEE_CREDITS,
+        max_number_of_transactions: MAX_NUMBER_OF_TRANSACTIONS,
+        max_block_size: MAX_BLOCK_SIZE,
+        min_step_timeout: MIN_STEP_TIMEOUT,
+        max_step_timeout: MAX_STEP_TIMEOUT,
+        timeout_increase: TIMEOUT_INCREASE,
+        emergency_mode_iteration_threshold: EMERGENCY_MODE_ITERATION_THRESHOLD,
+        minimum_block_time: MINIMUM_BLOCK_TIME,
+        candidate_fault_activation_height: CANDIDATE_FAULT_ACTIVATION_HEIGHT,
+    };
+
+    /// Returns the total credits of the committee of the given step
+    pub fn committee_credits(&self, step: StepName) -> usize {
+        match step {
+            StepName::Proposal => PROPOSAL_COMMITTEE_CREDITS,
+            StepName::Validation => self.validation_committee_credits,
+            StepName::Ratification => self.ratification_committee_credits,
+        }
+    }
 
-/// Returns the number of credits beyond the quorum for a Ratification committee
-pub fn ratification_extra() -> usize {
-    RATIFICATION_COMMITTEE_CREDITS - ratification_quorum()
-}
+    /// Returns the quorum of a Ratification committee
+    pub fn ratification_quorum(&self) -> usize {
+        supermajority(self.ratification_committee_credits)
+    }
+
+    /// Returns the quorum of a Validation committee
+    pub fn validation_quorum(&self) -> usize {
+        supermajority(self.validation_committee_credits)
+    }
+
+    /// Returns the number of credits beyond the quorum for a Validation
+    /// committee
+    pub fn validation_extra(&self) -> usize {
+        self.validation_committee_credits - self.validation_quorum()
+    }
+
+    /// Returns the number of credits beyond the quorum for a Ratification
+    /// committee
+    pub fn ratification_extra(&self) -> usize {
+        self.ratification_committee_credits - self.ratification_quorum()
+    }
+
+    /// Returns whether the current iteration is an emergency iteration
+    pub fn is_emergency_iter(&self, iter: u8) -> bool {
+        iter >= self.emergency_mode_iteration_threshold
+    }
 
-/// Returns whether the current iteration is an emergency iteration
-pub fn is_emergency_iter(iter: u8) -> bool {
-    iter >= EMERGENCY_MODE_ITERATION_THRESHOLD
+    /// Returns whether faults of double candidates are enabled at the given
+    /// height
+    pub fn candidate_faults_enabled(&self, height: u64) -> bool {
+        height >= self.candidate_fault_activation_height
+    }
+
+    /// Checks that the parameters can be run by the consensus
+    pub fn validate(&self) -> Result<(), &'static str> {
+        // Votes of a committee are tracked in a 64 bits bitset
+        let credits = 1..=u64::BITS as usize;
+        if !credits.contains(&self.validation_committee_credits)
+            || !credits.contains(&self.ratification_committee_credits)
+        {
+            return Err("committee credits must be between 1 and 64");
+        }
+        if self.max_number_of_transactions == 0 || self.max_block_size == 0 {
+            return Err("block limits must be greater than zero");
+        }
+        if self.min_step_timeout.is_zero()
+            || self.min_step_timeout > self.max_step_timeout
+        {
+            return Err("step timeouts must be a non-empty range");
+        }
+        if self.emergency_mode_iteration_threshold >= CONSENSUS_MAX_ITER {
+            return Err("emergency mode must start before the last iteration");
+        }
+        Ok(())
+    }
 }
 
-/// Returns if the next iteration generator needs to be excluded
-pub fn exclude_next_generator(iter: u8) -> bool {
-    iter < CONSENSUS_MAX_ITER - 1
+impl Default for ConsensusParams {
+    fn default() -> Self {
+        Self::MAINNET
+    }
 }
 
 #[cfg(test)]
@@ -87,10 +184,41 @@ mod tests {
 
     #[test]
     fn test_quorums() {
+        let params = ConsensusParams::default();
         assert_eq!(majority(VALIDATION_COMMITTEE_CREDITS), 33);
-        assert_eq!(validation_quorum(), 43);
-        assert_eq!(ratification_quorum(), 43);
-        assert_eq!(validation_extra(), 21);
-        assert_eq!(ratification_extra(), 21);
+        assert_eq!(params.validation_quorum(), 43);
+        assert_eq!(params.ratification_quorum(), 43);
+        assert_eq!(params.validation_extra(), 21);
+        assert_eq!(params.ratification_extra(), 21);
+    }
+
+    #[test]
+    fn test_params_validation() {
+        assert_eq!(ConsensusParams::MAINNET.validate(), Ok(()));
+
+        let devnet = ConsensusParams {
+            validation_committee_credits: 8,
+            ratification_committee_credits: 8,
+            min_step_timeout: Duration::from_secs(1),
+            max_step_timeout: Duration::from_secs(5),
+            minimum_block_time: 1,
+            ..Default::default()
+        };
+        assert_eq!(devnet.validate(), Ok(()));
+        assert_eq!(devnet.validation_quorum(), 6);
+        assert_eq!(devnet.validation_extra(), 2);
+
+        let too_many_credits = ConsensusParams {
+            validation_committee_credits: 65,
+            ..Default::default()
+        };
+        assert!(too_many_credits.validate().is_err());
+
+        let empty_timeouts = ConsensusParams {
+            min_step_timeout: Duration::from_secs(10),
+            max_step_timeout: Duration::from_secs(5),
+            ..Default::default()
+        };
+        assert!(empty_timeouts.validate().is_err());
     }
 }
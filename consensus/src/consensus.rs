@@ -5,13 +5,14 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::commons::{Database, QuorumMsgSender, RoundUpdate};
-use crate::config::{CONSENSUS_MAX_ITER, EMERGENCY_MODE_ITERATION_THRESHOLD};
+use crate::config::CONSENSUS_MAX_ITER;
 use crate::errors::ConsensusError;
 use crate::operations::Operations;
 use crate::phase::Phase;
 
 use node_data::message::{AsyncQueue, Message, Topics};
 
+use crate::equivocation::EquivocationRegistry;
 use crate::execution_ctx::ExecutionCtx;
 use crate::proposal;
 use crate::queue::MsgRegistry;
@@ -38,6 +39,10 @@ pub struct Consensus<T: Operations, D: Database> {
     /// msgs are pending to be handled in a future round/step.
     future_msgs: Arc<Mutex<MsgRegistry<Message>>>,
 
+    /// equivocations records the signed messages to detect the provisioners
+    /// equivocating, and the faults to include in the next candidates.
+    equivocations: Arc<Mutex<EquivocationRegistry>>,
+
     /// Reference to the executor of any EST-related call
     executor: Arc<T>,
 
@@ -59,6 +64,7 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
         inbound: AsyncQueue<Message>,
         outbound: AsyncQueue<Message>,
         future_msgs: Arc<Mutex<MsgRegistry<Message>>>,
+        equivocations: Arc<Mutex<EquivocationRegistry>>,
         executor: Arc<T>,
         db: Arc<Mutex<D>>,
     ) -> Self {
@@ -66,6 +72,7 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
             inbound,
             outbound,
             future_msgs,
+            equivocations,
             executor,
             db,
         }
@@ -131,6 +138,7 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
         let inbound = self.inbound.clone();
         let outbound = self.outbound.clone();
         let future_msgs = self.future_msgs.clone();
+        let equivocations = self.equivocations.clone();
         let executor = self.executor.clone();
         let db = self.db.clone();
 
@@ -138,12 +146,13 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
             if ru.round > 0 {
                 future_msgs.lock().await.remove_msgs_by_round(ru.round - 1);
             }
+            equivocations.lock().await.on_new_round(ru.round);
 
             let sv_registry =
                 Arc::new(Mutex::new(AttInfoRegistry::new(ru.clone())));
 
             let proposal_handler = Arc::new(Mutex::new(
-                proposal::handler::ProposalHandler::new(db.clone()),
+                proposal::handler::ProposalHandler::new(db.clone(), ru.params),
             ));
 
             let validation_handler = Arc::new(Mutex::new(
@@ -184,13 +193,16 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
                 ratification_handler,
                 proposal_handler,
                 ru.base_timeouts.clone(),
+                ru.params,
             );
 
             let (prev_block_hash, saved_iter) =
                 db.lock().await.get_last_iter().await;
 
-            let saved_iter =
-                cmp::min(EMERGENCY_MODE_ITERATION_THRESHOLD, saved_iter);
+            let saved_iter = cmp::min(
+                ru.params.emergency_mode_iteration_threshold,
+                saved_iter,
+            );
 
             if ru.hash() == prev_block_hash {
                 // If starting from `saved_iter`, we regenerate all committees
@@ -234,6 +246,7 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
                         inbound.clone(),
                         outbound.clone(),
                         future_msgs.clone(),
+                        equivocations.clone(),
                         provisioners.as_ref(),
                         ru.clone(),
                         iter,
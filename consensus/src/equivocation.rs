@@ -0,0 +1,336 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+
+use execution_core::stake::EPOCH;
+use node_data::bls::PublicKeyBytes;
+use node_data::ledger::{Fault, FaultData, Header};
+use node_data::message::payload::Vote;
+use node_data::message::{Message, Payload};
+use tracing::warn;
+
+use crate::config::ConsensusParams;
+
+/// Message signed by a provisioner for a given round, iteration and step
+#[derive(Debug, Clone)]
+enum Signed {
+    Candidate(FaultData<Header>),
+    Validation(FaultData<Vote>),
+    Ratification(FaultData<Vote>),
+}
+
+impl Signed {
+    /// Candidates are only recorded once their faults are enabled, and if
+    /// their hash matches their header, since the fault recomputes it.
+    fn from_msg(msg: &Message, params: &ConsensusParams) -> Option<Self> {
+        let signed = match &msg.payload {
+            Payload::Candidate(c) => {
+                let header = c.candidate.header();
+                if !params.candidate_faults_enabled(header.height)
+                    || header.compute_hash().ok()? != header.hash
+                {
+                    return None;
+                }
+                Self::Candidate(c.as_ref().into())
+            }
+            Payload::Validation(v) => Self::Validation(v.into()),
+            Payload::Ratification(r) => Self::Ratification(r.into()),
+            _ => return None,
+        };
+        Some(signed)
+    }
+
+    /// Returns the fault proving the signer signed both messages, if they
+    /// differ while referring to the same tip.
+    fn conflict(&self, other: &Self) -> Option<Fault> {
+        use Signed::*;
+        let fault = match (self, other) {
+            (Candidate(a), Candidate(b))
+                if a.header() == b.header()
+                    && a.data().hash != b.data().hash =>
+            {
+                Fault::DoubleCandidateHeader(a.clone(), b.clone())
+            }
+            (Validation(a), Validation(b)) if conflicting(a, b) => {
+                Fault::DoubleValidationVote(a.clone(), b.clone())
+            }
+            (Ratification(a), Ratification(b)) if conflicting(a, b) => {
+                Fault::DoubleRatificationVote(a.clone(), b.clone())
+            }
+            _ => return None,
+        };
+        Some(fault)
+    }
+}
+
+fn conflicting<V: PartialEq>(a: &FaultData<V>, b: &FaultData<V>) -> bool {
+    a.header() == b.header() && a.data() != b.data()
+}
+
+struct Slot {
+    signed: Signed,
+    /// Set once a fault has been produced for this slot, so that its signer
+    /// is not slashed more than once for the same step
+    faulted: bool,
+}
+
+/// Registry of the messages signed by the provisioners, detecting the ones
+/// that sign conflicting messages for the same round, iteration and step.
+///
+/// Every message is expected to be recorded once its signature has been
+/// verified, so that the produced [`Fault`]s are valid evidence of an
+/// equivocation.
+#[derive(Default)]
+pub struct EquivocationRegistry {
+    /// Messages by round, step and signer
+    msgs: BTreeMap<u64, HashMap<(u8, PublicKeyBytes), Slot>>,
+    /// Faults detected but not yet included in a block
+    faults: Vec<Fault>,
+    params: ConsensusParams,
+}
+
+impl EquivocationRegistry {
+    pub fn new(params: ConsensusParams) -> Self {
+        Self {
+            params,
+            ..Default::default()
+        }
+    }
+
+    /// Records a message with a verified signature, returning the fault
+    /// produced if its signer already signed a different message for the
+    /// same round, iteration and step.
+    ///
+    /// Messages of emergency iterations are not recorded, since multiple
+    /// votes are allowed there.
+    pub fn record(&mut self, msg: &Message) -> Option<Fault> {
+        if self.params.is_emergency_iter(msg.header.iteration) {
+            return None;
+        }
+        let signed = Signed::from_msg(msg, &self.params)?;
+        let signer = *msg.get_signer()?.bytes();
+
+        let round = self.msgs.entry(msg.header.round).or_default();
+        let slot = match round.entry((msg.get_step(), signer)) {
+            Entry::Vacant(entry) => {
+                entry.insert(Slot {
+                    signed,
+                    faulted: false,
+                });
+                return None;
+            }
+            Entry::Occupied(entry) => entry.into_mut(),
+        };
+
+        if slot.faulted {
+            return None;
+        }
+        let fault = slot.signed.conflict(&signed)?;
+        slot.faulted = true;
+
+        warn!(
+            event = "equivocation detected",
+            signer = signer.to_bs58(),
+            round = msg.header.round,
+            iter = msg.header.iteration,
+            step = msg.get_step(),
+            topic = ?msg.topic(),
+        );
+
+        if self.faults.iter().any(|f| f.same(&fault)) {
+            return None;
+        }
+        self.faults.push(fault.clone());
+        Some(fault)
+    }
+
+    /// Checks if the message conflicts with a recorded one, meaning that it
+    /// would produce a fault once its signature is verified.
+    pub fn conflicts(&self, msg: &Message) -> bool {
+        if self.params.is_emergency_iter(msg.header.iteration) {
+            return false;
+        }
+        let (Some(signed), Some(signer)) =
+            (Signed::from_msg(msg, &self.params), msg.get_signer())
+        else {
+            return false;
+        };
+
+        self.msgs
+            .get(&msg.header.round)
+            .and_then(|round| round.get(&(msg.get_step(), *signer.bytes())))
+            .is_some_and(|slot| {
+                !slot.faulted && slot.signed.conflict(&signed).is_some()
+            })
+    }
+
+    /// Faults detected but not yet included in a block
+    pub fn faults(&self) -> &[Fault] {
+        &self.faults
+    }
+
+    /// Forgets a fault, either because it has been included in a block or
+    /// because it cannot be anymore.
+    pub fn remove_fault(&mut self, fault: &Fault) {
+        self.faults.retain(|f| !f.same(fault));
+    }
+
+    /// Forgets the messages of the rounds before the given one, together
+    /// with the faults that expired.
+    pub fn on_new_round(&mut self, round: u64) {
+        self.msgs = self.msgs.split_off(&round);
+
+        let expiry = round.saturating_sub(EPOCH);
+        self.faults.retain(|f| {
+            let header = match f {
+                Fault::DoubleCandidate(a, _) => a.header(),
+                Fault::DoubleCandidateHeader(a, _) => a.header(),
+                Fault::DoubleRatificationVote(a, _)
+                | Fault::DoubleValidationVote(a, _) => a.header(),
+            };
+            header.round >= expiry
+        });
+    }
+
+    /// Returns the total number of recorded messages.
+    pub fn msg_count(&self) -> usize {
+        self.msgs.values().map(HashMap::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons::RoundUpdate;
+    use crate::signer::{sign_message, InMemorySigner};
+    use execution_core::signatures::bls::SecretKey as BlsSecretKey;
+    use node_data::ledger::{Block, Header};
+    use node_data::message::payload::{Candidate, Validation};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::sync::Arc;
+
+    fn round_update(seed: u64) -> RoundUpdate {
+        let sk = BlsSecretKey::random(&mut StdRng::seed_from_u64(seed));
+        let signer = Arc::new(InMemorySigner::new(sk));
+        RoundUpdate::new(
+            signer,
+            &Header::default(),
+            ConsensusParams::default(),
+            HashMap::new(),
+            vec![],
+        )
+    }
+
+    async fn build_validation_payload(
+        vote: Vote,
+        ru: &RoundUpdate,
+        iteration: u8,
+    ) -> Validation {
+        crate::build_validation_payload(vote, ru, iteration)
+            .await
+            .expect("signing should succeed")
+    }
+
+    async fn candidate(ru: &RoundUpdate, timestamp: u64) -> Message {
+        let header = Header {
+            height: ru.round,
+            prev_block_hash: ru.hash(),
+            timestamp,
+            generator_bls_pubkey: *ru.pubkey_bls.bytes(),
+            ..Default::default()
+        };
+        let block = Block::new(header, vec![], vec![]).expect("valid block");
+        let mut candidate = Candidate { candidate: block };
+        sign_message(ru.signer.as_ref(), &mut candidate)
+            .await
+            .expect("signing should succeed");
+        candidate.into()
+    }
+
+    #[tokio::test]
+    async fn double_validation_vote() {
+        let ru = round_update(1);
+        let mut registry = EquivocationRegistry::default();
+
+        let valid =
+            build_validation_payload(Vote::Valid([1; 32]), &ru, 0).await;
+        let invalid =
+            build_validation_payload(Vote::Invalid([1; 32]), &ru, 0).await;
+        let other = build_validation_payload(Vote::NoCandidate, &ru, 0).await;
+
+        assert!(registry.record(&valid.clone().into()).is_none());
+        assert!(registry.record(&valid.into()).is_none());
+        assert!(registry.conflicts(&invalid.clone().into()));
+
+        let fault = registry
+            .record(&invalid.into())
+            .expect("conflicting votes should produce a fault");
+        assert!(matches!(fault, Fault::DoubleValidationVote(..)));
+        fault.validate(ru.round).expect("fault should be valid");
+
+        // The signer is not slashed twice for the same step
+        assert!(registry.record(&other.into()).is_none());
+        assert_eq!(registry.faults().len(), 1);
+
+        // Votes for another iteration do not conflict
+        let next_iter =
+            build_validation_payload(Vote::NoCandidate, &ru, 1).await;
+        assert!(registry.record(&next_iter.into()).is_none());
+
+        registry.remove_fault(&fault);
+        assert!(registry.faults().is_empty());
+
+        registry.on_new_round(ru.round + 1);
+        assert_eq!(registry.msg_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn double_candidate() {
+        let ru = round_update(2);
+
+        // Candidate faults are not produced before their activation
+        let mut registry = EquivocationRegistry::default();
+        assert!(registry.record(&candidate(&ru, 1).await).is_none());
+        assert!(registry.record(&candidate(&ru, 2).await).is_none());
+
+        let mut registry = EquivocationRegistry::new(ConsensusParams {
+            candidate_fault_activation_height: ru.round,
+            ..Default::default()
+        });
+        assert!(registry.record(&candidate(&ru, 1).await).is_none());
+        let fault = registry
+            .record(&candidate(&ru, 2).await)
+            .expect("conflicting candidates should produce a fault");
+
+        assert!(matches!(fault, Fault::DoubleCandidateHeader(..)));
+        fault.validate(ru.round).expect("fault should be valid");
+
+        // The evidence is bound to the header the generator signed, not to
+        // the hash it claims
+        let Fault::DoubleCandidateHeader(a, b) = fault else {
+            unreachable!()
+        };
+        let mut tampered = candidate(&ru, 3).await;
+        if let Payload::Candidate(c) = &mut tampered.payload {
+            let header = Header {
+                timestamp: 4,
+                ..c.candidate.header().clone()
+            };
+            c.candidate =
+                Block::new(header, vec![], vec![]).expect("valid block");
+        }
+        let Payload::Candidate(c) = &tampered.payload else {
+            unreachable!()
+        };
+        let forged = Fault::DoubleCandidateHeader(a, c.as_ref().into());
+        assert!(forged.validate(ru.round).is_err());
+        let forged = Fault::DoubleCandidateHeader(b.clone(), b);
+        assert!(forged.validate(ru.round).is_err());
+    }
+}
@@ -15,6 +15,9 @@ use node_data::{
 };
 use thiserror::Error;
 
+use crate::signer::SignerError;
+use crate::slashing_protection::SigningError;
+
 #[derive(Debug, Clone, Copy, Error)]
 pub enum StepSigError {
     #[error("Failed to reach a quorum")]
@@ -82,6 +85,10 @@ pub enum OperationError {
     InvalidIterationInfo(io::Error),
     #[error("Invalid Faults {0}")]
     InvalidFaults(InvalidFault),
+    #[error("Refused to sign {0}")]
+    SigningRefused(SigningError),
+    #[error("Failed to sign {0}")]
+    Signer(SignerError),
 }
 
 #[derive(Debug, Error)]
@@ -183,6 +190,18 @@ impl From<io::Error> for OperationError {
     }
 }
 
+impl From<SignerError> for OperationError {
+    fn from(value: SignerError) -> Self {
+        Self::Signer(value)
+    }
+}
+
+impl From<SigningError> for OperationError {
+    fn from(value: SigningError) -> Self {
+        Self::SigningRefused(value)
+    }
+}
+
 impl From<InvalidFault> for OperationError {
     fn from(value: InvalidFault) -> Self {
         Self::InvalidFaults(value)
@@ -6,6 +6,7 @@
 
 use crate::commons::{Database, QuorumMsgSender, RoundUpdate};
 
+use crate::equivocation::EquivocationRegistry;
 use crate::errors::ConsensusError;
 use crate::iteration_ctx::IterationCtx;
 use crate::msg_handler::{HandleMsgOutput, MsgHandler};
@@ -17,11 +18,11 @@ use crate::user::provisioners::Provisioners;
 
 use node_data::bls::PublicKeyBytes;
 use node_data::ledger::{to_str, Block};
-use node_data::message::{AsyncQueue, Message, Payload};
+use node_data::message::{AsyncQueue, Message, Payload, Status};
 
 use node_data::StepName;
 
-use crate::config::{is_emergency_iter, CONSENSUS_MAX_ITER};
+use crate::config::CONSENSUS_MAX_ITER;
 use crate::ratification::step::RatificationStep;
 use crate::validation::step::ValidationStep;
 use node_data::message::payload::{QuorumType, ValidationResult, Vote};
@@ -41,6 +42,7 @@ pub struct ExecutionCtx<'a, T, DB: Database> {
     pub inbound: AsyncQueue<Message>,
     pub outbound: AsyncQueue<Message>,
     pub future_msgs: Arc<Mutex<MsgRegistry<Message>>>,
+    pub equivocations: Arc<Mutex<EquivocationRegistry>>,
 
     /// State-related fields
     pub provisioners: &'a Provisioners,
@@ -65,6 +67,7 @@ impl<'a, T: Operations + 'static, DB: Database> ExecutionCtx<'a, T, DB> {
         inbound: AsyncQueue<Message>,
         outbound: AsyncQueue<Message>,
         future_msgs: Arc<Mutex<MsgRegistry<Message>>>,
+        equivocations: Arc<Mutex<EquivocationRegistry>>,
         provisioners: &'a Provisioners,
         round_update: RoundUpdate,
         iteration: u8,
@@ -78,6 +81,7 @@ impl<'a, T: Operations + 'static, DB: Database> ExecutionCtx<'a, T, DB> {
             inbound,
             outbound,
             future_msgs,
+            equivocations,
             provisioners,
             round_update,
             iteration,
@@ -259,13 +263,51 @@ impl<'a, T: Operations + 'static, DB: Database> ExecutionCtx<'a, T, DB> {
 
     /// Process messages from past
     ///
-    /// Ignores messages that do not originate from emergency iteration of
-    /// current round
-    async fn process_past_events(&mut self, msg: Message) {
-        if msg.header.round == self.round_update.round
-            && is_emergency_iter(msg.header.iteration)
+    /// Ignores messages that do not originate from the current round.
+    /// Messages of non-emergency iterations are only checked for
+    /// equivocations.
+    async fn process_past_events<C: MsgHandler>(&mut self, msg: Message) {
+        if msg.header.round != self.round_update.round {
+            return;
+        }
+        if self
+            .round_update
+            .params
+            .is_emergency_iter(msg.header.iteration)
         {
             self.on_emergency_mode(msg).await;
+        } else {
+            self.record_past_equivocation::<C>(&msg).await;
+        }
+    }
+
+    /// Records a message of a past iteration or step of the current round if
+    /// it conflicts with one already recorded.
+    ///
+    /// Past messages are not verified by the phase handlers, thus the
+    /// signature is checked here, and only for the conflicting ones.
+    async fn record_past_equivocation<C: MsgHandler>(&self, msg: &Message) {
+        let mut equivocations = self.equivocations.lock().await;
+        if !equivocations.conflicts(msg) {
+            return;
+        }
+
+        let verified = C::verify_message(
+            msg,
+            &self.round_update,
+            &self.iter_ctx.committees,
+            Status::Past,
+        );
+        if verified.is_ok() {
+            equivocations.record(msg);
+        }
+    }
+
+    /// Records a message of the current round whose signature has been
+    /// verified, to detect its signer equivocating.
+    async fn record_signed(&self, msg: &Message) {
+        if msg.header.round == self.round_update.round {
+            self.equivocations.lock().await.record(msg);
         }
     }
 
@@ -368,6 +410,8 @@ impl<'a, T: Operations + 'static, DB: Database> ExecutionCtx<'a, T, DB> {
 
         match valid {
             Ok(_) => {
+                self.record_signed(&msg).await;
+
                 // Re-publish the returned message
                 self.outbound.try_send(msg.clone());
             }
@@ -380,6 +424,12 @@ impl<'a, T: Operations + 'static, DB: Database> ExecutionCtx<'a, T, DB> {
                 // Re-propagate messages from future iterations of the current
                 // round
                 if msg.header.round == self.round_update.round {
+                    // Messages of the current round are verified only if the
+                    // committee of their step is known
+                    let step = msg.get_step();
+                    if self.iter_ctx.committees.get_committee(step).is_some() {
+                        self.record_signed(&msg).await;
+                    }
                     self.outbound.try_send(msg.clone());
                 }
 
@@ -392,7 +442,7 @@ impl<'a, T: Operations + 'static, DB: Database> ExecutionCtx<'a, T, DB> {
                 return None;
             }
             Err(ConsensusError::PastEvent) => {
-                self.process_past_events(msg).await;
+                self.process_past_events::<C>(msg).await;
                 return None;
             }
             Err(ConsensusError::InvalidValidation(QuorumType::NoQuorum)) => {
@@ -484,6 +534,8 @@ impl<'a, T: Operations + 'static, DB: Database> ExecutionCtx<'a, T, DB> {
                     &self.iter_ctx.committees,
                 );
                 if ret.is_ok() {
+                    self.record_signed(&msg).await;
+
                     // Re-publish a drained message
                     debug!(
                         event = "republish",
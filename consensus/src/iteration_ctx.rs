@@ -7,9 +7,7 @@
 use crate::commons::{Database, RoundUpdate, TimeoutSet};
 use std::cmp;
 
-use crate::config::{
-    exclude_next_generator, MAX_STEP_TIMEOUT, TIMEOUT_INCREASE,
-};
+use crate::config::{exclude_next_generator, ConsensusParams};
 use crate::msg_handler::HandleMsgOutput;
 use crate::msg_handler::MsgHandler;
 
@@ -81,6 +79,8 @@ pub struct IterationCtx<DB: Database> {
 
     /// Implements the adaptive timeout algorithm
     timeouts: TimeoutSet,
+
+    params: ConsensusParams,
 }
 
 impl<DB: Database> IterationCtx<DB> {
@@ -93,6 +93,7 @@ impl<DB: Database> IterationCtx<DB> {
         >,
         proposal_handler: Arc<Mutex<proposal::handler::ProposalHandler<DB>>>,
         timeouts: TimeoutSet,
+        params: ConsensusParams,
     ) -> Self {
         Self {
             round,
@@ -102,6 +103,7 @@ impl<DB: Database> IterationCtx<DB> {
             ratification_handler,
             committees: Default::default(),
             timeouts,
+            params,
             proposal_handler,
         }
     }
@@ -127,8 +129,10 @@ impl<DB: Database> IterationCtx<DB> {
         let curr_step_timeout =
             self.timeouts.get_mut(&step_name).expect("valid timeout");
 
-        *curr_step_timeout =
-            cmp::min(MAX_STEP_TIMEOUT, curr_step_timeout.add(TIMEOUT_INCREASE));
+        *curr_step_timeout = cmp::min(
+            self.params.max_step_timeout,
+            curr_step_timeout.add(self.params.timeout_increase),
+        );
     }
 
     /// Calculates and returns the adjusted timeout for the specified step
@@ -146,7 +150,12 @@ impl<DB: Database> IterationCtx<DB> {
         exclusion: Vec<PublicKeyBytes>,
     ) -> sortition::Config {
         sortition::Config::new(
-            seed, self.round, self.iter, step_name, exclusion,
+            seed,
+            self.round,
+            self.iter,
+            step_name,
+            self.params.committee_credits(step_name),
+            exclusion,
         )
     }
 
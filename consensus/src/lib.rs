@@ -6,6 +6,7 @@
 
 extern crate core;
 
+pub mod clock;
 pub mod commons;
 pub mod consensus;
 pub mod errors;
@@ -13,6 +14,7 @@ pub mod user;
 
 mod aggregator;
 pub mod config;
+pub mod equivocation;
 mod execution_ctx;
 mod msg_handler;
 pub mod operations;
@@ -21,6 +23,8 @@ mod proposal;
 pub mod queue;
 pub mod quorum;
 mod ratification;
+pub mod signer;
+pub mod slashing_protection;
 mod step_votes_reg;
 mod validation;
 
@@ -5,7 +5,6 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::commons::RoundUpdate;
-use crate::config::is_emergency_iter;
 use crate::errors::ConsensusError;
 use crate::iteration_ctx::RoundCommittees;
 use crate::proposal;
@@ -58,7 +57,7 @@ pub trait MsgHandler {
         let msg_tip = msg.header.prev_block_hash;
         match msg.compare(ru.round, current_iteration, step) {
             Status::Past => {
-                if is_emergency_iter(msg.header.iteration) {
+                if ru.params.is_emergency_iter(msg.header.iteration) {
                     Self::verify_message(
                         msg,
                         ru,
@@ -135,6 +134,7 @@ pub trait MsgHandler {
                         proposal::handler::verify_stateless(
                             c,
                             round_committees,
+                            &ru.params,
                         )?;
                     }
                     node_data::message::Payload::Quorum(_) => {}
@@ -27,6 +27,7 @@ pub struct CallParams {
     pub to_slash: Vec<Slash>,
     pub voters_pubkey: Vec<Voter>,
     pub max_txs_bytes: usize,
+    pub max_txs: usize,
 }
 
 #[derive(Default)]
@@ -6,16 +6,17 @@
 
 use crate::commons::RoundUpdate;
 use crate::operations::{CallParams, Operations, Voter};
+use crate::signer::{sign_message, ConsensusSigner, SignRequest};
 use node_data::ledger::{to_str, Block, Fault, IterationsInfo, Seed, Slash};
 use std::cmp::max;
 
 use crate::merkle::merkle_root;
 
-use crate::config::{MAX_BLOCK_SIZE, MAX_NUMBER_OF_FAULTS, MINIMUM_BLOCK_TIME};
+use crate::config::MAX_NUMBER_OF_FAULTS;
 use dusk_bytes::Serializable;
+use node_data::ledger;
 use node_data::message::payload::Candidate;
-use node_data::message::{Message, StepMessage, BLOCK_HEADER_VERSION};
-use node_data::{get_current_timestamp, ledger};
+use node_data::message::{Message, BLOCK_HEADER_VERSION};
 use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, info};
@@ -34,12 +35,11 @@ impl<T: Operations> Generator<T> {
         ru: &RoundUpdate,
         iteration: u8,
         failed_iterations: IterationsInfo,
+        faults: &[Fault],
     ) -> Result<Message, crate::errors::OperationError> {
         // Sign seed
-        let seed: [u8; 48] = ru
-            .secret_key
-            .sign_multisig(ru.pubkey_bls.inner(), &ru.seed().inner()[..])
-            .to_bytes();
+        let seed = ru.seed();
+        let seed = ru.signer.sign(SignRequest::Seed(&seed)).await?.to_bytes();
 
         let start = Instant::now();
 
@@ -49,7 +49,7 @@ impl<T: Operations> Generator<T> {
                 Seed::from(seed),
                 iteration,
                 failed_iterations,
-                &[],
+                faults,
                 ru.att_voters(),
             )
             .await?;
@@ -64,9 +64,15 @@ impl<T: Operations> Generator<T> {
 
         debug!("block: {:?}", &candidate);
 
+        let header = ru.consensus_header(iteration);
+        let hash = candidate.header().hash;
+        ru.slashing_protection
+            .approve_candidate(&header, &hash)
+            .await?;
+
         let mut candidate = Candidate { candidate };
 
-        candidate.sign(&ru.secret_key, ru.pubkey_bls.inner());
+        sign_message(ru.signer.as_ref(), &mut candidate).await?;
 
         Ok(candidate.into())
     }
@@ -123,8 +129,12 @@ impl<T: Operations> Generator<T> {
 
         blk_header.faultroot = merkle_root(&faults_hashes);
 
-        // We know for sure that this operation cannot underflow
-        let max_txs_bytes = MAX_BLOCK_SIZE - header_size - faults_size;
+        // A block size too small for the header and the faults leaves no
+        // room for transactions
+        let max_txs_bytes = ru
+            .params
+            .max_block_size
+            .saturating_sub(header_size + faults_size);
 
         let call_params = CallParams {
             round: ru.round,
@@ -132,6 +142,7 @@ impl<T: Operations> Generator<T> {
             to_slash,
             voters_pubkey: voters.to_owned(),
             max_txs_bytes,
+            max_txs: ru.params.max_number_of_transactions,
         };
 
         let result =
@@ -145,8 +156,10 @@ impl<T: Operations> Generator<T> {
         let txs: Vec<_> = result.txs.into_iter().map(|t| t.inner).collect();
         blk_header.txroot = merkle_root(&tx_hashes[..]);
 
-        blk_header.timestamp =
-            max(ru.timestamp() + MINIMUM_BLOCK_TIME, get_current_timestamp());
+        blk_header.timestamp = max(
+            ru.timestamp() + ru.params.minimum_block_time,
+            ru.clock.now(),
+        );
 
         Block::new(blk_header, txs, faults.to_vec()).map_err(|e| {
             crate::errors::OperationError::InvalidEST(anyhow::anyhow!(
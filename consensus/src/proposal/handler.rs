@@ -5,10 +5,7 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::commons::{Database, RoundUpdate};
-use crate::config::{
-    is_emergency_iter, MAX_BLOCK_SIZE, MAX_NUMBER_OF_FAULTS,
-    MAX_NUMBER_OF_TRANSACTIONS,
-};
+use crate::config::{ConsensusParams, MAX_NUMBER_OF_FAULTS};
 use crate::errors::ConsensusError;
 use crate::merkle::merkle_root;
 use crate::msg_handler::{HandleMsgOutput, MsgHandler};
@@ -26,6 +23,7 @@ use tokio::sync::Mutex;
 
 pub struct ProposalHandler<D: Database> {
     pub(crate) db: Arc<Mutex<D>>,
+    params: ConsensusParams,
 }
 
 #[async_trait]
@@ -41,7 +39,7 @@ impl<D: Database> MsgHandler for ProposalHandler<D> {
         let generator = round_committees
             .get_generator(iteration)
             .expect("committee to be created before run");
-        super::handler::verify_new_block(p, &generator)?;
+        super::handler::verify_new_block(p, &generator, &self.params)?;
 
         Ok(())
     }
@@ -96,7 +94,7 @@ impl<D: Database> MsgHandler for ProposalHandler<D> {
         ru: &RoundUpdate,
         curr_iteration: u8,
     ) -> Option<Message> {
-        if is_emergency_iter(curr_iteration) {
+        if ru.params.is_emergency_iter(curr_iteration) {
             // While we are in Emergency mode but still the candidate is missing
             // then we should request it
             info!(
@@ -117,8 +115,8 @@ impl<D: Database> MsgHandler for ProposalHandler<D> {
 }
 
 impl<D: Database> ProposalHandler<D> {
-    pub(crate) fn new(db: Arc<Mutex<D>>) -> Self {
-        Self { db }
+    pub(crate) fn new(db: Arc<Mutex<D>>, params: ConsensusParams) -> Self {
+        Self { db, params }
     }
 
     fn unwrap_msg(msg: &Message) -> Result<&Candidate, ConsensusError> {
@@ -132,6 +130,7 @@ impl<D: Database> ProposalHandler<D> {
 fn verify_new_block(
     p: &Candidate,
     expected_generator: &PublicKeyBytes,
+    params: &ConsensusParams,
 ) -> Result<(), ConsensusError> {
     if expected_generator != p.sign_info().signer.bytes() {
         return Err(ConsensusError::NotCommitteeMember);
@@ -141,7 +140,7 @@ fn verify_new_block(
         .candidate
         .size()
         .map_err(|_| ConsensusError::UnknownBlockSize)?;
-    if candidate_size > MAX_BLOCK_SIZE {
+    if candidate_size > params.max_block_size {
         return Err(ConsensusError::InvalidBlockSize(candidate_size));
     }
 
@@ -154,7 +153,7 @@ fn verify_new_block(
         return Err(ConsensusError::InvalidBlockHash);
     }
 
-    if p.candidate.txs().len() > MAX_NUMBER_OF_TRANSACTIONS {
+    if p.candidate.txs().len() > params.max_number_of_transactions {
         return Err(ConsensusError::TooManyTransactions(
             p.candidate.txs().len(),
         ));
@@ -184,12 +183,13 @@ fn verify_new_block(
 pub fn verify_stateless(
     c: &Candidate,
     round_committees: &RoundCommittees,
+    params: &ConsensusParams,
 ) -> Result<(), ConsensusError> {
     let iteration = c.header().iteration;
     let generator = round_committees
         .get_generator(iteration)
         .expect("committee to be created before run");
-    verify_new_block(c, &generator)?;
+    verify_new_block(c, &generator, params)?;
 
     Ok(())
 }
@@ -4,13 +4,12 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use crate::commons::Database;
+use crate::commons::{Database, RoundUpdate};
 use crate::errors::ConsensusError;
 use crate::execution_ctx::ExecutionCtx;
 use crate::msg_handler::{HandleMsgOutput, MsgHandler};
 use crate::operations::Operations;
-use node_data::get_current_timestamp;
-use node_data::ledger::IterationsInfo;
+use node_data::ledger::{Fault, IterationsInfo};
 use node_data::message::Message;
 use std::cmp;
 use std::sync::Arc;
@@ -18,7 +17,6 @@ use std::time::Duration;
 use tokio::sync::Mutex;
 
 use crate::config;
-use crate::config::MINIMUM_BLOCK_TIME;
 use crate::proposal::block_generator::Generator;
 use crate::proposal::handler;
 use tracing::{debug, error, info};
@@ -68,18 +66,21 @@ impl<T: Operations + 'static, D: Database> ProposalStep<T, D> {
             let failed_attestations =
                 ctx.sv_registry.lock().await.get_failed_atts(iteration);
 
+            let faults = Self::pending_faults(&ctx).await;
+
             if let Ok(msg) = self
                 .bg
                 .generate_candidate_message(
                     &ctx.round_update,
                     ctx.iteration,
                     IterationsInfo::new(failed_attestations),
+                    &faults,
                 )
                 .await
             {
                 ctx.outbound.try_send(msg.clone());
 
-                Self::wait_until_next_slot(ctx.round_update.timestamp()).await;
+                Self::wait_until_next_slot(&ctx.round_update).await;
                 // register new candidate in local state
                 match self
                     .handler
@@ -99,7 +100,7 @@ impl<T: Operations + 'static, D: Database> ProposalStep<T, D> {
             }
         }
 
-        Self::wait_until_next_slot(ctx.round_update.timestamp()).await;
+        Self::wait_until_next_slot(&ctx.round_update).await;
 
         // handle queued messages for current round and step.
         if let Some(m) = ctx.handle_future_msgs(self.handler.clone()).await {
@@ -109,11 +110,38 @@ impl<T: Operations + 'static, D: Database> ProposalStep<T, D> {
         ctx.event_loop(self.handler.clone()).await
     }
 
+    /// Returns the faults detected by the equivocation registry that can be
+    /// included in a block of the current round.
+    ///
+    /// Faults that cannot be included anymore, e.g. because they have already
+    /// been included in a former block, are removed from the registry.
+    async fn pending_faults(ctx: &ExecutionCtx<'_, T, D>) -> Vec<Fault> {
+        let mut equivocations = ctx.equivocations.lock().await;
+        let mut faults = vec![];
+
+        for fault in equivocations.faults().to_vec() {
+            let round = ctx.round_update.round;
+            let single = std::slice::from_ref(&fault);
+            match ctx.client.verify_faults(round, single).await {
+                Ok(_) => faults.push(fault),
+                Err(err) => {
+                    debug!(event = "discarding fault", ?err);
+                    equivocations.remove_fault(&fault);
+                }
+            }
+        }
+
+        if !faults.is_empty() {
+            info!(event = "including faults", count = faults.len());
+        }
+        faults
+    }
+
     /// Waits until the next slot is reached
-    async fn wait_until_next_slot(tip_timestamp: u64) {
-        let current_time_secs = get_current_timestamp();
+    async fn wait_until_next_slot(ru: &RoundUpdate) {
+        let current_time_secs = ru.clock.now();
 
-        let next_slot_timestamp = tip_timestamp + MINIMUM_BLOCK_TIME;
+        let next_slot_timestamp = ru.timestamp() + ru.params.minimum_block_time;
         if current_time_secs >= next_slot_timestamp {
             return;
         }
@@ -17,7 +17,7 @@ use crate::user::cluster::Cluster;
 use crate::user::committee::{Committee, CommitteeSet};
 use crate::user::sortition;
 
-use crate::config::exclude_next_generator;
+use crate::config::{exclude_next_generator, ConsensusParams};
 use dusk_bytes::Serializable as BytesSerializable;
 use execution_core::signatures::bls::{
     MultisigPublicKey as BlsMultisigPublicKey,
@@ -32,6 +32,7 @@ pub async fn verify_step_votes(
     committees_set: &RwLock<CommitteeSet<'_>>,
     seed: Seed,
     step: StepName,
+    params: &ConsensusParams,
 ) -> Result<(QuorumResult, Vec<Voter>), StepSigError> {
     let round = header.round;
     let iteration = header.iteration;
@@ -55,8 +56,14 @@ pub async fn verify_step_votes(
         exclusion_list.push(next_generator);
     }
 
-    let cfg =
-        sortition::Config::new(seed, round, iteration, step, exclusion_list);
+    let cfg = sortition::Config::new(
+        seed,
+        round,
+        iteration,
+        step,
+        params.committee_credits(step),
+        exclusion_list,
+    );
 
     if committees_set.read().await.get(&cfg).is_none() {
         let _ = committees_set.write().await.get_or_create(&cfg);
@@ -189,10 +196,11 @@ pub async fn get_step_voters(
     committees_set: &RwLock<CommitteeSet<'_>>,
     seed: Seed,
     step: StepName,
+    params: &ConsensusParams,
 ) -> Vec<Voter> {
     // compute committee for `step`
     let committee =
-        get_step_committee(header, committees_set, seed, step).await;
+        get_step_committee(header, committees_set, seed, step, params).await;
 
     // extract quorum voters from `sv`
     let bitset = sv.bitset;
@@ -206,6 +214,7 @@ async fn get_step_committee(
     committees_set: &RwLock<CommitteeSet<'_>>,
     seed: Seed,
     step: StepName,
+    params: &ConsensusParams,
 ) -> Committee {
     let round = header.round;
     let iteration = header.iteration;
@@ -231,8 +240,14 @@ async fn get_step_committee(
         exclusion_list.push(next_generator);
     }
 
-    let cfg =
-        sortition::Config::new(seed, round, iteration, step, exclusion_list);
+    let cfg = sortition::Config::new(
+        seed,
+        round,
+        iteration,
+        step,
+        params.committee_credits(step),
+        exclusion_list,
+    );
 
     if committees_set.read().await.get(&cfg).is_none() {
         let _ = committees_set.write().await.get_or_create(&cfg);
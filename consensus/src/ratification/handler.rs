@@ -107,7 +107,7 @@ impl MsgHandler for RatificationHandler {
         // Collect vote, if msg payload is of ratification type
         let (sv, quorum_reached) = self
             .aggregator
-            .collect_vote(committee, &p)
+            .collect_vote(committee, &p, &ru.params)
             .map_err(|error| {
                 warn!(
                     event = "Cannot collect vote",
@@ -147,14 +147,15 @@ impl MsgHandler for RatificationHandler {
     async fn collect_from_past(
         &mut self,
         msg: Message,
-        _ru: &RoundUpdate,
+        ru: &RoundUpdate,
         committee: &Committee,
         generator: Option<PublicKeyBytes>,
     ) -> Result<HandleMsgOutput, ConsensusError> {
         let p = Self::unwrap_msg(msg)?;
 
         // Collect vote, if msg payload is ratification type
-        let collect_vote = self.aggregator.collect_vote(committee, &p);
+        let collect_vote =
+            self.aggregator.collect_vote(committee, &p, &ru.params);
 
         match collect_vote {
             Ok((sv, quorum_reached)) => {
@@ -5,20 +5,20 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::commons::{Database, RoundUpdate};
-use crate::config::is_emergency_iter;
 use crate::errors::ConsensusError;
 use crate::execution_ctx::ExecutionCtx;
 use crate::operations::Operations;
 
 use crate::msg_handler::{HandleMsgOutput, MsgHandler};
 use crate::ratification::handler;
+use crate::signer::{sign_message, SignerError};
 use node_data::message::payload::{self, QuorumType, ValidationResult};
-use node_data::message::{AsyncQueue, Message, Payload, StepMessage};
-use node_data::{get_current_timestamp, message};
+use node_data::message::{AsyncQueue, Message, Payload};
+use node_data::{message, StepName};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use tracing::{info, Instrument};
+use tracing::{error, info, Instrument};
 
 pub struct RatificationStep {
     handler: Arc<Mutex<handler::RatificationHandler>>,
@@ -30,14 +30,33 @@ impl RatificationStep {
         iteration: u8,
         result: &ValidationResult,
         outbound: AsyncQueue<Message>,
-    ) -> Message {
+    ) -> Option<Message> {
+        let header = ru.consensus_header(iteration);
+        let vote = result.vote();
+        let step = StepName::Ratification;
+        let approval = ru
+            .slashing_protection
+            .approve_vote(&header, step, vote)
+            .await;
+        if let Err(err) = approval {
+            error!(event = "refusing to sign", ?vote, %err);
+            return None;
+        }
+
         // Sign and construct ratification message
         let ratification =
-            self::build_ratification_payload(ru, iteration, result);
+            build_ratification_payload(ru, iteration, result).await;
+        let ratification = match ratification {
+            Ok(ratification) => ratification,
+            Err(err) => {
+                error!(event = "failed to sign", ?vote, %err);
+                return None;
+            }
+        };
 
         let msg = Message::from(ratification);
 
-        let is_emergency = is_emergency_iter(iteration);
+        let is_emergency = ru.params.is_emergency_iter(iteration);
 
         if result.quorum() == QuorumType::Valid || !is_emergency {
             // Publish ratification vote
@@ -47,15 +66,15 @@ impl RatificationStep {
             outbound.try_send(msg.clone());
         }
 
-        msg
+        Some(msg)
     }
 }
 
-pub fn build_ratification_payload(
+pub async fn build_ratification_payload(
     ru: &RoundUpdate,
     iteration: u8,
     result: &ValidationResult,
-) -> payload::Ratification {
+) -> Result<payload::Ratification, SignerError> {
     let header = message::ConsensusHeader {
         prev_block_hash: ru.hash(),
         round: ru.round,
@@ -68,10 +87,10 @@ pub fn build_ratification_payload(
         vote: *result.vote(),
         sign_info,
         validation_result: result.clone(),
-        timestamp: get_current_timestamp(),
+        timestamp: ru.clock.now(),
     };
-    ratification.sign(&ru.secret_key, ru.pubkey_bls.inner());
-    ratification
+    sign_message(ru.signer.as_ref(), &mut ratification).await?;
+    Ok(ratification)
 }
 
 impl RatificationStep {
@@ -135,11 +154,13 @@ impl RatificationStep {
             .await;
 
             // Collect my own vote
-            let res = handler
-                .collect(vote_msg, &ctx.round_update, committee, generator)
-                .await?;
-            if let HandleMsgOutput::Ready(m) = res {
-                return Ok(m);
+            if let Some(vote_msg) = vote_msg {
+                let res = handler
+                    .collect(vote_msg, &ctx.round_update, committee, generator)
+                    .await?;
+                if let HandleMsgOutput::Ready(m) = res {
+                    return Ok(m);
+                }
             }
         }
 
@@ -0,0 +1,159 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#[cfg(unix)]
+mod remote;
+
+use std::fmt;
+use std::io;
+
+use async_trait::async_trait;
+use dusk_bytes::Serializable;
+use execution_core::signatures::bls::{
+    MultisigSignature as BlsMultisigSignature, PublicKey as BlsPublicKey,
+    SecretKey as BlsSecretKey,
+};
+use node_data::bls::PublicKey;
+use node_data::ledger::{Header, Seed};
+use node_data::message::payload::{Candidate, Ratification, Validation};
+use node_data::message::StepMessage;
+use thiserror::Error;
+
+#[cfg(unix)]
+pub use remote::{serve_connection, RemoteSigner};
+
+#[derive(Debug, Error)]
+pub enum SignerError {
+    #[error("signer unreachable: {0}")]
+    Io(#[from] io::Error),
+    #[error("signer failed: {0}")]
+    Remote(String),
+    #[error("invalid signer response: {0}")]
+    InvalidResponse(String),
+}
+
+/// Data the consensus keys are asked to sign.
+///
+/// Only these requests are ever signed, so that a signer can check what it
+/// signs against its slashing protection.
+#[derive(Debug, Clone, Copy)]
+pub enum SignRequest<'a> {
+    /// Seed of the tip, signed to get the seed of a candidate block
+    Seed(&'a Seed),
+    /// Header of a candidate block
+    Candidate(&'a Header),
+    Validation(&'a Validation),
+    Ratification(&'a Ratification),
+}
+
+impl SignRequest<'_> {
+    /// Returns the bytes signed for this request
+    pub fn signable(&self) -> Vec<u8> {
+        match self {
+            Self::Seed(seed) => seed.inner().to_vec(),
+            Self::Candidate(header) => {
+                let mut signable = Candidate::SIGN_SEED.to_vec();
+                signable.extend_from_slice(&header.hash);
+                signable
+            }
+            Self::Validation(validation) => validation.signable(),
+            Self::Ratification(ratification) => ratification.signable(),
+        }
+    }
+}
+
+/// Signs the consensus messages with the consensus keys of the node.
+///
+/// Implementations do not need to expose the secret key, which can live in a
+/// separate process.
+#[async_trait]
+pub trait ConsensusSigner: fmt::Debug + Send + Sync {
+    /// Public key of the consensus keys
+    fn public_key(&self) -> &PublicKey;
+
+    /// Signs the request with the multisig scheme
+    async fn sign(
+        &self,
+        request: SignRequest<'_>,
+    ) -> Result<BlsMultisigSignature, SignerError>;
+}
+
+/// Consensus messages signed with the consensus keys
+pub trait SignedMessage: StepMessage {
+    fn sign_request(&self) -> SignRequest<'_>;
+}
+
+impl SignedMessage for Candidate {
+    fn sign_request(&self) -> SignRequest<'_> {
+        SignRequest::Candidate(self.candidate.header())
+    }
+}
+
+impl SignedMessage for Validation {
+    fn sign_request(&self) -> SignRequest<'_> {
+        SignRequest::Validation(self)
+    }
+}
+
+impl SignedMessage for Ratification {
+    fn sign_request(&self) -> SignRequest<'_> {
+        SignRequest::Ratification(self)
+    }
+}
+
+/// Signs a consensus message, setting the signature and the signer.
+pub async fn sign_message<M: SignedMessage>(
+    signer: &dyn ConsensusSigner,
+    msg: &mut M,
+) -> Result<(), SignerError> {
+    let signature = signer.sign(msg.sign_request()).await?;
+    msg.set_signature(signer.public_key(), signature.to_bytes().into());
+    Ok(())
+}
+
+/// Signer keeping the secret key in the memory of the node
+pub struct InMemorySigner {
+    secret_key: BlsSecretKey,
+    public_key: PublicKey,
+}
+
+impl InMemorySigner {
+    pub fn new(secret_key: BlsSecretKey) -> Self {
+        let public_key = PublicKey::new(BlsPublicKey::from(&secret_key));
+        Self {
+            secret_key,
+            public_key,
+        }
+    }
+
+    /// Signs the request right away
+    pub fn sign_now(&self, request: SignRequest<'_>) -> BlsMultisigSignature {
+        let pk = self.public_key.inner();
+        self.secret_key.sign_multisig(pk, &request.signable())
+    }
+}
+
+impl fmt::Debug for InMemorySigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemorySigner")
+            .field("public_key", &self.public_key.to_bs58())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl ConsensusSigner for InMemorySigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    async fn sign(
+        &self,
+        request: SignRequest<'_>,
+    ) -> Result<BlsMultisigSignature, SignerError> {
+        Ok(self.sign_now(request))
+    }
+}
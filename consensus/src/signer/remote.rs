@@ -0,0 +1,384 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Signing protocol spoken over a Unix socket with a signer process.
+//!
+//! Every request is framed as `kind (u8) | length (u32 BE) | payload`:
+//! - kind `0` requests the public key, with an empty payload;
+//! - kind `1` requests the signature of the 48 bytes seed in the payload;
+//! - kind `2` requests the signature of the candidate block header in the
+//!   payload;
+//! - kind `3` and `4` request the signature of the validation and ratification
+//!   messages in the payload.
+//!
+//! Every response is framed as `status (u8) | length (u32 BE) | payload`.
+//! On success (status `0`) the payload is the 96 bytes public key or the 48
+//! bytes signature, otherwise it is the UTF-8 error message.
+//!
+//! The signer process parses every request and checks it against its own
+//! slashing protection before signing, so it never signs arbitrary bytes.
+
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use dusk_bytes::Serializable;
+use execution_core::signatures::bls::{
+    MultisigPublicKey as BlsMultisigPublicKey,
+    MultisigSignature as BlsMultisigSignature,
+};
+use node_data::bls::PublicKey;
+use node_data::ledger::Header;
+use node_data::message::payload::{Ratification, Validation};
+use node_data::Serializable as _;
+use node_data::StepName;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixStream;
+use tokio::time;
+
+use super::{ConsensusSigner, InMemorySigner, SignRequest, SignerError};
+use crate::slashing_protection::{SigningError, SlashingProtection};
+
+const REQ_PUBLIC_KEY: u8 = 0;
+const REQ_SIGN_SEED: u8 = 1;
+const REQ_SIGN_CANDIDATE: u8 = 2;
+const REQ_SIGN_VALIDATION: u8 = 3;
+const REQ_SIGN_RATIFICATION: u8 = 4;
+
+const STATUS_OK: u8 = 0;
+const STATUS_ERROR: u8 = 1;
+
+/// Maximum size of the payload of a frame
+const MAX_PAYLOAD_SIZE: usize = 64 * 1024;
+
+/// Timeout of the requests to the signer process
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Signer delegating the signatures to a separate process, listening on a
+/// Unix socket.
+#[derive(Debug)]
+pub struct RemoteSigner {
+    socket: PathBuf,
+    public_key: PublicKey,
+}
+
+impl RemoteSigner {
+    /// Connects to the signer process listening on the given socket,
+    /// fetching its public key.
+    pub async fn connect<P: AsRef<Path>>(
+        socket: P,
+    ) -> Result<Self, SignerError> {
+        let socket = socket.as_ref().to_path_buf();
+        let bytes = request(&socket, REQ_PUBLIC_KEY, &[]).await?;
+
+        let bytes: [u8; 96] = bytes.try_into().map_err(|_| {
+            SignerError::InvalidResponse("invalid public key size".into())
+        })?;
+        let public_key = PublicKey::try_from(bytes).map_err(|e| {
+            SignerError::InvalidResponse(format!("invalid public key {e:?}"))
+        })?;
+
+        Ok(Self { socket, public_key })
+    }
+}
+
+#[async_trait]
+impl ConsensusSigner for RemoteSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    async fn sign(
+        &self,
+        request: SignRequest<'_>,
+    ) -> Result<BlsMultisigSignature, SignerError> {
+        let (kind, payload) = encode_request(&request)?;
+        let bytes = self::request(&self.socket, kind, &payload).await?;
+
+        let bytes: [u8; 48] = bytes.try_into().map_err(|_| {
+            SignerError::InvalidResponse("invalid signature size".into())
+        })?;
+        let signature =
+            BlsMultisigSignature::from_bytes(&bytes).map_err(|e| {
+                SignerError::InvalidResponse(format!("invalid signature {e:?}"))
+            })?;
+
+        // Never propagate a signature not matching the public key
+        let pk = BlsMultisigPublicKey::aggregate(&[*self.public_key.inner()])
+            .map_err(|e| SignerError::InvalidResponse(format!("{e:?}")))?;
+        pk.verify(&signature, &request.signable()).map_err(|_| {
+            SignerError::InvalidResponse("signature does not verify".into())
+        })?;
+
+        Ok(signature)
+    }
+}
+
+fn encode_request(request: &SignRequest) -> io::Result<(u8, Vec<u8>)> {
+    let mut payload = vec![];
+    let kind = match request {
+        SignRequest::Seed(seed) => {
+            payload.extend_from_slice(seed.inner());
+            REQ_SIGN_SEED
+        }
+        SignRequest::Candidate(header) => {
+            header.write(&mut payload)?;
+            REQ_SIGN_CANDIDATE
+        }
+        SignRequest::Validation(validation) => {
+            validation.write(&mut payload)?;
+            REQ_SIGN_VALIDATION
+        }
+        SignRequest::Ratification(ratification) => {
+            ratification.write(&mut payload)?;
+            REQ_SIGN_RATIFICATION
+        }
+    };
+    Ok((kind, payload))
+}
+
+async fn request(
+    socket: &Path,
+    kind: u8,
+    payload: &[u8],
+) -> Result<Vec<u8>, SignerError> {
+    let exchange = async {
+        let mut stream = UnixStream::connect(socket).await?;
+        stream.write_all(&encode_frame(kind, payload)).await?;
+
+        let mut tag = [0u8; 1];
+        stream.read_exact(&mut tag).await?;
+        let mut len = [0u8; 4];
+        stream.read_exact(&mut len).await?;
+        let mut payload = vec![0u8; frame_len(len)?];
+        stream.read_exact(&mut payload).await?;
+        io::Result::Ok((tag[0], payload))
+    };
+    let response = time::timeout(REQUEST_TIMEOUT, exchange).await;
+    let (status, payload) = response.map_err(|_| {
+        io::Error::new(io::ErrorKind::TimedOut, "signer timed out")
+    })??;
+
+    match status {
+        STATUS_OK => Ok(payload),
+        _ => Err(SignerError::Remote(
+            String::from_utf8_lossy(&payload).into_owned(),
+        )),
+    }
+}
+
+fn encode_frame(tag: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 5);
+    frame.push(tag);
+    frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn frame_len(len: [u8; 4]) -> io::Result<usize> {
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_PAYLOAD_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame too big: {len} bytes"),
+        ));
+    }
+    Ok(len)
+}
+
+fn read_frame<R: Read>(r: &mut R) -> io::Result<(u8, Vec<u8>)> {
+    let mut tag = [0u8; 1];
+    r.read_exact(&mut tag)?;
+
+    let mut len = [0u8; 4];
+    r.read_exact(&mut len)?;
+
+    let mut payload = vec![0u8; frame_len(len)?];
+    r.read_exact(&mut payload)?;
+    Ok((tag[0], payload))
+}
+
+/// Serves the requests of a node on the given connection, signing them with
+/// the given signer, until the connection is closed.
+///
+/// Every message is checked against the slashing protection before being
+/// signed. This is meant to be run by the signer process for every accepted
+/// connection, on its own thread.
+pub fn serve_connection<S: Read + Write>(
+    stream: &mut S,
+    signer: &InMemorySigner,
+    protection: &SlashingProtection,
+) -> io::Result<()> {
+    loop {
+        let (kind, payload) = match read_frame(stream) {
+            Ok(frame) => frame,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let frame = match handle_request(kind, &payload, signer, protection) {
+            Ok(bytes) => encode_frame(STATUS_OK, &bytes),
+            Err(error) => encode_frame(STATUS_ERROR, error.as_bytes()),
+        };
+        stream.write_all(&frame)?;
+        stream.flush()?;
+    }
+}
+
+fn handle_request(
+    kind: u8,
+    payload: &[u8],
+    signer: &InMemorySigner,
+    protection: &SlashingProtection,
+) -> Result<Vec<u8>, String> {
+    let invalid = |e: io::Error| format!("invalid request: {e}");
+    let refused = |e: SigningError| format!("refused to sign: {e}");
+
+    let signature = match kind {
+        REQ_PUBLIC_KEY => {
+            return Ok(signer.public_key().bytes().inner().to_vec())
+        }
+        REQ_SIGN_SEED => {
+            let seed: [u8; 48] =
+                payload.try_into().map_err(|_| "invalid seed size")?;
+            signer.sign_now(SignRequest::Seed(&seed.into()))
+        }
+        REQ_SIGN_CANDIDATE => {
+            let header = Header::read(&mut &payload[..]).map_err(invalid)?;
+            if header.compute_hash().map_err(invalid)? != header.hash {
+                return Err("candidate hash mismatch".into());
+            }
+            if &header.generator_bls_pubkey != signer.public_key().bytes() {
+                return Err("not the generator of the candidate".into());
+            }
+            let consensus_header = header.to_consensus_header();
+            protection
+                .approve_candidate_blocking(&consensus_header, &header.hash)
+                .map_err(refused)?;
+            signer.sign_now(SignRequest::Candidate(&header))
+        }
+        REQ_SIGN_VALIDATION => {
+            let validation =
+                Validation::read(&mut &payload[..]).map_err(invalid)?;
+            protection
+                .approve_vote_blocking(
+                    &validation.header,
+                    StepName::Validation,
+                    &validation.vote,
+                )
+                .map_err(refused)?;
+            signer.sign_now(SignRequest::Validation(&validation))
+        }
+        REQ_SIGN_RATIFICATION => {
+            let ratification =
+                Ratification::read(&mut &payload[..]).map_err(invalid)?;
+            protection
+                .approve_vote_blocking(
+                    &ratification.header,
+                    StepName::Ratification,
+                    &ratification.vote,
+                )
+                .map_err(refused)?;
+            signer.sign_now(SignRequest::Ratification(&ratification))
+        }
+        kind => return Err(format!("unknown request {kind}")),
+    };
+
+    Ok(signature.to_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConsensusParams;
+    use crate::signer::sign_message;
+    use crate::slashing_protection::tests::MemoryHistory;
+    use execution_core::signatures::bls::SecretKey as BlsSecretKey;
+    use node_data::ledger::Block;
+    use node_data::message::payload::{Candidate, Vote};
+    use node_data::message::{ConsensusHeader, SignInfo};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::os::unix::net::UnixListener;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn remote_signer() {
+        let sk = BlsSecretKey::random(&mut StdRng::seed_from_u64(7));
+        let local = Arc::new(InMemorySigner::new(sk));
+        let protection = SlashingProtection::new(
+            Arc::new(MemoryHistory::default()),
+            ConsensusParams::default(),
+        );
+
+        let socket = std::env::temp_dir()
+            .join(format!("consensus-signer-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&socket);
+        let listener = UnixListener::bind(&socket).unwrap();
+
+        let server = local.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                serve_connection(&mut stream, &server, &protection).unwrap();
+            }
+        });
+
+        let remote = RemoteSigner::connect(&socket).await.unwrap();
+        assert_eq!(remote.public_key(), local.public_key());
+
+        let seed = [3u8; 48].into();
+        let request = SignRequest::Seed(&seed);
+        assert_eq!(
+            remote.sign(request).await.unwrap().to_bytes(),
+            local.sign_now(request).to_bytes()
+        );
+
+        let mut validation = Validation {
+            header: ConsensusHeader {
+                prev_block_hash: [1; 32],
+                round: 10,
+                iteration: 0,
+            },
+            vote: Vote::Valid([2; 32]),
+            sign_info: SignInfo::default(),
+        };
+        sign_message(&remote, &mut validation).await.unwrap();
+        assert_eq!(
+            validation.sign_info.signature.inner(),
+            &local
+                .sign_now(SignRequest::Validation(&validation))
+                .to_bytes()
+        );
+
+        // Conflicting votes are refused by the signer process
+        validation.vote = Vote::NoCandidate;
+        assert!(matches!(
+            sign_message(&remote, &mut validation).await,
+            Err(SignerError::Remote(_))
+        ));
+
+        // Candidates are signed only if their hash matches their header
+        let header = Header {
+            height: 10,
+            generator_bls_pubkey: *local.public_key().bytes(),
+            ..Default::default()
+        };
+        let block = Block::new(header, vec![], vec![]).unwrap();
+        let mut candidate = Candidate { candidate: block };
+        sign_message(&remote, &mut candidate).await.unwrap();
+
+        let mut forged = candidate.candidate.header().clone();
+        forged.timestamp += 1;
+        assert!(matches!(
+            remote.sign(SignRequest::Candidate(&forged)).await,
+            Err(SignerError::Remote(_))
+        ));
+
+        let _ = std::fs::remove_file(&socket);
+    }
+}
@@ -0,0 +1,305 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use node_data::ledger::Hash;
+use node_data::message::payload::Vote;
+use node_data::message::ConsensusHeader;
+use node_data::{Serializable, StepName};
+use sha3::{Digest, Sha3_256};
+use thiserror::Error;
+use tokio::task;
+
+use crate::config::ConsensusParams;
+
+/// Round, iteration, step and tip of a message signed by the local
+/// provisioner.
+///
+/// The tip is part of the slot, so that signing on top of a fork never
+/// replaces what was signed on top of another tip for the same step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SigningSlot {
+    pub round: u64,
+    pub iteration: u8,
+    pub step: StepName,
+    /// Tip the message was signed on top of
+    pub prev_block_hash: Hash,
+}
+
+/// What the local provisioner signed for a [`SigningSlot`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignedRecord {
+    /// Digest of the signed content: the candidate hash or the vote
+    pub digest: [u8; 32],
+}
+
+impl SignedRecord {
+    pub fn new(signed: &[u8]) -> Self {
+        Self {
+            digest: Sha3_256::digest(signed).into(),
+        }
+    }
+
+    /// Returns true if signing both records for the same slot would be an
+    /// equivocation.
+    pub fn conflicts(&self, other: &Self) -> bool {
+        self.digest != other.digest
+    }
+}
+
+/// Persistent history of the messages signed by the local provisioner.
+///
+/// Records must be durable once [`SigningHistory::put`] returns, since they
+/// are meant to survive a restart of the node.
+pub trait SigningHistory: Send + Sync {
+    fn get(&self, slot: &SigningSlot) -> anyhow::Result<Option<SignedRecord>>;
+    fn put(
+        &self,
+        slot: &SigningSlot,
+        record: &SignedRecord,
+    ) -> anyhow::Result<()>;
+}
+
+#[derive(Debug, Error)]
+pub enum SigningError {
+    #[error("conflicting message already signed for {0:?}")]
+    Conflicting(SigningSlot),
+    #[error("signing history unavailable: {0}")]
+    History(anyhow::Error),
+}
+
+/// Guards the local signatures against equivocations, consulting the
+/// signing history before every signature.
+///
+/// The default instance has no history and approves every signature.
+#[derive(Clone, Default)]
+pub struct SlashingProtection {
+    history: Option<Arc<dyn SigningHistory>>,
+    lock: Arc<Mutex<()>>,
+    params: ConsensusParams,
+}
+
+impl fmt::Debug for SlashingProtection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlashingProtection")
+            .field("enabled", &self.history.is_some())
+            .finish()
+    }
+}
+
+impl SlashingProtection {
+    pub fn new(
+        history: Arc<dyn SigningHistory>,
+        params: ConsensusParams,
+    ) -> Self {
+        Self {
+            history: Some(history),
+            lock: Arc::default(),
+            params,
+        }
+    }
+
+    /// Approves signing a candidate block with the given hash.
+    ///
+    /// The history is accessed on the blocking thread pool, not to stall the
+    /// async runtime while records are made durable.
+    pub async fn approve_candidate(
+        &self,
+        header: &ConsensusHeader,
+        hash: &Hash,
+    ) -> Result<(), SigningError> {
+        self.spawn_approve(header, StepName::Proposal, hash.to_vec())
+            .await
+    }
+
+    /// Approves signing a vote for the given step.
+    ///
+    /// The history is accessed on the blocking thread pool, not to stall the
+    /// async runtime while records are made durable.
+    pub async fn approve_vote(
+        &self,
+        header: &ConsensusHeader,
+        step: StepName,
+        vote: &Vote,
+    ) -> Result<(), SigningError> {
+        self.spawn_approve(header, step, vote_bytes(vote)).await
+    }
+
+    /// Blocking version of [`SlashingProtection::approve_candidate`].
+    pub fn approve_candidate_blocking(
+        &self,
+        header: &ConsensusHeader,
+        hash: &Hash,
+    ) -> Result<(), SigningError> {
+        self.approve(header, StepName::Proposal, hash)
+    }
+
+    /// Blocking version of [`SlashingProtection::approve_vote`].
+    pub fn approve_vote_blocking(
+        &self,
+        header: &ConsensusHeader,
+        step: StepName,
+        vote: &Vote,
+    ) -> Result<(), SigningError> {
+        self.approve(header, step, &vote_bytes(vote))
+    }
+
+    async fn spawn_approve(
+        &self,
+        header: &ConsensusHeader,
+        step: StepName,
+        signed: Vec<u8>,
+    ) -> Result<(), SigningError> {
+        if self.history.is_none() {
+            return Ok(());
+        }
+
+        let protection = self.clone();
+        let header = header.clone();
+        task::spawn_blocking(move || protection.approve(&header, step, &signed))
+            .await
+            .map_err(|e| SigningError::History(e.into()))?
+    }
+
+    /// Approves signing a message of the given step, recording it in the
+    /// history.
+    ///
+    /// Signing the same content twice is allowed, while signing a different
+    /// one for the same slot is refused. Messages of emergency
+    /// iterations are always approved, since multiple votes are allowed
+    /// there.
+    ///
+    /// This blocks until the record is durable.
+    pub fn approve(
+        &self,
+        header: &ConsensusHeader,
+        step: StepName,
+        signed: &[u8],
+    ) -> Result<(), SigningError> {
+        let Some(history) = &self.history else {
+            return Ok(());
+        };
+        if self.params.is_emergency_iter(header.iteration) {
+            return Ok(());
+        }
+
+        let slot = SigningSlot {
+            round: header.round,
+            iteration: header.iteration,
+            step,
+            prev_block_hash: header.prev_block_hash,
+        };
+        let record = SignedRecord::new(signed);
+
+        let _guard = self.lock.lock().expect("lock to be acquired");
+        match history.get(&slot).map_err(SigningError::History)? {
+            Some(signed) if signed.conflicts(&record) => {
+                Err(SigningError::Conflicting(slot))
+            }
+            Some(_) => Ok(()),
+            None => history.put(&slot, &record).map_err(SigningError::History),
+        }
+    }
+}
+
+fn vote_bytes(vote: &Vote) -> Vec<u8> {
+    let mut signed = vec![];
+    vote.write(&mut signed)
+        .expect("writing to vec should succeed");
+    signed
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    pub(crate) struct MemoryHistory(Mutex<HashMap<SigningSlot, SignedRecord>>);
+
+    impl SigningHistory for MemoryHistory {
+        fn get(
+            &self,
+            slot: &SigningSlot,
+        ) -> anyhow::Result<Option<SignedRecord>> {
+            Ok(self.0.lock().unwrap().get(slot).copied())
+        }
+
+        fn put(
+            &self,
+            slot: &SigningSlot,
+            record: &SignedRecord,
+        ) -> anyhow::Result<()> {
+            self.0.lock().unwrap().insert(*slot, *record);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn refuse_conflicting_votes() {
+        let protection = SlashingProtection::new(
+            Arc::new(MemoryHistory::default()),
+            ConsensusParams::default(),
+        );
+        let header = ConsensusHeader {
+            prev_block_hash: [1; 32],
+            round: 10,
+            iteration: 0,
+        };
+        let step = StepName::Validation;
+        let valid = Vote::Valid([2; 32]);
+        let nil = Vote::NoCandidate;
+
+        protection
+            .approve_vote(&header, step, &valid)
+            .await
+            .expect("first vote");
+        protection
+            .approve_vote(&header, step, &valid)
+            .await
+            .expect("same vote");
+        assert!(matches!(
+            protection.approve_vote(&header, step, &nil).await,
+            Err(SigningError::Conflicting(_))
+        ));
+
+        // Other steps and iterations are unaffected
+        protection
+            .approve_vote(&header, StepName::Ratification, &nil)
+            .await
+            .expect("other step");
+        let next_iter = ConsensusHeader {
+            iteration: 1,
+            ..header.clone()
+        };
+        protection
+            .approve_vote(&next_iter, step, &nil)
+            .await
+            .expect("other iter");
+
+        // Signing on top of a different tip is not an equivocation
+        let fork = ConsensusHeader {
+            prev_block_hash: [3; 32],
+            ..header.clone()
+        };
+        protection
+            .approve_vote(&fork, step, &nil)
+            .await
+            .expect("fork");
+
+        // Going back to the first tip still refuses a conflicting vote
+        assert!(matches!(
+            protection.approve_vote(&header, step, &nil).await,
+            Err(SigningError::Conflicting(_))
+        ));
+        protection
+            .approve_vote(&header, step, &valid)
+            .await
+            .expect("same vote after fork");
+    }
+}
@@ -4,6 +4,7 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use crate::config::PROPOSAL_COMMITTEE_CREDITS;
 use crate::user::sortition;
 use crate::user::stake::Stake;
 use execution_core::dusk;
@@ -214,6 +215,7 @@ impl Provisioners {
             round,
             iteration,
             StepName::Proposal,
+            PROPOSAL_COMMITTEE_CREDITS,
             vec![],
         );
         let committee_keys = Committee::new(self, &cfg);
@@ -11,11 +11,6 @@ use sha3::{Digest, Sha3_256};
 
 use node_data::{bls::PublicKeyBytes, ledger::Seed, StepName};
 
-use crate::config::{
-    PROPOSAL_COMMITTEE_CREDITS, RATIFICATION_COMMITTEE_CREDITS,
-    VALIDATION_COMMITTEE_CREDITS,
-};
-
 #[derive(Debug, Clone, Default, Eq, Hash, PartialEq)]
 pub struct Config {
     seed: Seed,
@@ -31,13 +26,9 @@ impl Config {
         round: u64,
         iteration: u8,
         step: StepName,
+        committee_credits: usize,
         exclusion: Vec<PublicKeyBytes>,
     ) -> Config {
-        let committee_credits = match step {
-            StepName::Proposal => PROPOSAL_COMMITTEE_CREDITS,
-            StepName::Ratification => RATIFICATION_COMMITTEE_CREDITS,
-            StepName::Validation => VALIDATION_COMMITTEE_CREDITS,
-        };
         let step = step.to_step(iteration);
         Self {
             seed,
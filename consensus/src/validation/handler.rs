@@ -6,7 +6,6 @@
 
 use crate::aggregator::{Aggregator, StepVote};
 use crate::commons::RoundUpdate;
-use crate::config::is_emergency_iter;
 use crate::errors::ConsensusError;
 use crate::msg_handler::{HandleMsgOutput, MsgHandler};
 use crate::step_votes_reg::SafeAttestationInfoRegistry;
@@ -126,7 +125,7 @@ impl MsgHandler for ValidationHandler {
     async fn collect(
         &mut self,
         msg: Message,
-        _ru: &RoundUpdate,
+        ru: &RoundUpdate,
         committee: &Committee,
         generator: Option<PublicKeyBytes>,
     ) -> Result<HandleMsgOutput, ConsensusError> {
@@ -144,8 +143,10 @@ impl MsgHandler for ValidationHandler {
             return Err(ConsensusError::InvalidMsgIteration(iteration));
         }
 
-        let (sv, quorum_reached) =
-            self.aggr.collect_vote(committee, &p).map_err(|error| {
+        let (sv, quorum_reached) = self
+            .aggr
+            .collect_vote(committee, &p, &ru.params)
+            .map_err(|error| {
                 warn!(
                     event = "Cannot collect vote",
                     ?error,
@@ -188,7 +189,7 @@ impl MsgHandler for ValidationHandler {
     async fn collect_from_past(
         &mut self,
         msg: Message,
-        _ru: &RoundUpdate,
+        ru: &RoundUpdate,
         committee: &Committee,
         generator: Option<PublicKeyBytes>,
     ) -> Result<HandleMsgOutput, ConsensusError> {
@@ -200,7 +201,7 @@ impl MsgHandler for ValidationHandler {
         }
 
         // Collect vote, if msg payload is validation type
-        let collect_vote = self.aggr.collect_vote(committee, &p);
+        let collect_vote = self.aggr.collect_vote(committee, &p, &ru.params);
 
         match collect_vote {
             Ok((sv, validation_quorum_reached)) => {
@@ -236,10 +237,10 @@ impl MsgHandler for ValidationHandler {
     /// Handles of an event of step execution timeout
     fn handle_timeout(
         &self,
-        _ru: &RoundUpdate,
+        ru: &RoundUpdate,
         curr_iteration: u8,
     ) -> Option<Message> {
-        if is_emergency_iter(curr_iteration) {
+        if ru.params.is_emergency_iter(curr_iteration) {
             // While we are in Emergency mode but still the candidate is missing
             // then we request it
 
@@ -5,18 +5,19 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::commons::{Database, RoundUpdate};
-use crate::config::is_emergency_iter;
 use crate::errors::ConsensusError;
 use crate::execution_ctx::ExecutionCtx;
 use crate::operations::{Operations, Voter};
+use crate::signer::{sign_message, SignerError};
 use crate::validation::handler;
 use anyhow::anyhow;
 use node_data::bls::PublicKeyBytes;
 use node_data::ledger::{to_str, Block};
 use node_data::message::payload::{Validation, Vote};
 use node_data::message::{
-    AsyncQueue, ConsensusHeader, Message, Payload, SignInfo, StepMessage,
+    AsyncQueue, ConsensusHeader, Message, Payload, SignInfo,
 };
+use node_data::StepName;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::task::JoinSet;
@@ -130,12 +131,30 @@ impl<T: Operations + 'static> ValidationStep<T> {
         outbound: AsyncQueue<Message>,
         inbound: AsyncQueue<Message>,
     ) {
+        let header = ru.consensus_header(iteration);
+        let step = StepName::Validation;
+        let approval = ru
+            .slashing_protection
+            .approve_vote(&header, step, &vote)
+            .await;
+        if let Err(err) = approval {
+            error!(event = "refusing to sign", ?vote, %err);
+            return;
+        }
+
         // Sign and construct validation message
-        let validation = self::build_validation_payload(vote, ru, iteration);
+        let validation =
+            match self::build_validation_payload(vote, ru, iteration).await {
+                Ok(validation) => validation,
+                Err(err) => {
+                    error!(event = "failed to sign", ?vote, %err);
+                    return;
+                }
+            };
         info!(event = "send_vote", vote = ?validation.vote);
         let msg = Message::from(validation);
 
-        if vote.is_valid() || !is_emergency_iter(iteration) {
+        if vote.is_valid() || !ru.params.is_emergency_iter(iteration) {
             // Publish
             outbound.try_send(msg.clone());
 
@@ -180,11 +199,11 @@ impl<T: Operations + 'static> ValidationStep<T> {
     }
 }
 
-pub fn build_validation_payload(
+pub async fn build_validation_payload(
     vote: Vote,
     ru: &RoundUpdate,
     iteration: u8,
-) -> Validation {
+) -> Result<Validation, SignerError> {
     let header = ConsensusHeader {
         prev_block_hash: ru.hash(),
         round: ru.round,
@@ -197,8 +216,8 @@ pub fn build_validation_payload(
         vote,
         sign_info,
     };
-    validation.sign(&ru.secret_key, ru.pubkey_bls.inner());
-    validation
+    sign_message(ru.signer.as_ref(), &mut validation).await?;
+    Ok(validation)
 }
 
 impl<T: Operations + 'static> ValidationStep<T> {
@@ -248,8 +267,8 @@ impl<T: Operations + 'static> ValidationStep<T> {
             let candidate = self.handler.lock().await.candidate.clone();
 
             // Casting a NIL vote is disabled in Emergency Mode
-            let voting_enabled =
-                candidate.is_some() || !is_emergency_iter(ctx.iteration);
+            let voting_enabled = candidate.is_some()
+                || !ctx.round_update.params.is_emergency_iter(ctx.iteration);
 
             let current_generator = ctx
                 .iter_ctx
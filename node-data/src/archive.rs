@@ -22,4 +22,37 @@ pub enum ArchivalData {
     ArchivedEvents(u64, Hash, Vec<ContractTxEvent>),
     FinalizedBlock(u64, HexHash),
     DeletedBlock(u64, HexHash),
+    /// Lifecycle event of a mempool transaction together with the
+    /// transaction hash and the timestamp of the event.
+    MempoolTx(Hash, MempoolTxEvent, u64),
+}
+
+/// Lifecycle event of a transaction in the mempool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MempoolTxEvent {
+    /// The transaction has been accepted in the mempool
+    Included,
+    /// The transaction has been replaced by the transaction with the given
+    /// hash
+    Replaced(Hash),
+    /// The transaction has been removed after its expiry time
+    Expired,
+    /// The transaction has been evicted in favor of higher paying ones
+    Evicted,
+    /// The transaction has been discarded during block generation for the
+    /// given reason
+    Discarded(String),
+}
+
+impl MempoolTxEvent {
+    /// Name of the event as stored in the archive
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Included => "included",
+            Self::Replaced(_) => "replaced",
+            Self::Expired => "expired",
+            Self::Evicted => "evicted",
+            Self::Discarded(_) => "discarded",
+        }
+    }
 }
@@ -14,7 +14,7 @@ mod transaction;
 pub use transaction::{SpendingId, SpentTransaction, Transaction};
 
 mod faults;
-pub use faults::{Fault, InvalidFault, Slash, SlashType};
+pub use faults::{Fault, FaultData, InvalidFault, Slash, SlashType};
 
 mod attestation;
 pub use attestation::{
@@ -48,5 +48,5 @@ pub fn to_str<const N: usize>(bytes: &[u8; N]) -> String {
 
 #[cfg(any(feature = "faker", test))]
 pub mod faker {
-    pub use super::transaction::faker::gen_dummy_tx;
+    pub use super::transaction::faker::{gen_dummy_moonlight_tx, gen_dummy_tx};
 }
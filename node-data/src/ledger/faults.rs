@@ -33,6 +33,9 @@ pub enum Fault {
     DoubleCandidate(FaultData<Hash>, FaultData<Hash>),
     DoubleRatificationVote(FaultData<Vote>, FaultData<Vote>),
     DoubleValidationVote(FaultData<Vote>, FaultData<Vote>),
+    /// Candidates carrying their whole header, so that the signed hash is
+    /// recomputed rather than trusted
+    DoubleCandidateHeader(FaultData<Header>, FaultData<Header>),
 }
 
 impl Fault {
@@ -51,6 +54,12 @@ impl Fault {
                 a.data.size() + b.data.size()
             }
             Fault::DoubleValidationVote(a, b) => a.data.size() + b.data.size(),
+            Fault::DoubleCandidateHeader(a, b) => {
+                let size = |h: &Header| {
+                    h.size().expect("Writing to vec should succeed")
+                };
+                size(&a.data) + size(&b.data)
+            }
         };
 
         HEADERS + SIG_INFOS + faults_data_size
@@ -75,6 +84,10 @@ pub enum InvalidFault {
     EmergencyIteration,
     #[error("Round mismatch")]
     RoundMismatch,
+    #[error("Signer is not the generator of the candidate")]
+    GeneratorMismatch,
+    #[error("Fault type is not active yet")]
+    Inactive,
     #[error("Invalid Signature {0}")]
     InvalidSignature(BlsSigError),
     #[error("Generic error {0}")]
@@ -126,6 +139,12 @@ impl Fault {
                 let b = sha3::Sha3_256::digest(b.get_signed_data(seed)).into();
                 (a, b)
             }
+            Fault::DoubleCandidateHeader(a, b) => {
+                let seed = Candidate::SIGN_SEED;
+                let a = sha3::Sha3_256::digest(a.get_signed_data(seed)).into();
+                let b = sha3::Sha3_256::digest(b.get_signed_data(seed)).into();
+                (a, b)
+            }
         }
     }
 
@@ -134,6 +153,7 @@ impl Fault {
             Fault::DoubleRatificationVote(a, _)
             | Fault::DoubleValidationVote(a, _) => a.sig.signer.clone(),
             Fault::DoubleCandidate(a, _) => a.sig.signer.clone(),
+            Fault::DoubleCandidateHeader(a, _) => a.sig.signer.clone(),
         }
     }
 
@@ -143,6 +163,7 @@ impl Fault {
             Fault::DoubleRatificationVote(a, b)
             | Fault::DoubleValidationVote(a, b) => (&a.header, &b.header),
             Fault::DoubleCandidate(a, b) => (&a.header, &b.header),
+            Fault::DoubleCandidateHeader(a, b) => (&a.header, &b.header),
         }
     }
 
@@ -166,6 +187,12 @@ impl Fault {
             return Err(InvalidFault::PrevHashMismatch);
         }
 
+        // Check that the candidates are the ones the fault refers to
+        if let Fault::DoubleCandidateHeader(a, b) = self {
+            a.check_candidate()?;
+            b.check_candidate()?;
+        }
+
         // Check that fault refers to different fault_data
         let (id_a, id_b) = self.faults_id();
         if id_a == id_b {
@@ -219,6 +246,14 @@ impl Fault {
                 Self::verify_signature(&b.sig, &msg)?;
                 Ok(())
             }
+            Fault::DoubleCandidateHeader(a, b) => {
+                let seed = Candidate::SIGN_SEED;
+                let msg = a.get_signed_data(seed);
+                Self::verify_signature(&a.sig, &msg)?;
+                let msg = b.get_signed_data(seed);
+                Self::verify_signature(&b.sig, &msg)?;
+                Ok(())
+            }
         }
     }
 
@@ -241,6 +276,35 @@ impl FaultData<Hash> {
         signable
     }
 }
+impl FaultData<Header> {
+    /// Candidates are signed over the hash of their block only, which is
+    /// recomputed from the header
+    fn get_signed_data(&self, seed: &[u8]) -> Vec<u8> {
+        let hash = self.data.compute_hash().expect("Hashing should succeed");
+        let mut signable = seed.to_vec();
+        signable.extend_from_slice(&hash);
+        signable
+    }
+
+    /// Checks that the candidate header is the one of the consensus header
+    /// and of the signer of the fault
+    fn check_candidate(&self) -> Result<(), InvalidFault> {
+        let candidate = self.data.to_consensus_header();
+        if candidate.round != self.header.round {
+            return Err(InvalidFault::RoundMismatch);
+        }
+        if candidate.iteration != self.header.iteration {
+            return Err(InvalidFault::IterationMismatch);
+        }
+        if candidate.prev_block_hash != self.header.prev_block_hash {
+            return Err(InvalidFault::PrevHashMismatch);
+        }
+        if &self.data.generator_bls_pubkey != self.sig.signer.bytes() {
+            return Err(InvalidFault::GeneratorMismatch);
+        }
+        Ok(())
+    }
+}
 impl FaultData<Vote> {
     fn get_signed_data(&self, seed: &[u8]) -> Vec<u8> {
         let mut signable = self.header.signable();
@@ -261,6 +325,50 @@ pub struct FaultData<V> {
     data: V,
 }
 
+impl<V> FaultData<V> {
+    pub fn header(&self) -> &ConsensusHeader {
+        &self.header
+    }
+
+    pub fn signer(&self) -> &PublicKey {
+        &self.sig.signer
+    }
+
+    pub fn data(&self) -> &V {
+        &self.data
+    }
+}
+
+impl From<&Candidate> for FaultData<Header> {
+    fn from(candidate: &Candidate) -> Self {
+        Self {
+            header: candidate.header(),
+            sig: candidate.sign_info(),
+            data: candidate.candidate.header().clone(),
+        }
+    }
+}
+
+impl From<&Validation> for FaultData<Vote> {
+    fn from(validation: &Validation) -> Self {
+        Self {
+            header: validation.header(),
+            sig: validation.sign_info(),
+            data: validation.vote,
+        }
+    }
+}
+
+impl From<&Ratification> for FaultData<Vote> {
+    fn from(ratification: &Ratification) -> Self {
+        Self {
+            header: ratification.header(),
+            sig: ratification.sign_info(),
+            data: ratification.vote,
+        }
+    }
+}
+
 impl<V: Serializable> Serializable for FaultData<V> {
     fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
         self.header.write(w)?;
@@ -299,6 +407,11 @@ impl Serializable for Fault {
                 a.write(w)?;
                 b.write(w)?;
             }
+            Fault::DoubleCandidateHeader(a, b) => {
+                w.write_all(&[3u8])?;
+                a.write(w)?;
+                b.write(w)?;
+            }
         }
 
         Ok(())
@@ -321,6 +434,10 @@ impl Serializable for Fault {
                 FaultData::read(r)?,
                 FaultData::read(r)?,
             ),
+            3 => Fault::DoubleCandidateHeader(
+                FaultData::read(r)?,
+                FaultData::read(r)?,
+            ),
             p => Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!("Invalid fault: {p}"),
@@ -393,7 +510,8 @@ impl From<&Fault> for Slash {
         let slash_type = match value {
             Fault::DoubleCandidate(_, _)
             | Fault::DoubleRatificationVote(_, _)
-            | Fault::DoubleValidationVote(_, _) => {
+            | Fault::DoubleValidationVote(_, _)
+            | Fault::DoubleCandidateHeader(_, _) => {
                 SlashType::HardWithSeverity(2u8)
             }
         };
@@ -81,6 +81,13 @@ impl std::fmt::Debug for Header {
 }
 
 impl Header {
+    /// Computes the block hash out of the hashable fields
+    pub fn compute_hash(&self) -> io::Result<Hash> {
+        let mut hasher = sha3::Sha3_256::new();
+        self.marshal_hashable(&mut hasher)?;
+        Ok(hasher.finalize().into())
+    }
+
     /// Return the corresponding ConsensusHeader
     pub fn to_consensus_header(&self) -> ConsensusHeader {
         ConsensusHeader {
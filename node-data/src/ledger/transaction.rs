@@ -96,6 +96,16 @@ impl Transaction {
             }
         }
     }
+
+    /// Returns the sender account and the nonce of a Moonlight transaction.
+    pub fn moonlight_nonce(&self) -> Option<(bls::PublicKey, u64)> {
+        match &self.inner {
+            ProtocolTransaction::Moonlight(m) => {
+                Some((*m.from_account(), m.nonce()))
+            }
+            ProtocolTransaction::Phoenix(_) => None,
+        }
+    }
 }
 
 impl PartialEq<Self> for Transaction {
@@ -140,6 +150,7 @@ pub mod faker {
     use crate::ledger::Dummy;
     use execution_core::transfer::{
         data::{ContractCall, TransactionData},
+        moonlight::Transaction as MoonlightTransaction,
         phoenix::{
             Fee, Note, Payload as PhoenixPayload,
             PublicKey as PhoenixPublicKey, SecretKey as PhoenixSecretKey,
@@ -147,7 +158,8 @@ pub mod faker {
         },
     };
     use execution_core::{BlsScalar, JubJubScalar};
-    use rand::Rng;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
 
     impl<T> Dummy<T> for Transaction {
         fn dummy_with_rng<R: Rng + ?Sized>(_config: &T, _rng: &mut R) -> Self {
@@ -212,4 +224,31 @@ pub mod faker {
 
         tx.into()
     }
+
+    /// Generates a moonlight transaction sent by the account derived from
+    /// `sk_seed`, with a specified nonce and gas price.
+    pub fn gen_dummy_moonlight_tx(
+        sk_seed: u64,
+        nonce: u64,
+        gas_price: u64,
+    ) -> Transaction {
+        let rng = &mut StdRng::seed_from_u64(sk_seed);
+        let sk = bls::SecretKey::random(rng);
+
+        let tx: ProtocolTransaction = MoonlightTransaction::new(
+            &sk,
+            None,
+            0,
+            0,
+            1,
+            gas_price,
+            nonce,
+            0xFA,
+            None::<TransactionData>,
+        )
+        .expect("moonlight transaction to be valid")
+        .into();
+
+        tx.into()
+    }
 }
@@ -1406,12 +1406,17 @@ pub trait StepMessage {
         pk.verify(&sig, &msg)
     }
 
+    /// Sets the signature of the signable data by the given signer
+    fn set_signature(&mut self, signer: &PublicKey, signature: Signature) {
+        let sign_info = self.sign_info_mut();
+        sign_info.signature = signature;
+        sign_info.signer = signer.clone()
+    }
+
     fn sign(&mut self, sk: &BlsSecretKey, pk: &BlsPublicKey) {
         let msg = self.signable();
-        let sign_info = self.sign_info_mut();
         let signature = sk.sign_multisig(pk, &msg).to_bytes();
-        sign_info.signature = signature.into();
-        sign_info.signer = PublicKey::new(*pk)
+        self.set_signature(&PublicKey::new(*pk), signature.into())
     }
 }
 
@@ -1485,10 +1490,9 @@ impl StepMessage for Candidate {
         }
     }
 
-    fn sign(&mut self, sk: &BlsSecretKey, pk: &BlsPublicKey) {
-        let msg = self.signable();
-        let signature = sk.sign_multisig(pk, &msg).to_bytes();
-        self.candidate.set_signature(signature.into());
+    /// The signer of a candidate is its generator
+    fn set_signature(&mut self, _signer: &PublicKey, signature: Signature) {
+        self.candidate.set_signature(signature);
     }
 }
 
@@ -5,9 +5,12 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use dusk_consensus::commons::RoundUpdate;
+use dusk_consensus::config::ConsensusParams;
+use dusk_consensus::signer::InMemorySigner;
 use node::chain;
 
 use criterion::async_executor::FuturesExecutor;
@@ -34,7 +37,7 @@ use node_data::{ledger, StepName};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 
-fn create_step_votes(
+async fn create_step_votes(
     tip_header: &ledger::Header,
     vote: &Vote,
     step: StepName,
@@ -54,6 +57,7 @@ fn create_step_votes(
         round,
         iteration,
         step,
+        ConsensusParams::default().committee_credits(step),
         vec![generator, next_generator],
     );
 
@@ -64,10 +68,11 @@ fn create_step_votes(
     for (pk, sk) in keys.iter() {
         if let Some(weight) = committee.votes_for(pk) {
             let vote = vote.clone();
+            let signer = Arc::new(InMemorySigner::new(sk.clone()));
             let ru = RoundUpdate::new(
-                pk.clone(),
-                sk.clone(),
+                signer,
                 tip_header,
+                ConsensusParams::default(),
                 HashMap::default(),
                 vec![],
             );
@@ -76,6 +81,8 @@ fn create_step_votes(
                     dusk_consensus::build_validation_payload(
                         vote, &ru, iteration,
                     )
+                    .await
+                    .unwrap()
                     .sign_info
                     .signature
                 }
@@ -89,6 +96,8 @@ fn create_step_votes(
                             QuorumType::Valid,
                         ),
                     )
+                    .await
+                    .unwrap()
                     .sign_info
                     .signature
                 }
@@ -139,22 +148,23 @@ pub fn verify_att(c: &mut Criterion) {
             let vote = Vote::Valid(block_hash);
             let iteration = 0;
 
-            let validation = create_step_votes(
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            let validation = runtime.block_on(create_step_votes(
                 &tip_header,
                 &vote,
                 StepName::Validation,
                 iteration,
                 &provisioners,
                 &keys[..],
-            );
-            let ratification = create_step_votes(
+            ));
+            let ratification = runtime.block_on(create_step_votes(
                 &tip_header,
                 &vote,
                 StepName::Ratification,
                 iteration,
                 &provisioners,
                 &keys[..],
-            );
+            ));
             let att = Attestation {
                 result: RatificationResult::Success(Vote::Valid(block_hash)),
                 validation,
@@ -182,6 +192,7 @@ pub fn verify_att(c: &mut Criterion) {
                             RatificationResult::Success(Vote::Valid(
                                 block_hash,
                             )),
+                            &ConsensusParams::default(),
                         )
                         .await
                         .expect("block to be verified")
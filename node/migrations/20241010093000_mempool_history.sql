@@ -0,0 +1,14 @@
+-- Sqlite schema for the mempool history table
+CREATE TABLE mempool_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    tx_hash TEXT NOT NULL,
+    -- One of: included, replaced, expired, evicted, discarded
+    event TEXT NOT NULL,
+    -- Hash of the replacing transaction, for replaced events
+    replaced_by TEXT,
+    -- Reason why the transaction has been discarded, for discarded events
+    reason TEXT,
+    timestamp INTEGER NOT NULL
+);
+
+CREATE INDEX mempool_history_tx_hash_idx ON mempool_history (tx_hash);
@@ -0,0 +1,17 @@
+-- Sqlite schema for the normalized contract events table
+CREATE TABLE contract_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    block_height INTEGER NOT NULL,
+    block_hash TEXT NOT NULL,
+    -- Hash of the transaction that emitted the event, if any
+    origin TEXT,
+    -- Contract that emitted the event
+    target TEXT NOT NULL,
+    topic TEXT NOT NULL,
+    -- Hex encoded event data
+    data TEXT NOT NULL
+);
+
+CREATE INDEX contract_events_origin_idx ON contract_events (origin);
+CREATE INDEX contract_events_target_idx ON contract_events (target, topic, block_height);
+CREATE INDEX contract_events_block_hash_idx ON contract_events (block_hash);
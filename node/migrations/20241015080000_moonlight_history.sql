@@ -0,0 +1,18 @@
+-- Sqlite schema for the Moonlight account history table
+CREATE TABLE moonlight_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    -- Base58 encoded public key of the account
+    account TEXT NOT NULL,
+    block_height INTEGER NOT NULL,
+    -- Hash of the transaction that moved the funds, if any
+    tx_hash TEXT,
+    -- Topic of the transfer contract event that moved the funds
+    event TEXT NOT NULL,
+    -- Either 'in' for incoming or 'out' for outgoing funds
+    direction TEXT NOT NULL,
+    value INTEGER NOT NULL,
+    -- Fee paid by the account, in LUX
+    fee INTEGER NOT NULL
+);
+
+CREATE INDEX moonlight_history_account_idx ON moonlight_history (account, block_height);
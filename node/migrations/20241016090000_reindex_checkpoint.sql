@@ -0,0 +1,7 @@
+-- Sqlite schema for the checkpoint of the archive reindex
+CREATE TABLE reindex_checkpoint (
+    -- The table holds a single row
+    id INTEGER PRIMARY KEY NOT NULL CHECK (id = 0),
+    -- Height of the last block replayed by the reindex
+    block_height INTEGER NOT NULL
+);
@@ -0,0 +1,22 @@
+-- Fill the contract events table with the events of the blocks archived
+-- before it existed. Origins are stored in the json as arrays of bytes.
+INSERT INTO contract_events (block_height, block_hash, origin, target, topic, data)
+SELECT
+    a.block_height,
+    a.block_hash,
+    (
+        SELECT group_concat(byte, '')
+        FROM (
+            SELECT printf('%02x', o.value) AS byte
+            FROM json_each(e.value, '$.origin') AS o
+            ORDER BY o.key
+        )
+    ),
+    json_extract(e.value, '$.event.target'),
+    json_extract(e.value, '$.event.topic'),
+    json_extract(e.value, '$.event.data')
+FROM archive AS a, json_each(a.json_contract_events) AS e
+WHERE NOT EXISTS (
+    SELECT 1 FROM contract_events AS c WHERE c.block_hash = a.block_hash
+)
+ORDER BY a.block_height, e.key;
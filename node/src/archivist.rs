@@ -4,8 +4,13 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+mod moonlight;
+mod reindex;
+
+pub use reindex::reindex;
+
 use crate::database::archive::SQLiteArchive;
-use crate::database::Archivist;
+use crate::database::{Archivist, Ledger};
 use crate::{database, vm, LongLivedService, Network};
 use async_trait::async_trait;
 use node_data::archive::ArchivalData;
@@ -26,7 +31,7 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
     async fn execute(
         &mut self,
         _: Arc<RwLock<N>>,
-        _: Arc<RwLock<DB>>,
+        db: Arc<RwLock<DB>>,
         _: Arc<RwLock<VM>>,
     ) -> anyhow::Result<usize> {
         loop {
@@ -63,7 +68,10 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
                     ArchivalData::FinalizedBlock(blk_height, hex_blk_hash) => {
                         if let Err(e) = self
                             .archivist
-                            .mark_block_finalized(blk_height, hex_blk_hash)
+                            .mark_block_finalized(
+                                blk_height,
+                                hex_blk_hash.clone(),
+                            )
                             .await
                         {
                             error!(
@@ -71,6 +79,30 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
                                 e
                             );
                         }
+
+                        // Finalized blocks cannot be reverted, so their
+                        // Moonlight account history can be safely indexed
+                        if let Err(e) = archive_moonlight_history(
+                            &self.archivist,
+                            &db,
+                            &hex_blk_hash,
+                        )
+                        .await
+                        {
+                            error!(
+                                "Failed to archive moonlight history: {:?}",
+                                e
+                            );
+                        }
+                    }
+                    ArchivalData::MempoolTx(tx_hash, event, timestamp) => {
+                        if let Err(e) = self
+                            .archivist
+                            .store_mempool_event(tx_hash, event, timestamp)
+                            .await
+                        {
+                            error!("Failed to archive mempool event: {:?}", e);
+                        }
                     }
                 }
             } else {
@@ -90,3 +122,26 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
         "archivist"
     }
 }
+
+/// Stores the movements of funds of the Moonlight accounts within the block
+/// with the given hash.
+async fn archive_moonlight_history<DB: database::DB>(
+    archive: &SQLiteArchive,
+    db: &RwLock<DB>,
+    hex_block_hash: &str,
+) -> anyhow::Result<()> {
+    let Some((block_height, events)) =
+        archive.fetch_block_events_by_hash(hex_block_hash).await?
+    else {
+        return Ok(());
+    };
+
+    let entries = db.read().await.view(|t| {
+        moonlight::history_entries(block_height, &events, |tx_id| {
+            let tx = t.get_ledger_tx_by_hash(tx_id).ok().flatten()?;
+            Some(tx.inner.gas_price())
+        })
+    });
+
+    archive.store_moonlight_history(block_height, entries).await
+}
@@ -0,0 +1,167 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bytes::Serializable;
+use execution_core::transfer::TRANSFER_CONTRACT;
+use node_data::events::contract::ContractTxEvent;
+use node_data::ledger::Hash;
+use tracing::warn;
+
+use crate::database::archive::MoonlightHistoryEntry;
+use crate::transfer::{moonlight_transfers, Direction};
+
+const IN: &str = "in";
+const OUT: &str = "out";
+
+/// Extracts the movements of funds to and from Moonlight accounts out of the
+/// events emitted within a block.
+///
+/// `gas_price` returns the gas price paid by the transaction with the given
+/// hash, which is needed to compute the fee paid by the sender.
+pub(crate) fn history_entries(
+    block_height: u64,
+    events: &[ContractTxEvent],
+    gas_price: impl Fn(&Hash) -> Option<u64>,
+) -> Vec<MoonlightHistoryEntry> {
+    let mut entries = vec![];
+
+    for tx_event in events {
+        let event = &tx_event.event;
+        if event.target.0 != TRANSFER_CONTRACT {
+            continue;
+        }
+
+        let Some(transfers) = moonlight_transfers(&event.topic, &event.data)
+        else {
+            warn!("Cannot decode {} event of transfer contract", event.topic);
+            continue;
+        };
+
+        for transfer in transfers {
+            let fee = match transfer.gas_spent {
+                Some(gas_spent) => {
+                    let price = tx_event.origin.as_ref().and_then(&gas_price);
+                    if price.is_none() {
+                        warn!("Cannot find gas price of moonlight transaction");
+                    }
+                    gas_spent.saturating_mul(price.unwrap_or_default())
+                }
+                None => 0,
+            };
+            let direction = match transfer.direction {
+                Direction::In => IN,
+                Direction::Out => OUT,
+            };
+
+            entries.push(MoonlightHistoryEntry {
+                account: bs58::encode(transfer.account.to_bytes())
+                    .into_string(),
+                block_height,
+                tx_hash: tx_event.origin.map(hex::encode),
+                event: event.topic.clone(),
+                direction: direction.to_string(),
+                value: transfer.value,
+                fee,
+            });
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use execution_core::signatures::bls::{
+        PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+    };
+    use execution_core::transfer::{
+        MoonlightTransactionEvent, TransferToAccountEvent,
+    };
+    use execution_core::ContractId;
+    use node_data::events::contract::{ContractEvent, WrappedContractId};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn contract_event<E>(
+        target: ContractId,
+        topic: &str,
+        data: &E,
+        origin: Hash,
+    ) -> ContractTxEvent
+    where
+        E: rkyv::Serialize<rkyv::ser::serializers::AllocSerializer<256>>,
+    {
+        ContractTxEvent {
+            event: ContractEvent {
+                target: WrappedContractId(target),
+                topic: topic.to_string(),
+                data: rkyv::to_bytes::<_, 256>(data).unwrap().to_vec(),
+            },
+            origin: Some(origin),
+        }
+    }
+
+    #[test]
+    fn test_history_entries() {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+        let from = AccountPublicKey::from(&AccountSecretKey::random(&mut rng));
+        let to = AccountPublicKey::from(&AccountSecretKey::random(&mut rng));
+
+        let moonlight = MoonlightTransactionEvent {
+            from,
+            to: Some(to),
+            value: 100,
+            memo: vec![],
+            gas_spent: 10,
+        };
+        let transfer = TransferToAccountEvent {
+            sender: ContractId::from_bytes([1; 32]),
+            value: 50,
+            receiver: from,
+        };
+
+        let events = vec![
+            contract_event(TRANSFER_CONTRACT, "moonlight", &moonlight, [1; 32]),
+            contract_event(
+                TRANSFER_CONTRACT,
+                "transfer_to_account",
+                &transfer,
+                [2; 32],
+            ),
+            // Events of other contracts are ignored
+            contract_event(
+                ContractId::from_bytes([1; 32]),
+                "moonlight",
+                &moonlight,
+                [3; 32],
+            ),
+        ];
+
+        let entries = history_entries(7, &events, |tx_id| {
+            (tx_id == &[1; 32]).then_some(2)
+        });
+
+        let from = bs58::encode(from.to_bytes()).into_string();
+        let to = bs58::encode(to.to_bytes()).into_string();
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|e| (e.account.as_str(), e.direction.as_str(), e.value, e.fee))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (from.as_str(), OUT, 100, 20),
+                (to.as_str(), IN, 100, 0),
+                (from.as_str(), IN, 50, 0),
+            ]
+        );
+        assert!(entries.iter().all(|e| e.block_height == 7));
+        assert_eq!(entries[2].tx_hash, Some(hex::encode([2; 32])));
+        assert_eq!(entries[2].event, "transfer_to_account");
+    }
+}
@@ -0,0 +1,188 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail};
+use dusk_consensus::config::ConsensusParams;
+use dusk_consensus::user::provisioners::ContextProvisioners;
+use node_data::archive::ArchivalData;
+use node_data::ledger::{Block, Header, Label};
+use node_data::message::payload::{RatificationResult, Vote};
+use tokio::sync::mpsc::Receiver;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use super::archive_moonlight_history;
+use crate::chain::verify_att;
+use crate::database::archive::SQLiteArchive;
+use crate::database::{self, Archivist, Ledger, LedgerRecord};
+use crate::vm::VMExecution;
+
+/// Rebuilds the archive by replaying through the VM the finalized blocks
+/// stored in the ledger, up to height `to` or to the last finalized block.
+///
+/// The VM must be at the state resulting from the block of the last
+/// checkpoint stored in the archive, or at the genesis state if there is
+/// none, and must send the archival data of the blocks it accepts to
+/// `archive_receiver`. A checkpoint is stored after every replayed block, so
+/// that an interrupted reindex can be resumed.
+///
+/// The checkpoint is stored before the VM state is finalized, so a VM left
+/// one block behind the checkpoint by an interruption replays that block
+/// again, archiving a block being idempotent.
+///
+/// Attestations are verified against the given consensus parameters, which
+/// must be the ones of the network the blocks come from.
+///
+/// Fails if the blocks to replay have been pruned from the ledger.
+///
+/// Returns the height of the last replayed block.
+pub async fn reindex<DB: database::DB, VM: VMExecution>(
+    db: Arc<RwLock<DB>>,
+    vm: &VM,
+    archive: &SQLiteArchive,
+    archive_receiver: &mut Receiver<ArchivalData>,
+    to: Option<u64>,
+    params: &ConsensusParams,
+) -> anyhow::Result<u64> {
+    let checkpoint = archive.fetch_reindex_checkpoint().await?.unwrap_or(0);
+    let state_root = vm.get_state_root()?;
+
+    let mut height = checkpoint;
+    let mut prev_header = fetch_final_header(&db, height).await?;
+    if prev_header.state_hash != state_root && height > 0 {
+        height -= 1;
+        prev_header = fetch_final_header(&db, height).await?;
+    }
+
+    if prev_header.state_hash != state_root {
+        bail!("VM state does not match the block at height {checkpoint}");
+    }
+
+    let mut prev_seed = match height {
+        0 => prev_header.seed,
+        _ => fetch_final_header(&db, height - 1).await?.seed,
+    };
+
+    let mut provisioners =
+        ContextProvisioners::new(vm.get_provisioners(state_root)?);
+    if height > 0 {
+        provisioners.apply_changes(vm.get_changed_provisioners(state_root)?);
+    }
+
+    info!("Reindexing archive from height {}", height + 1);
+
+    while to.map_or(true, |to| height < to) {
+        let Some(blk) = fetch_final_block(&db, height + 1).await? else {
+            break;
+        };
+        let header = blk.header();
+
+        // The voters of the previous block are rewarded by the accepted one
+        let voters = if prev_header.height == 0 {
+            vec![]
+        } else {
+            let (_, _, voters) = verify_att(
+                &header.prev_block_cert,
+                prev_header.to_consensus_header(),
+                prev_seed,
+                provisioners.prev(),
+                RatificationResult::Success(Vote::Valid(prev_header.hash)),
+                params,
+            )
+            .await
+            .map_err(|e| {
+                anyhow!("Invalid attestation of block {height}: {e:?}")
+            })?;
+            voters
+        };
+
+        vm.accept(&blk, &voters[..])?;
+
+        while let Ok(msg) = archive_receiver.try_recv() {
+            let ArchivalData::ArchivedEvents(blk_height, blk_hash, events) =
+                msg
+            else {
+                continue;
+            };
+
+            let hex_blk_hash = hex::encode(blk_hash);
+            if archive
+                .fetch_block_events_by_hash(&hex_blk_hash)
+                .await?
+                .is_some()
+            {
+                warn!("Block {blk_height} is already archived");
+                continue;
+            }
+            archive
+                .store_vm_events(blk_height, blk_hash, events)
+                .await?;
+        }
+
+        let hex_blk_hash = hex::encode(header.hash);
+        archive
+            .mark_block_finalized(header.height, hex_blk_hash.clone())
+            .await?;
+        archive_moonlight_history(archive, &db, &hex_blk_hash).await?;
+        archive.store_reindex_checkpoint(header.height).await?;
+
+        vm.finalize_state(header.state_hash, vec![prev_header.state_hash])?;
+
+        provisioners.update(vm.get_provisioners(header.state_hash)?);
+        provisioners
+            .apply_changes(vm.get_changed_provisioners(header.state_hash)?);
+
+        prev_seed = prev_header.seed;
+        prev_header = header.clone();
+        height = header.height;
+    }
+
+    info!("Reindexed archive up to height {height}");
+
+    Ok(height)
+}
+
+/// Fetches the header of the block at the given height, failing if it is
+/// not finalized.
+async fn fetch_final_header<DB: database::DB>(
+    db: &RwLock<DB>,
+    height: u64,
+) -> anyhow::Result<Header> {
+    let blk = fetch_final_block(db, height)
+        .await?
+        .ok_or_else(|| anyhow!("Block {height} is not finalized"))?;
+    Ok(blk.header().clone())
+}
+
+/// Fetches the block at the given height, if it is finalized.
+///
+/// Fails if the transactions and faults of the block have been pruned, as
+/// the block cannot be replayed.
+async fn fetch_final_block<DB: database::DB>(
+    db: &RwLock<DB>,
+    height: u64,
+) -> anyhow::Result<Option<Block>> {
+    db.read().await.view(|t| {
+        let hash = match t.fetch_block_label_by_height(height)? {
+            Some((hash, Label::Final(_))) => hash,
+            _ => return Ok(None),
+        };
+
+        match t.fetch_block_record(&hash)? {
+            LedgerRecord::Found(blk) => Ok(Some(blk)),
+            LedgerRecord::Pruned => {
+                let pruned_height = t.fetch_pruned_height()?.unwrap_or(height);
+                Err(anyhow!(
+                    "Cannot replay block {height}: blocks are pruned up to \
+                     height {pruned_height}"
+                ))
+            }
+            LedgerRecord::NotFound => Ok(None),
+        }
+    })
+}
@@ -22,6 +22,7 @@ use crate::{database, vm, Network};
 use crate::{LongLivedService, Message};
 use anyhow::Result;
 use async_trait::async_trait;
+use dusk_consensus::config::ConsensusParams;
 use dusk_consensus::errors::ConsensusError;
 pub use header_validation::verify_att;
 use node_data::events::Event;
@@ -55,6 +56,7 @@ pub struct ChainSrv<N: Network, DB: database::DB, VM: vm::VMExecution> {
     max_consensus_queue_size: usize,
     event_sender: Sender<Event>,
     genesis_timestamp: u64,
+    consensus_params: ConsensusParams,
 }
 
 #[async_trait]
@@ -94,6 +96,7 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
             vm,
             self.max_consensus_queue_size,
             self.event_sender.clone(),
+            self.consensus_params,
         )
         .await?;
 
@@ -243,6 +246,7 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> ChainSrv<N, DB, VM> {
         max_inbound_size: usize,
         event_sender: Sender<Event>,
         genesis_timestamp: u64,
+        consensus_params: ConsensusParams,
     ) -> Self {
         info!(
             "ChainSrv::new with keys_path: {}, max_inbound_size: {}",
@@ -256,6 +260,7 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> ChainSrv<N, DB, VM> {
             max_consensus_queue_size: max_inbound_size,
             event_sender,
             genesis_timestamp,
+            consensus_params,
         }
     }
 
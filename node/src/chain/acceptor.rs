@@ -8,7 +8,7 @@ use crate::database::{self, Candidate, Ledger, Mempool, Metadata};
 use crate::{vm, Message, Network};
 use anyhow::{anyhow, Result};
 use dusk_consensus::commons::TimeoutSet;
-use dusk_consensus::config::{MAX_STEP_TIMEOUT, MIN_STEP_TIMEOUT};
+use dusk_consensus::config::ConsensusParams;
 use dusk_consensus::errors::{ConsensusError, HeaderError};
 use dusk_consensus::user::provisioners::{ContextProvisioners, Provisioners};
 use node_data::bls::PublicKey;
@@ -75,6 +75,9 @@ pub(crate) struct Acceptor<N: Network, DB: database::DB, VM: vm::VMExecution> {
     pub(crate) network: Arc<RwLock<N>>,
 
     event_sender: Sender<Event>,
+
+    /// Consensus parameters of the network
+    params: ConsensusParams,
 }
 
 impl<DB: database::DB, VM: vm::VMExecution, N: Network> Drop
@@ -136,6 +139,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         vm: Arc<RwLock<VM>>,
         max_queue_size: usize,
         event_sender: Sender<Event>,
+        params: ConsensusParams,
     ) -> anyhow::Result<Self> {
         let tip_height = tip.inner().header().height;
         let tip_state_hash = tip.inner().header().state_hash;
@@ -148,17 +152,19 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             provisioners_list.apply_changes(changed_provisioners);
         }
 
+        let task =
+            Task::new_with_keys(keys_path.to_string(), max_queue_size, params)
+                .await?;
+
         let acc = Self {
             tip: RwLock::new(tip),
             provisioners_list: RwLock::new(provisioners_list),
             db: db.clone(),
             vm: vm.clone(),
             network: network.clone(),
-            task: RwLock::new(Task::new_with_keys(
-                keys_path.to_string(),
-                max_queue_size,
-            )?),
+            task: RwLock::new(task),
             event_sender,
+            params,
         };
 
         // NB. After restart, state_root returned by VM is always the last
@@ -220,8 +226,13 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         };
 
         let prev_seed = self.get_prev_block_seed().await.expect("valid seed");
-        Validator::<DB>::get_voters(tip.header(), provisioners_list, prev_seed)
-            .await
+        Validator::<DB>::get_voters(
+            tip.header(),
+            provisioners_list,
+            prev_seed,
+            &self.params,
+        )
+        .await
     }
 
     // Re-route message to consensus task
@@ -458,6 +469,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             &tip.inner().header().clone(),
             &provisioners_list,
             blk.header(),
+            self.params,
         )
         .await?;
 
@@ -472,7 +484,13 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         // Persist block in consistency with the VM state update
         let (label, finalized) = {
             let header = blk.header();
-            verify_faults(self.db.clone(), header.height, blk.faults()).await?;
+            verify_faults(
+                self.db.clone(),
+                header.height,
+                blk.faults(),
+                &self.params,
+            )
+            .await?;
 
             let vm = self.vm.write().await;
 
@@ -604,6 +622,19 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         gauge!("dusk_stored_candidates_count")
             .set(count.unwrap_or_default() as f64);
 
+        // Prune the ledger data of old finalized blocks, if enabled
+        if finalized {
+            let tip_height = tip.inner().header().height;
+            match self.db.read().await.prune(tip_height) {
+                Ok(0) => {}
+                Ok(pruned) => {
+                    debug!(event = "ledger pruned", blocks = pruned);
+                    counter!("dusk_pruned_blocks").increment(pruned as u64);
+                }
+                Err(e) => warn!("Error while pruning the ledger: {e}"),
+            }
+        }
+
         {
             // Avoid accumulation of future msgs while the node is syncing up
             let round = tip.inner().header().height;
@@ -1024,7 +1055,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
                     .unwrap_or_default(),
                 None => {
                     let mut metric = AverageElapsedTime::default();
-                    metric.push_back(MAX_STEP_TIMEOUT);
+                    metric.push_back(self.params.max_step_timeout);
                     metric
                 }
             };
@@ -1035,9 +1066,9 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         metric
             .unwrap_or_default()
             .average()
-            .unwrap_or(MIN_STEP_TIMEOUT)
-            .max(MIN_STEP_TIMEOUT)
-            .min(MAX_STEP_TIMEOUT)
+            .unwrap_or(self.params.min_step_timeout)
+            .max(self.params.min_step_timeout)
+            .min(self.params.max_step_timeout)
     }
 
     async fn get_prev_block_seed(&self) -> Result<Seed> {
@@ -1127,6 +1158,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             &prev_header,
             &provisioners_list,
             new,
+            self.params,
         )
         .await?;
 
@@ -1149,8 +1181,9 @@ pub(crate) async fn verify_block_header<DB: database::DB>(
     prev_header: &ledger::Header,
     provisioners: &ContextProvisioners,
     header: &ledger::Header,
+    params: ConsensusParams,
 ) -> Result<(u8, Vec<Voter>, Vec<Voter>), HeaderError> {
-    let validator = Validator::new(db, prev_header, provisioners);
+    let validator = Validator::new(db, prev_header, provisioners, params);
     let expected_generator = provisioners.current().get_generator(
         header.iteration,
         prev_header.seed,
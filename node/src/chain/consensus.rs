@@ -4,16 +4,23 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use crate::database::signing_history::SigningHistoryDB;
 use crate::database::{self, Candidate, Mempool, Metadata};
 use crate::{vm, Message};
 use async_trait::async_trait;
 use dusk_consensus::commons::{RoundUpdate, TimeoutSet};
+use dusk_consensus::config::ConsensusParams;
 use dusk_consensus::consensus::Consensus;
+use dusk_consensus::equivocation::EquivocationRegistry;
 use dusk_consensus::errors::{ConsensusError, HeaderError, OperationError};
 use dusk_consensus::operations::{
     CallParams, Operations, Output, VerificationOutput, Voter,
 };
 use dusk_consensus::queue::MsgRegistry;
+#[cfg(unix)]
+use dusk_consensus::signer::RemoteSigner;
+use dusk_consensus::signer::{ConsensusSigner, InMemorySigner};
+use dusk_consensus::slashing_protection::SlashingProtection;
 use dusk_consensus::user::provisioners::ContextProvisioners;
 use node_data::bls::PublicKeyBytes;
 use node_data::ledger::{Block, Fault, Hash, Header};
@@ -42,6 +49,10 @@ pub(crate) struct Task {
 
     pub(crate) future_msg: Arc<Mutex<MsgRegistry<Message>>>,
 
+    /// Signed messages and faults of the equivocating provisioners, kept
+    /// across consensus tasks until the faults are included in a block
+    pub(crate) equivocations: Arc<Mutex<EquivocationRegistry>>,
+
     pub(crate) result: AsyncQueue<Result<(), ConsensusError>>,
 
     /// a pair of join_handle and cancel_chan of the running consensus task.
@@ -52,29 +63,26 @@ pub(crate) struct Task {
     /// task id a counter to track consensus tasks
     task_id: u64,
 
-    /// Loaded Consensus keys
-    pub keys: (
-        execution_core::signatures::bls::SecretKey,
-        node_data::bls::PublicKey,
-    ),
+    /// Signer of the consensus messages
+    pub signer: Arc<dyn ConsensusSigner>,
+
+    /// Signing history of the consensus keys
+    slashing_protection: SlashingProtection,
+
+    /// Consensus parameters of the network
+    params: ConsensusParams,
 }
 
 impl Task {
-    /// Creates a new consensus task with the given keys encrypted with password
-    /// from env var DUSK_CONSENSUS_KEYS_PASS.
-    pub(crate) fn new_with_keys(
+    /// Creates a new consensus task signing with the given keys, or with a
+    /// remote signer if configured (see `load_signer`).
+    pub(crate) async fn new_with_keys(
         path: String,
         max_inbound_size: usize,
+        params: ConsensusParams,
     ) -> anyhow::Result<Self> {
-        let pwd = std::env::var("DUSK_CONSENSUS_KEYS_PASS")
-            .map_err(|_| anyhow::anyhow!("DUSK_CONSENSUS_KEYS_PASS not set"))?;
-        info!(event = "loading consensus keys", path = path);
-        let keys = node_data::bls::load_keys(path, pwd)?;
-
-        info!(
-            event = "loaded consensus keys",
-            pubkey = format!("{:?}", keys.1)
-        );
+        let (signer, slashing_protection) =
+            Self::load_signer(path, params).await?;
 
         Ok(Self {
             main_inbound: AsyncQueue::bounded(
@@ -86,13 +94,60 @@ impl Task {
                 "consensus_outbound",
             ),
             future_msg: Arc::new(Mutex::new(MsgRegistry::default())),
+            equivocations: Arc::new(Mutex::new(EquivocationRegistry::new(
+                params,
+            ))),
             result: AsyncQueue::bounded(1, "consensus_result"),
             running_task: None,
             task_id: 0,
-            keys,
+            signer,
+            slashing_protection,
+            params,
         })
     }
 
+    /// Loads the signer of the consensus messages, along with the slashing
+    /// protection guarding it.
+    ///
+    /// If DUSK_CONSENSUS_SIGNER_SOCKET is set, the messages are signed by the
+    /// signer process listening on that Unix socket, which keeps the signing
+    /// history of the keys. Otherwise the keys at the given path are
+    /// decrypted with the password from env var DUSK_CONSENSUS_KEYS_PASS, and
+    /// their signing history is kept next to them.
+    async fn load_signer(
+        path: String,
+        params: ConsensusParams,
+    ) -> anyhow::Result<(Arc<dyn ConsensusSigner>, SlashingProtection)> {
+        #[cfg(unix)]
+        if let Ok(socket) = std::env::var("DUSK_CONSENSUS_SIGNER_SOCKET") {
+            info!(event = "connecting to consensus signer", socket);
+            let signer = RemoteSigner::connect(socket).await?;
+
+            info!(
+                event = "connected to consensus signer",
+                pubkey = format!("{:?}", signer.public_key())
+            );
+            return Ok((Arc::new(signer), SlashingProtection::default()));
+        }
+
+        let history_path = SigningHistoryDB::default_path(&path);
+        let history = SigningHistoryDB::open(history_path)?;
+        let slashing_protection =
+            SlashingProtection::new(Arc::new(history), params);
+
+        let pwd = std::env::var("DUSK_CONSENSUS_KEYS_PASS")
+            .map_err(|_| anyhow::anyhow!("DUSK_CONSENSUS_KEYS_PASS not set"))?;
+        info!(event = "loading consensus keys", path = path);
+        let keys = node_data::bls::load_keys(path, pwd)?;
+
+        info!(
+            event = "loaded consensus keys",
+            pubkey = format!("{:?}", keys.1)
+        );
+        let signer = Arc::new(InMemorySigner::new(keys.0));
+        Ok((signer, slashing_protection))
+    }
+
     pub(crate) fn spawn<D: database::DB, VM: vm::VMExecution>(
         &mut self,
         tip: &node_data::ledger::Block,
@@ -107,22 +162,25 @@ impl Task {
             self.main_inbound.clone(),
             self.outbound.clone(),
             self.future_msg.clone(),
+            self.equivocations.clone(),
             Arc::new(Executor::new(
                 db,
                 vm,
                 tip.header().clone(),
                 provisioners_list, // TODO: Avoid cloning
+                self.params,
             )),
             Arc::new(Mutex::new(CandidateDB::new(db.clone()))),
         );
 
         let ru = RoundUpdate::new(
-            self.keys.1.clone(),
-            self.keys.0.clone(),
+            self.signer.clone(),
             tip.header(),
+            self.params,
             base_timeout.clone(),
             voters,
-        );
+        )
+        .with_slashing_protection(self.slashing_protection.clone());
 
         self.task_id += 1;
 
@@ -252,6 +310,7 @@ pub struct Executor<DB: database::DB, VM: vm::VMExecution> {
     vm: Arc<RwLock<VM>>,
     tip_header: ledger::Header,
     provisioners: ContextProvisioners,
+    params: ConsensusParams,
 }
 
 impl<DB: database::DB, VM: vm::VMExecution> Executor<DB, VM> {
@@ -260,12 +319,14 @@ impl<DB: database::DB, VM: vm::VMExecution> Executor<DB, VM> {
         vm: &Arc<RwLock<VM>>,
         tip_header: ledger::Header,
         provisioners: ContextProvisioners,
+        params: ConsensusParams,
     ) -> Self {
         Executor {
             db: db.clone(),
             vm: vm.clone(),
             tip_header,
             provisioners,
+            params,
         }
     }
 }
@@ -281,6 +342,7 @@ impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
             self.db.clone(),
             &self.tip_header,
             &self.provisioners,
+            self.params,
         );
 
         validator
@@ -297,6 +359,7 @@ impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
             self.db.clone(),
             &self.tip_header,
             &self.provisioners,
+            self.params,
         );
         Ok(validator.verify_faults(block_height, faults).await?)
     }
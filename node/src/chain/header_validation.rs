@@ -7,9 +7,7 @@
 use crate::database;
 use crate::database::Ledger;
 use dusk_bytes::Serializable;
-use dusk_consensus::config::{
-    is_emergency_iter, MINIMUM_BLOCK_TIME, RELAX_ITERATION_THRESHOLD,
-};
+use dusk_consensus::config::{ConsensusParams, RELAX_ITERATION_THRESHOLD};
 use dusk_consensus::errors::{
     AttestationError, FailedIterationError, HeaderError,
 };
@@ -46,6 +44,7 @@ pub(crate) struct Validator<'a, DB: database::DB> {
     pub(crate) db: Arc<RwLock<DB>>,
     prev_header: &'a ledger::Header,
     provisioners: &'a ContextProvisioners,
+    params: ConsensusParams,
 }
 
 impl<'a, DB: database::DB> Validator<'a, DB> {
@@ -53,11 +52,13 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
         db: Arc<RwLock<DB>>,
         prev_header: &'a ledger::Header,
         provisioners: &'a ContextProvisioners,
+        params: ConsensusParams,
     ) -> Self {
         Self {
             db,
             prev_header,
             provisioners,
+            params,
         }
     }
 
@@ -90,6 +91,7 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
                 self.prev_header.seed,
                 self.provisioners.current(),
                 RatificationResult::Success(Vote::Valid(header.hash)),
+                &self.params,
             )
             .await?;
         }
@@ -162,7 +164,7 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
 
         // Ensure rule of minimum block time is addressed
         if candidate_block.timestamp
-            < self.prev_header.timestamp + MINIMUM_BLOCK_TIME
+            < self.prev_header.timestamp + self.params.minimum_block_time
         {
             return Err(HeaderError::BlockTimeLess);
         }
@@ -251,6 +253,7 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
             prev_block_seed,
             self.provisioners.prev(),
             RatificationResult::Success(Vote::Valid(prev_block_hash)),
+            &self.params,
         )
         .await?;
 
@@ -299,6 +302,7 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
                     self.prev_header.seed,
                     self.provisioners.current(),
                     RatificationResult::Fail(Vote::default()),
+                    &self.params,
                 )
                 .await?;
 
@@ -319,6 +323,7 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
         blk: &'a ledger::Header,
         provisioners: &Provisioners,
         prev_block_seed: Seed,
+        params: &ConsensusParams,
     ) -> Vec<Voter> {
         let att = &blk.att;
         let consensus_header = blk.to_consensus_header();
@@ -331,6 +336,7 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
             &committee,
             prev_block_seed,
             StepName::Validation,
+            params,
         )
         .await;
 
@@ -340,6 +346,7 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
             &committee,
             prev_block_seed,
             StepName::Ratification,
+            params,
         )
         .await;
 
@@ -352,7 +359,8 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
         current_height: u64,
         faults: &[Fault],
     ) -> Result<(), InvalidFault> {
-        verify_faults(self.db.clone(), current_height, faults).await
+        verify_faults(self.db.clone(), current_height, faults, &self.params)
+            .await
     }
 }
 
@@ -360,10 +368,16 @@ pub async fn verify_faults<DB: database::DB>(
     db: Arc<RwLock<DB>>,
     current_height: u64,
     faults: &[Fault],
+    params: &ConsensusParams,
 ) -> Result<(), InvalidFault> {
     for f in faults {
+        if matches!(f, Fault::DoubleCandidateHeader(..))
+            && !params.candidate_faults_enabled(current_height)
+        {
+            return Err(InvalidFault::Inactive);
+        }
         let fault_header = f.validate(current_height)?;
-        if is_emergency_iter(fault_header.iteration) {
+        if params.is_emergency_iter(fault_header.iteration) {
             return Err(InvalidFault::EmergencyIteration);
         }
         db.read()
@@ -400,6 +414,7 @@ pub async fn verify_att(
     curr_seed: Signature,
     curr_eligible_provisioners: &Provisioners,
     expected_result: RatificationResult,
+    params: &ConsensusParams,
 ) -> Result<(QuorumResult, QuorumResult, Vec<Voter>), AttestationError> {
     // Check expected result
     match (att.result, expected_result) {
@@ -434,6 +449,7 @@ pub async fn verify_att(
         &committee,
         curr_seed,
         StepName::Validation,
+        params,
     )
     .await
     .map_err(|s| AttestationError::InvalidVotes(StepName::Validation, s))?;
@@ -446,6 +462,7 @@ pub async fn verify_att(
         &committee,
         curr_seed,
         StepName::Ratification,
+        params,
     )
     .await
     .map_err(|s| AttestationError::InvalidVotes(StepName::Ratification, s))?;
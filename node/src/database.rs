@@ -10,10 +10,16 @@ use std::path::Path;
 #[cfg(feature = "archive")]
 pub mod archive;
 pub mod rocksdb;
+pub mod signing_history;
 
 use anyhow::Result;
+use execution_core::signatures::bls::PublicKey as AccountPublicKey;
+use execution_core::ContractId;
 #[cfg(feature = "archive")]
-use {node_data::events::contract::ContractTxEvent, node_data::ledger::Hash};
+use {
+    node_data::archive::MempoolTxEvent,
+    node_data::events::contract::ContractTxEvent, node_data::ledger::Hash,
+};
 
 use node_data::ledger::{self, Fault, Label, SpendingId, SpentTransaction};
 
@@ -51,9 +57,35 @@ pub trait DB: Send + Sync + 'static {
     where
         F: for<'a> FnOnce(&Self::P<'a>) -> Result<T>;
 
+    /// Prunes the transactions and faults of the finalized blocks that are
+    /// older than the pruning window configured in [`DatabaseOptions`], if
+    /// any, relative to `tip_height`.
+    ///
+    /// Returns the number of pruned blocks.
+    fn prune(&self, tip_height: u64) -> Result<usize>;
+
     fn close(&mut self);
 }
 
+/// A ledger record which may have been pruned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerRecord<T> {
+    Found(T),
+    /// The record is known, but its data has been pruned
+    Pruned,
+    NotFound,
+}
+
+impl<T> LedgerRecord<T> {
+    /// Returns the record, if it is found and not pruned
+    pub fn found(self) -> Option<T> {
+        match self {
+            LedgerRecord::Found(record) => Some(record),
+            _ => None,
+        }
+    }
+}
+
 /// Implements both read-write and read-only transactions to DB.
 
 pub trait Ledger {
@@ -73,7 +105,19 @@ pub trait Ledger {
 
     fn fetch_light_block(&self, hash: &[u8]) -> Result<Option<LightBlock>>;
 
+    /// Fetches the block with the given hash.
+    ///
+    /// Returns `None` for pruned blocks as well, see
+    /// [`Ledger::fetch_block_record`] to tell them apart.
     fn fetch_block(&self, hash: &[u8]) -> Result<Option<ledger::Block>>;
+
+    /// Fetches the block with the given hash, reporting whether its
+    /// transactions and faults have been pruned.
+    fn fetch_block_record(
+        &self,
+        hash: &[u8],
+    ) -> Result<LedgerRecord<ledger::Block>>;
+
     fn fetch_block_hash_by_height(
         &self,
         height: u64,
@@ -106,6 +150,46 @@ pub trait Ledger {
 
     fn fetch_faults_by_block(&self, start_height: u64) -> Result<Vec<Fault>>;
     fn fetch_faults(&self, faults_ids: &[[u8; 32]]) -> Result<Vec<Fault>>;
+
+    /// Deletes the transactions and faults of the block at the given height,
+    /// keeping its header and its height index.
+    ///
+    /// Blocks are expected to be pruned in ascending order of height.
+    fn prune_block(&self, height: u64) -> Result<()>;
+
+    /// Returns the height of the last pruned block, if any
+    fn fetch_pruned_height(&self) -> Result<Option<u64>>;
+
+    /// Returns the height of the first block whose ledger transactions are
+    /// indexed, the blocks stored while the index was disabled not being
+    /// indexed.
+    ///
+    /// Returns `None` if the transactions index is disabled.
+    fn fetch_tx_index_height(&self) -> Result<Option<u64>>;
+
+    /// Returns the ids of at most `limit` ledger transactions sent or
+    /// received by the given Moonlight account, within an inclusive range of
+    /// block heights, together with their block height.
+    ///
+    /// Returns `None` if the transactions index is disabled.
+    fn fetch_tx_ids_by_account(
+        &self,
+        account: &AccountPublicKey,
+        height_range: (u64, u64),
+        limit: usize,
+    ) -> Result<Option<Vec<(u64, [u8; 32])>>>;
+
+    /// Returns the ids of at most `limit` ledger transactions calling the
+    /// given contract, within an inclusive range of block heights, together
+    /// with their block height.
+    ///
+    /// Returns `None` if the transactions index is disabled.
+    fn fetch_tx_ids_by_contract(
+        &self,
+        contract: &ContractId,
+        height_range: (u64, u64),
+        limit: usize,
+    ) -> Result<Option<Vec<(u64, [u8; 32])>>>;
 }
 
 pub trait Candidate {
@@ -148,6 +232,13 @@ pub trait Mempool {
     /// Get transactions hash from the mempool, searching by spendable ids
     fn get_txs_by_spendable_ids(&self, n: &[SpendingId]) -> HashSet<[u8; 32]>;
 
+    /// Get the nonce and hash of all the mempool transactions sent by a
+    /// Moonlight account, sorted by nonce
+    fn get_txs_by_account(
+        &self,
+        account: &AccountPublicKey,
+    ) -> Result<Vec<(u64, [u8; 32])>>;
+
     /// Get an iterator over the mempool transactions sorted by gas price
     fn get_txs_sorted_by_fee(
         &self,
@@ -158,6 +249,12 @@ pub trait Mempool {
         &self,
     ) -> Result<Box<dyn Iterator<Item = (u64, [u8; 32])> + '_>>;
 
+    /// Get an iterator over the mempool transactions hash by gas price,
+    /// starting from the lowest one
+    fn get_txs_ids_sorted_by_low_fee(
+        &self,
+    ) -> Result<Box<dyn Iterator<Item = (u64, [u8; 32])> + '_>>;
+
     /// Get all transactions hashes.
     fn get_txs_ids(&self) -> Result<Vec<[u8; 32]>>;
 
@@ -209,6 +306,35 @@ pub struct DatabaseOptions {
 
     /// Enables a set of flags for collecting DB stats as log data.
     pub enable_debug: bool,
+
+    /// Prunes the transactions and faults of the finalized blocks older than
+    /// this number of epochs, keeping their headers and their height index.
+    /// It must be at least [`MIN_PRUNE_AFTER_EPOCHS`].
+    ///
+    /// Pruning is disabled if not set, which is required by archive nodes.
+    #[serde(default)]
+    pub prune_after_epochs: Option<u64>,
+
+    /// Indexes the ledger transactions by Moonlight account and by called
+    /// contract. Only the blocks stored while enabled are indexed.
+    #[serde(default)]
+    pub enable_tx_index: bool,
+}
+
+/// Minimum number of epochs kept by the pruning, the faults of the last epoch
+/// being needed to detect double faults
+pub const MIN_PRUNE_AFTER_EPOCHS: u64 = 2;
+
+impl DatabaseOptions {
+    /// Checks that the options can be run by the database
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if let Some(epochs) = self.prune_after_epochs {
+            if epochs < MIN_PRUNE_AFTER_EPOCHS {
+                return Err("pruning must keep at least 2 epochs");
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for DatabaseOptions {
@@ -218,6 +344,8 @@ impl Default for DatabaseOptions {
             mempool_cf_max_write_buffer_size: 10 * 1024 * 1024, // 10 MiB
             blocks_cf_disable_block_cache: true,
             enable_debug: false,
+            prune_after_epochs: None,
+            enable_tx_index: false,
         }
     }
 }
@@ -253,4 +381,19 @@ pub(crate) trait Archivist {
         block_height: u64,
         hex_block_hash: String,
     ) -> Result<bool>;
+
+    async fn store_mempool_event(
+        &self,
+        tx_hash: Hash,
+        event: MempoolTxEvent,
+        timestamp: u64,
+    ) -> Result<()>;
+
+    async fn store_moonlight_history(
+        &self,
+        block_height: u64,
+        entries: Vec<archive::MoonlightHistoryEntry>,
+    ) -> Result<()>;
+
+    async fn store_reindex_checkpoint(&self, block_height: u64) -> Result<()>;
 }
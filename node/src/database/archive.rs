@@ -4,10 +4,13 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::fmt;
 use std::fs;
 use std::path::Path;
+use std::str::FromStr;
 
 use anyhow::Result;
+use node_data::archive::MempoolTxEvent;
 use node_data::events::contract::ContractTxEvent;
 use node_data::ledger::Hash;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
@@ -18,11 +21,84 @@ use super::Archivist;
 const ARCHIVE_FOLDER_NAME: &str = "archive";
 const SQLITE_DB_NAME: &str = "archive.sqlite3";
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SQLiteArchive {
     archive_db: SqlitePool,
 }
 
+/// A recorded lifecycle event of a mempool transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolHistoryEntry {
+    /// Name of the event, see [`MempoolTxEvent::name`]
+    pub event: String,
+    /// Hash of the replacing transaction, for replaced events
+    pub replaced_by: Option<String>,
+    /// Reason why the transaction has been discarded, for discarded events
+    pub reason: Option<String>,
+    pub timestamp: u64,
+}
+
+/// Position of a row in the archive.
+///
+/// Rows are ordered by block height first, since the rows of a block
+/// archived again, or backfilled, get the highest ids.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ArchiveCursor {
+    pub block_height: u64,
+    pub id: u64,
+}
+
+impl fmt::Display for ArchiveCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.block_height, self.id)
+    }
+}
+
+impl FromStr for ArchiveCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (block_height, id) = s
+            .split_once('-')
+            .ok_or_else(|| anyhow::anyhow!("invalid cursor {s}"))?;
+        Ok(Self {
+            block_height: block_height.parse()?,
+            id: id.parse()?,
+        })
+    }
+}
+
+/// A contract event stored in the archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedEvent {
+    pub block_height: u64,
+    pub block_hash: String,
+    /// Hash of the transaction that emitted the event, if any
+    pub origin: Option<String>,
+    /// Contract that emitted the event
+    pub target: String,
+    pub topic: String,
+    /// Hex encoded event data
+    pub data: String,
+}
+
+/// A movement of funds to or from a Moonlight account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoonlightHistoryEntry {
+    /// Base58 encoded public key of the account
+    pub account: String,
+    pub block_height: u64,
+    /// Hash of the transaction that moved the funds, if any
+    pub tx_hash: Option<String>,
+    /// Topic of the transfer contract event that moved the funds
+    pub event: String,
+    /// Either `in` for incoming or `out` for outgoing funds
+    pub direction: String,
+    pub value: u64,
+    /// Fee paid by the account, in LUX
+    pub fee: u64,
+}
+
 impl SQLiteArchive {
     pub async fn create_or_open<T>(path: T) -> Self
     where
@@ -70,6 +146,191 @@ impl SQLiteArchive {
 
         Ok(events.json_contract_events)
     }
+
+    /// Fetch all the recorded mempool events of the transaction with the
+    /// given hash, in chronological order
+    pub async fn fetch_mempool_history(
+        &self,
+        hex_tx_hash: &str,
+    ) -> Result<Vec<MempoolHistoryEntry>> {
+        let mut conn = self.archive_db.acquire().await?;
+
+        let records = sqlx::query!(
+            r#"SELECT event, replaced_by, reason, timestamp FROM mempool_history WHERE tx_hash = ? ORDER BY id"#,
+            hex_tx_hash
+        ).fetch_all(&mut *conn).await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| MempoolHistoryEntry {
+                event: r.event,
+                replaced_by: r.replaced_by,
+                reason: r.reason,
+                timestamp: r.timestamp as u64,
+            })
+            .collect())
+    }
+
+    /// Fetch at most `limit` contract events emitted by the transaction with
+    /// the given hash
+    pub async fn fetch_events_by_tx(
+        &self,
+        hex_origin: &str,
+        limit: u64,
+    ) -> Result<Vec<ArchivedEvent>> {
+        let limit = limit.min(i64::MAX as u64) as i64;
+
+        let mut conn = self.archive_db.acquire().await?;
+
+        let records = sqlx::query!(
+            r#"SELECT block_height, block_hash, origin, target, topic, data FROM contract_events WHERE origin = ?1 ORDER BY id LIMIT ?2"#,
+            hex_origin, limit
+        ).fetch_all(&mut *conn).await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| ArchivedEvent {
+                block_height: r.block_height as u64,
+                block_hash: r.block_hash,
+                origin: r.origin,
+                target: r.target,
+                topic: r.topic,
+                data: r.data,
+            })
+            .collect())
+    }
+
+    /// Fetch at most `limit` events emitted by the given contract, optionally
+    /// filtered by topic and by an inclusive range of block heights.
+    ///
+    /// Every event is returned together with its position in the archive, so
+    /// that the following events can be fetched by passing it as `cursor`.
+    pub async fn fetch_events_by_contract(
+        &self,
+        hex_contract: &str,
+        topic: Option<&str>,
+        height_range: Option<(u64, u64)>,
+        cursor: Option<ArchiveCursor>,
+        limit: u64,
+    ) -> Result<Vec<(ArchiveCursor, ArchivedEvent)>> {
+        let (from, to) = height_range.unwrap_or((0, i64::MAX as u64));
+        let from = from as i64;
+        let to = to.min(i64::MAX as u64) as i64;
+        let cursor = cursor.unwrap_or_default();
+        let cursor_height = cursor.block_height.min(i64::MAX as u64) as i64;
+        let cursor_id = cursor.id.min(i64::MAX as u64) as i64;
+        let limit = limit.min(i64::MAX as u64) as i64;
+
+        let mut conn = self.archive_db.acquire().await?;
+
+        let records = sqlx::query!(
+            r#"SELECT id, block_height, block_hash, origin, target, topic, data FROM contract_events WHERE target = ?1 AND (?2 IS NULL OR topic = ?2) AND block_height BETWEEN ?3 AND ?4 AND (block_height > ?5 OR (block_height = ?5 AND id > ?6)) ORDER BY block_height, id LIMIT ?7"#,
+            hex_contract, topic, from, to, cursor_height, cursor_id, limit
+        ).fetch_all(&mut *conn).await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| {
+                let cursor = ArchiveCursor {
+                    block_height: r.block_height as u64,
+                    id: r.id as u64,
+                };
+                let event = ArchivedEvent {
+                    block_height: r.block_height as u64,
+                    block_hash: r.block_hash,
+                    origin: r.origin,
+                    target: r.target,
+                    topic: r.topic,
+                    data: r.data,
+                };
+                (cursor, event)
+            })
+            .collect())
+    }
+
+    /// Fetch the height and the list of all vm events of the block with the
+    /// given hash
+    pub async fn fetch_block_events_by_hash(
+        &self,
+        hex_block_hash: &str,
+    ) -> Result<Option<(u64, Vec<ContractTxEvent>)>> {
+        let mut conn = self.archive_db.acquire().await?;
+
+        let r = sqlx::query!(
+            r#"SELECT block_height, json_contract_events FROM archive WHERE block_hash = ?"#,
+            hex_block_hash
+        ).fetch_optional(&mut *conn).await?;
+
+        match r {
+            Some(r) => {
+                let events = serde_json::from_str(&r.json_contract_events)?;
+                Ok(Some((r.block_height as u64, events)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Fetch the height of the last block replayed by the archive reindex, if
+    /// any
+    pub async fn fetch_reindex_checkpoint(&self) -> Result<Option<u64>> {
+        let mut conn = self.archive_db.acquire().await?;
+
+        let r = sqlx::query!(
+            r#"SELECT block_height FROM reindex_checkpoint WHERE id = 0"#
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(r.map(|r| r.block_height as u64))
+    }
+
+    /// Fetch at most `limit` history entries of the given Moonlight account,
+    /// optionally filtered by an inclusive range of block heights.
+    ///
+    /// Every entry is returned together with its position in the archive, so
+    /// that the following entries can be fetched by passing it as `cursor`.
+    pub async fn fetch_moonlight_history(
+        &self,
+        account: &str,
+        height_range: Option<(u64, u64)>,
+        cursor: Option<ArchiveCursor>,
+        limit: u64,
+    ) -> Result<Vec<(ArchiveCursor, MoonlightHistoryEntry)>> {
+        let (from, to) = height_range.unwrap_or((0, i64::MAX as u64));
+        let from = from as i64;
+        let to = to.min(i64::MAX as u64) as i64;
+        let cursor = cursor.unwrap_or_default();
+        let cursor_height = cursor.block_height.min(i64::MAX as u64) as i64;
+        let cursor_id = cursor.id.min(i64::MAX as u64) as i64;
+        let limit = limit.min(i64::MAX as u64) as i64;
+
+        let mut conn = self.archive_db.acquire().await?;
+
+        let records = sqlx::query!(
+            r#"SELECT id, account, block_height, tx_hash, event, direction, value, fee FROM moonlight_history WHERE account = ?1 AND block_height BETWEEN ?2 AND ?3 AND (block_height > ?4 OR (block_height = ?4 AND id > ?5)) ORDER BY block_height, id LIMIT ?6"#,
+            account, from, to, cursor_height, cursor_id, limit
+        ).fetch_all(&mut *conn).await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| {
+                let cursor = ArchiveCursor {
+                    block_height: r.block_height as u64,
+                    id: r.id as u64,
+                };
+                let entry = MoonlightHistoryEntry {
+                    account: r.account,
+                    block_height: r.block_height as u64,
+                    tx_hash: r.tx_hash,
+                    event: r.event,
+                    direction: r.direction,
+                    value: r.value as u64,
+                    fee: r.fee as u64,
+                };
+                (cursor, entry)
+            })
+            .collect())
+    }
 }
 
 impl Archivist for SQLiteArchive {
@@ -85,12 +346,28 @@ impl Archivist for SQLiteArchive {
         // Serialize the events to a json string
         let json_contract_events = serde_json::to_string(&events)?;
 
-        let mut conn = self.archive_db.acquire().await?;
+        let mut tx = self.archive_db.begin().await?;
 
         sqlx::query!(
              r#"INSERT INTO archive (block_height, block_hash, json_contract_events) VALUES (?, ?, ?)"#,
             block_height, hex_block_hash, json_contract_events
-        ).execute(&mut *conn).await?.rows_affected();
+        ).execute(&mut *tx).await?.rows_affected();
+
+        // Store every event in its own row, so that they can be queried by
+        // transaction or by contract
+        for event in events {
+            let origin = event.origin.map(hex::encode);
+            let target = hex::encode(event.event.target.0.as_bytes());
+            let topic = event.event.topic;
+            let data = hex::encode(event.event.data);
+
+            sqlx::query!(
+                r#"INSERT INTO contract_events (block_height, block_hash, origin, target, topic, data) VALUES (?, ?, ?, ?, ?, ?)"#,
+                block_height, hex_block_hash, origin, target, topic, data
+            ).execute(&mut *tx).await?;
+        }
+
+        tx.commit().await?;
 
         info!(
             "Archived events from block {} with height {}",
@@ -158,7 +435,7 @@ impl Archivist for SQLiteArchive {
     ) -> Result<bool> {
         let block_height: i64 = block_height as i64;
 
-        let mut conn = self.archive_db.acquire().await?;
+        let mut tx = self.archive_db.begin().await?;
 
         let r = sqlx::query!(
             r#"DELETE FROM archive WHERE block_hash = ? AND (finalized IS NULL OR finalized = 0)
@@ -166,9 +443,20 @@ impl Archivist for SQLiteArchive {
             "#,
             hex_block_hash
         )
-        .fetch_optional(&mut *conn)
+        .fetch_optional(&mut *tx)
         .await?;
 
+        if r.is_some() {
+            sqlx::query!(
+                r#"DELETE FROM contract_events WHERE block_hash = ?"#,
+                hex_block_hash
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
         if let Some(r) = r {
             info!(
                 "Deleted events from block {} with block height: {} at height {}",
@@ -185,6 +473,79 @@ impl Archivist for SQLiteArchive {
             Ok(false)
         }
     }
+
+    /// Store a lifecycle event of the mempool transaction with the given hash.
+    async fn store_mempool_event(
+        &self,
+        tx_hash: Hash,
+        event: MempoolTxEvent,
+        timestamp: u64,
+    ) -> Result<()> {
+        let hex_tx_hash = hex::encode(tx_hash);
+        let event_name = event.name();
+        let (replaced_by, reason) = match event {
+            MempoolTxEvent::Replaced(by) => (Some(hex::encode(by)), None),
+            MempoolTxEvent::Discarded(reason) => (None, Some(reason)),
+            _ => (None, None),
+        };
+        let timestamp = timestamp as i64;
+
+        let mut conn = self.archive_db.acquire().await?;
+
+        sqlx::query!(
+            r#"INSERT INTO mempool_history (tx_hash, event, replaced_by, reason, timestamp) VALUES (?, ?, ?, ?, ?)"#,
+            hex_tx_hash, event_name, replaced_by, reason, timestamp
+        ).execute(&mut *conn).await?;
+
+        Ok(())
+    }
+
+    /// Store the Moonlight account history entries of the block with the
+    /// given height, replacing any previously stored one.
+    async fn store_moonlight_history(
+        &self,
+        block_height: u64,
+        entries: Vec<MoonlightHistoryEntry>,
+    ) -> Result<()> {
+        let block_height: i64 = block_height as i64;
+
+        let mut tx = self.archive_db.begin().await?;
+
+        sqlx::query!(
+            r#"DELETE FROM moonlight_history WHERE block_height = ?"#,
+            block_height
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for entry in entries {
+            let value = entry.value as i64;
+            let fee = entry.fee as i64;
+
+            sqlx::query!(
+                r#"INSERT INTO moonlight_history (account, block_height, tx_hash, event, direction, value, fee) VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+                entry.account, block_height, entry.tx_hash, entry.event, entry.direction, value, fee
+            ).execute(&mut *tx).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Store the height of the last block replayed by the archive reindex.
+    async fn store_reindex_checkpoint(&self, block_height: u64) -> Result<()> {
+        let block_height: i64 = block_height as i64;
+
+        let mut conn = self.archive_db.acquire().await?;
+
+        sqlx::query!(
+            r#"INSERT INTO reindex_checkpoint (id, block_height) VALUES (0, ?) ON CONFLICT (id) DO UPDATE SET block_height = excluded.block_height"#,
+            block_height
+        ).execute(&mut *conn).await?;
+
+        Ok(())
+    }
 }
 
 mod util {
@@ -319,4 +680,197 @@ mod tests {
             .await
             .unwrap());
     }
+
+    #[tokio::test]
+    async fn test_fetch_indexed_events() {
+        let path = get_test_dir();
+        let archive = SQLiteArchive::create_or_open(path).await;
+        let events = get_dummy_data();
+
+        archive
+            .store_vm_events(1, [5; 32], events.clone())
+            .await
+            .unwrap();
+        archive.store_vm_events(2, [6; 32], events).await.unwrap();
+
+        let by_tx = archive
+            .fetch_events_by_tx(&hex::encode([1; 32]), 10)
+            .await
+            .unwrap();
+        assert_eq!(by_tx.len(), 2);
+        assert!(by_tx.iter().all(|e| e.topic == "contract2"));
+        assert_eq!(by_tx[0].data, hex::encode([1, 2, 3]));
+
+        let contract = hex::encode([0; 32]);
+        let by_contract = archive
+            .fetch_events_by_contract(&contract, None, None, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(by_contract.len(), 2);
+
+        let by_contract = archive
+            .fetch_events_by_contract(
+                &contract,
+                Some("contract1"),
+                Some((2, 2)),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(by_contract.len(), 1);
+        assert_eq!(by_contract[0].1.block_height, 2);
+        assert_eq!(by_contract[0].1.block_hash, hex::encode([6; 32]));
+
+        let by_contract = archive
+            .fetch_events_by_contract(&contract, Some("other"), None, None, 10)
+            .await
+            .unwrap();
+        assert!(by_contract.is_empty());
+
+        // Events of a block archived again keep their place by height
+        archive
+            .remove_deleted_block(1, hex::encode([5; 32]))
+            .await
+            .unwrap();
+        archive
+            .store_vm_events(1, [7; 32], get_dummy_data())
+            .await
+            .unwrap();
+        let page = archive
+            .fetch_events_by_contract(&contract, None, None, None, 1)
+            .await
+            .unwrap();
+        assert_eq!(page[0].1.block_hash, hex::encode([7; 32]));
+
+        let cursor: ArchiveCursor = page[0].0.to_string().parse().unwrap();
+        let page = archive
+            .fetch_events_by_contract(&contract, None, None, Some(cursor), 1)
+            .await
+            .unwrap();
+        assert_eq!(page[0].1.block_height, 2);
+
+        let page = archive
+            .fetch_events_by_contract(&contract, None, None, Some(page[0].0), 1)
+            .await
+            .unwrap();
+        assert!(page.is_empty());
+
+        // Events of deleted blocks are removed as well
+        archive
+            .remove_deleted_block(2, hex::encode([6; 32]))
+            .await
+            .unwrap();
+        let by_contract = archive
+            .fetch_events_by_contract(&contract, None, None, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(by_contract.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mempool_history() {
+        let path = get_test_dir();
+        let archive = SQLiteArchive::create_or_open(path).await;
+        let tx_hash = [1; 32];
+        let hex_tx_hash = hex::encode(tx_hash);
+
+        let events = [
+            MempoolTxEvent::Included,
+            MempoolTxEvent::Replaced([2; 32]),
+            MempoolTxEvent::Discarded("reason".to_string()),
+        ];
+        for (timestamp, event) in events.into_iter().enumerate() {
+            archive
+                .store_mempool_event(tx_hash, event, timestamp as u64)
+                .await
+                .unwrap();
+        }
+        archive
+            .store_mempool_event([3; 32], MempoolTxEvent::Expired, 10)
+            .await
+            .unwrap();
+
+        let history =
+            archive.fetch_mempool_history(&hex_tx_hash).await.unwrap();
+
+        assert_eq!(
+            history,
+            vec![
+                MempoolHistoryEntry {
+                    event: "included".to_string(),
+                    replaced_by: None,
+                    reason: None,
+                    timestamp: 0,
+                },
+                MempoolHistoryEntry {
+                    event: "replaced".to_string(),
+                    replaced_by: Some(hex::encode([2; 32])),
+                    reason: None,
+                    timestamp: 1,
+                },
+                MempoolHistoryEntry {
+                    event: "discarded".to_string(),
+                    replaced_by: None,
+                    reason: Some("reason".to_string()),
+                    timestamp: 2,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_moonlight_history() {
+        let path = get_test_dir();
+        let archive = SQLiteArchive::create_or_open(path).await;
+
+        let entry = |account: &str, block_height: u64, value: u64| {
+            MoonlightHistoryEntry {
+                account: account.to_string(),
+                block_height,
+                tx_hash: Some(hex::encode([block_height as u8; 32])),
+                event: "moonlight".to_string(),
+                direction: "in".to_string(),
+                value,
+                fee: 0,
+            }
+        };
+
+        for height in 1..=3 {
+            let entries =
+                vec![entry("a", height, height), entry("b", height, 1)];
+            archive
+                .store_moonlight_history(height, entries)
+                .await
+                .unwrap();
+        }
+        // Storing the same block twice replaces its entries, which keep
+        // their place by height
+        archive
+            .store_moonlight_history(2, vec![entry("a", 2, 20)])
+            .await
+            .unwrap();
+
+        let page = archive
+            .fetch_moonlight_history("a", None, None, 2)
+            .await
+            .unwrap();
+        let values: Vec<_> = page.iter().map(|(_, e)| e.value).collect();
+        assert_eq!(values, vec![1, 20]);
+
+        let cursor = page.last().map(|(cursor, _)| *cursor);
+        let page = archive
+            .fetch_moonlight_history("a", None, cursor, 2)
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].1, entry("a", 3, 3));
+
+        let page = archive
+            .fetch_moonlight_history("b", Some((2, 3)), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].1.block_height, 3);
+    }
 }
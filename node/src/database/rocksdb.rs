@@ -5,9 +5,17 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use super::{
-    Candidate, DatabaseOptions, Ledger, LightBlock, Metadata, Persist, DB,
+    Candidate, DatabaseOptions, Ledger, LedgerRecord, LightBlock, Metadata,
+    Persist, DB,
 };
 use anyhow::Result;
+use dusk_bytes::Serializable as _;
+use execution_core::signatures::bls::PublicKey as AccountPublicKey;
+use execution_core::stake::EPOCH;
+use execution_core::transfer::{
+    Transaction as ProtocolTransaction, TRANSFER_CONTRACT,
+};
+use execution_core::ContractId;
 use std::cell::RefCell;
 
 use node_data::ledger::{
@@ -19,7 +27,7 @@ use crate::database::Mempool;
 
 use rocksdb_lib::{
     AsColumnFamilyRef, BlockBasedOptions, ColumnFamily, ColumnFamilyDescriptor,
-    DBAccess, DBRawIteratorWithThreadMode, IteratorMode, LogLevel,
+    DBAccess, DBRawIteratorWithThreadMode, Direction, IteratorMode, LogLevel,
     OptimisticTransactionDB, OptimisticTransactionOptions, Options,
     SnapshotWithThreadMode, Transaction, WriteOptions,
 };
@@ -38,6 +46,8 @@ const CF_LEDGER_HEADER: &str = "cf_ledger_header";
 const CF_LEDGER_TXS: &str = "cf_ledger_txs";
 const CF_LEDGER_FAULTS: &str = "cf_ledger_faults";
 const CF_LEDGER_HEIGHT: &str = "cf_ledger_height";
+const CF_LEDGER_TXS_BY_ACCOUNT: &str = "cf_ledger_txs_by_account";
+const CF_LEDGER_TXS_BY_CONTRACT: &str = "cf_ledger_txs_by_contract";
 const CF_CANDIDATES: &str = "cf_candidates";
 const CF_CANDIDATES_HEIGHT: &str = "cf_candidates_height";
 const CF_MEMPOOL: &str = "cf_mempool";
@@ -54,10 +64,18 @@ pub const MD_AVG_VALIDATION: &[u8] = b"avg_validation_time";
 pub const MD_AVG_RATIFICATION: &[u8] = b"avg_ratification_time";
 pub const MD_AVG_PROPOSAL: &[u8] = b"avg_proposal_time";
 pub const MD_LAST_ITER: &[u8] = b"consensus_last_iter";
+pub const MD_PRUNED_HEIGHT_KEY: &[u8] = b"pruned_height_key";
+pub const MD_TX_INDEX_HEIGHT_KEY: &[u8] = b"tx_index_height_key";
+
+/// Maximum number of blocks pruned at once, so that enabling the pruning on
+/// an existing database does not stall the node
+const PRUNE_BATCH_SIZE: u64 = 1000;
 
 #[derive(Clone)]
 pub struct Backend {
     rocksdb: Arc<OptimisticTransactionDB>,
+    prune_after_epochs: Option<u64>,
+    tx_index: bool,
 }
 
 impl Backend {
@@ -119,6 +137,19 @@ impl Backend {
             .cf_handle(CF_METADATA)
             .expect("CF_METADATA column family must exist");
 
+        // The transactions index is populated only if enabled
+        let ledger_txs_by_account_cf = self.tx_index.then(|| {
+            self.rocksdb
+                .cf_handle(CF_LEDGER_TXS_BY_ACCOUNT)
+                .expect("CF_LEDGER_TXS_BY_ACCOUNT column family must exist")
+        });
+
+        let ledger_txs_by_contract_cf = self.tx_index.then(|| {
+            self.rocksdb
+                .cf_handle(CF_LEDGER_TXS_BY_CONTRACT)
+                .expect("CF_LEDGER_TXS_BY_CONTRACT column family must exist")
+        });
+
         let snapshot = self.rocksdb.snapshot();
 
         DBTransaction::<'_, OptimisticTransactionDB> {
@@ -132,6 +163,8 @@ impl Backend {
             nullifiers_cf,
             fees_cf,
             ledger_height_cf,
+            ledger_txs_by_account_cf,
+            ledger_txs_by_contract_cf,
             metadata_cf,
             snapshot,
             cumulative_inner_size: RefCell::new(0),
@@ -199,6 +232,14 @@ impl DB for Backend {
                 CF_LEDGER_HEIGHT,
                 blocks_cf_opts.clone(),
             ),
+            ColumnFamilyDescriptor::new(
+                CF_LEDGER_TXS_BY_ACCOUNT,
+                blocks_cf_opts.clone(),
+            ),
+            ColumnFamilyDescriptor::new(
+                CF_LEDGER_TXS_BY_CONTRACT,
+                blocks_cf_opts.clone(),
+            ),
             ColumnFamilyDescriptor::new(CF_CANDIDATES, blocks_cf_opts.clone()),
             ColumnFamilyDescriptor::new(
                 CF_CANDIDATES_HEIGHT,
@@ -219,6 +260,8 @@ impl DB for Backend {
                 )
                 .expect("should be a valid database in {path}"),
             ),
+            prune_after_epochs: db_opts.prune_after_epochs,
+            tx_index: db_opts.enable_tx_index,
         }
     }
 
@@ -250,6 +293,30 @@ impl DB for Backend {
         Ok(ret)
     }
 
+    fn prune(&self, tip_height: u64) -> Result<usize> {
+        let Some(epochs) = self.prune_after_epochs else {
+            return Ok(0);
+        };
+        let threshold = tip_height.saturating_sub(epochs.saturating_mul(EPOCH));
+
+        self.update(|t| {
+            // The genesis block is never pruned
+            let from = t.fetch_pruned_height()?.map_or(1, |h| h + 1);
+            let to = threshold.min(from + PRUNE_BATCH_SIZE);
+
+            let mut pruned = 0;
+            for height in from..to {
+                match t.fetch_block_label_by_height(height)? {
+                    Some((_, Label::Final(_))) => t.prune_block(height)?,
+                    _ => break,
+                }
+                pruned += 1;
+            }
+
+            Ok(pruned)
+        })
+    }
+
     fn close(&mut self) {}
 }
 
@@ -268,6 +335,9 @@ pub struct DBTransaction<'db, DB: DBAccess> {
     ledger_faults_cf: &'db ColumnFamily,
     ledger_txs_cf: &'db ColumnFamily,
     ledger_height_cf: &'db ColumnFamily,
+    // Optional transactions index column families
+    ledger_txs_by_account_cf: Option<&'db ColumnFamily>,
+    ledger_txs_by_contract_cf: Option<&'db ColumnFamily>,
 
     // Mempool column families
     mempool_cf: &'db ColumnFamily,
@@ -321,7 +391,9 @@ impl<'db, DB: DBAccess> Ledger for DBTransaction<'db, DB> {
                 let mut d = vec![];
                 tx.write(&mut d)?;
                 self.put_cf(cf, tx.inner.id(), d)?;
+                self.index_tx(header.height, &tx.inner, false)?;
             }
+            self.update_tx_index_height(header.height)?;
         }
 
         // COLUMN FAMILY: CF_LEDGER_FAULTS
@@ -390,6 +462,7 @@ impl<'db, DB: DBAccess> Ledger for DBTransaction<'db, DB> {
 
         for tx in b.txs() {
             self.inner.delete_cf(self.ledger_txs_cf, tx.id())?;
+            self.index_tx(b.header().height, tx, true)?;
         }
         for f in b.faults() {
             self.inner.delete_cf(self.ledger_faults_cf, f.hash())?;
@@ -417,8 +490,10 @@ impl<'db, DB: DBAccess> Ledger for DBTransaction<'db, DB> {
         let faults_buffer = self.snapshot.multi_get_cf(ids);
 
         let mut faults = vec![];
-        for buf in faults_buffer {
-            let buf = buf?.unwrap();
+        for (id, buf) in faults_ids.iter().zip(faults_buffer) {
+            let buf = buf?.ok_or_else(|| {
+                anyhow::anyhow!("Cannot read fault {}", hex::encode(id))
+            })?;
             let fault = ledger::Fault::read(&mut &buf.to_vec()[..])?;
             faults.push(fault);
         }
@@ -427,49 +502,57 @@ impl<'db, DB: DBAccess> Ledger for DBTransaction<'db, DB> {
     }
 
     fn fetch_block(&self, hash: &[u8]) -> Result<Option<ledger::Block>> {
-        match self.snapshot.get_cf(self.ledger_cf, hash)? {
-            Some(blob) => {
-                let record = LightBlock::read(&mut &blob[..])?;
-
-                // Retrieve all transactions buffers with single call
-                let txs_buffers = self.snapshot.multi_get_cf(
-                    record
-                        .transactions_ids
-                        .iter()
-                        .map(|id| (self.ledger_txs_cf, id))
-                        .collect::<Vec<(&ColumnFamily, &[u8; 32])>>(),
-                );
-
-                let mut txs = vec![];
-                for buf in txs_buffers {
-                    let buf = buf?.unwrap();
-                    let tx =
-                        ledger::SpentTransaction::read(&mut &buf.to_vec()[..])?;
-                    txs.push(tx.inner);
-                }
+        Ok(self.fetch_block_record(hash)?.found())
+    }
 
-                // Retrieve all faults ID with single call
-                let faults_buffer = self.snapshot.multi_get_cf(
-                    record
-                        .faults_ids
-                        .iter()
-                        .map(|id| (self.ledger_faults_cf, id))
-                        .collect::<Vec<(&ColumnFamily, &[u8; 32])>>(),
-                );
-                let mut faults = vec![];
-                for buf in faults_buffer {
-                    let buf = buf?.unwrap();
-                    let fault = ledger::Fault::read(&mut &buf.to_vec()[..])?;
-                    faults.push(fault);
-                }
+    fn fetch_block_record(
+        &self,
+        hash: &[u8],
+    ) -> Result<LedgerRecord<ledger::Block>> {
+        let Some(blob) = self.snapshot.get_cf(self.ledger_cf, hash)? else {
+            return Ok(LedgerRecord::NotFound);
+        };
+        let record = LightBlock::read(&mut &blob[..])?;
+
+        // Retrieve all transactions buffers with single call
+        let txs_buffers = self.snapshot.multi_get_cf(
+            record
+                .transactions_ids
+                .iter()
+                .map(|id| (self.ledger_txs_cf, id))
+                .collect::<Vec<(&ColumnFamily, &[u8; 32])>>(),
+        );
+
+        let mut txs = vec![];
+        for buf in txs_buffers {
+            let Some(buf) = buf? else {
+                return self.pruned_block(&record.header);
+            };
+            let tx = ledger::SpentTransaction::read(&mut &buf.to_vec()[..])?;
+            txs.push(tx.inner);
+        }
 
-                Ok(Some(
-                    ledger::Block::new(record.header, txs, faults)
-                        .expect("block should be valid"),
-                ))
-            }
-            None => Ok(None),
+        // Retrieve all faults ID with single call
+        let faults_buffer = self.snapshot.multi_get_cf(
+            record
+                .faults_ids
+                .iter()
+                .map(|id| (self.ledger_faults_cf, id))
+                .collect::<Vec<(&ColumnFamily, &[u8; 32])>>(),
+        );
+        let mut faults = vec![];
+        for buf in faults_buffer {
+            let Some(buf) = buf? else {
+                return self.pruned_block(&record.header);
+            };
+            let fault = ledger::Fault::read(&mut &buf.to_vec()[..])?;
+            faults.push(fault);
         }
+
+        Ok(LedgerRecord::Found(
+            ledger::Block::new(record.header, txs, faults)
+                .expect("block should be valid"),
+        ))
     }
 
     fn fetch_light_block(&self, hash: &[u8]) -> Result<Option<LightBlock>> {
@@ -558,6 +641,82 @@ impl<'db, DB: DBAccess> Ledger for DBTransaction<'db, DB> {
             })
             .transpose()?)
     }
+
+    fn prune_block(&self, height: u64) -> Result<()> {
+        let block = match self.fetch_block_hash_by_height(height)? {
+            Some(hash) => self.fetch_light_block(&hash)?,
+            None => None,
+        };
+
+        if let Some(block) = block {
+            for tx_id in block.transactions_ids {
+                if let Some(tx) = self.get_ledger_tx_by_hash(&tx_id)? {
+                    self.index_tx(height, &tx.inner, true)?;
+                }
+                self.inner.delete_cf(self.ledger_txs_cf, tx_id)?;
+            }
+            for fault_id in block.faults_ids {
+                self.inner.delete_cf(self.ledger_faults_cf, fault_id)?;
+            }
+        }
+
+        self.op_write(MD_PRUNED_HEIGHT_KEY, height.to_le_bytes())
+    }
+
+    fn fetch_tx_ids_by_account(
+        &self,
+        account: &AccountPublicKey,
+        height_range: (u64, u64),
+        limit: usize,
+    ) -> Result<Option<Vec<(u64, [u8; 32])>>> {
+        self.ledger_txs_by_account_cf
+            .map(|cf| {
+                self.fetch_indexed_txs(
+                    cf,
+                    &account.to_bytes(),
+                    height_range,
+                    limit,
+                )
+            })
+            .transpose()
+    }
+
+    fn fetch_tx_ids_by_contract(
+        &self,
+        contract: &ContractId,
+        height_range: (u64, u64),
+        limit: usize,
+    ) -> Result<Option<Vec<(u64, [u8; 32])>>> {
+        self.ledger_txs_by_contract_cf
+            .map(|cf| {
+                self.fetch_indexed_txs(
+                    cf,
+                    contract.as_bytes(),
+                    height_range,
+                    limit,
+                )
+            })
+            .transpose()
+    }
+
+    fn fetch_pruned_height(&self) -> Result<Option<u64>> {
+        Ok(self.op_read(MD_PRUNED_HEIGHT_KEY)?.map(|h| {
+            let mut height = [0u8; 8];
+            height.copy_from_slice(&h[..8]);
+            u64::from_le_bytes(height)
+        }))
+    }
+
+    fn fetch_tx_index_height(&self) -> Result<Option<u64>> {
+        if self.ledger_txs_by_contract_cf.is_none() {
+            return Ok(None);
+        }
+        Ok(self.op_read(MD_TX_INDEX_HEIGHT_KEY)?.map(|h| {
+            let mut height = [0u8; 8];
+            height.copy_from_slice(&h[..8]);
+            u64::from_le_bytes(height)
+        }))
+    }
 }
 
 /// Implementation of the `Candidate` trait for `DBTransaction<'db, DB>`.
@@ -714,8 +873,8 @@ impl<'db, DB: DBAccess> Mempool for DBTransaction<'db, DB> {
         self.put_cf(self.mempool_cf, hash, tx_data)?;
 
         // Add Secondary indexes //
-        // Nullifiers
-        for n in tx.inner.nullifiers() {
+        // Spending ids (Phoenix nullifiers and Moonlight account nonces)
+        for n in tx.to_spend_ids() {
             let key = n.to_bytes();
             self.put_cf(self.nullifiers_cf, key, hash)?;
         }
@@ -758,8 +917,8 @@ impl<'db, DB: DBAccess> Mempool for DBTransaction<'db, DB> {
             self.inner.delete_cf(self.mempool_cf, hash)?;
 
             // Delete Secondary indexes
-            // Delete Nullifiers
-            for n in tx.inner.nullifiers() {
+            // Delete Spending ids
+            for n in tx.to_spend_ids() {
                 let key = n.to_bytes();
                 self.inner.delete_cf(self.nullifiers_cf, key)?;
             }
@@ -787,6 +946,45 @@ impl<'db, DB: DBAccess> Mempool for DBTransaction<'db, DB> {
             .collect()
     }
 
+    fn get_txs_by_account(
+        &self,
+        account: &AccountPublicKey,
+    ) -> Result<Vec<(u64, [u8; 32])>> {
+        // Moonlight spending ids are keyed by account || nonce, so all the
+        // entries of an account are adjacent to each other
+        let prefix = account.to_bytes();
+        let iter = self.inner.iterator_cf(
+            self.nullifiers_cf,
+            IteratorMode::From(&prefix, Direction::Forward),
+        );
+
+        let mut txs = vec![];
+        for entry in iter {
+            let (key, tx_id) = entry?;
+            if !key.starts_with(&prefix) {
+                break;
+            }
+
+            let nonce = key[prefix.len()..]
+                .try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid nonce")
+                })?;
+            let tx_id = tx_id[..].try_into().map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid tx id")
+            })?;
+
+            txs.push((nonce, tx_id));
+        }
+
+        // Nonces are stored in little-endian, so the lexicographic order of
+        // the keys does not match the numeric one
+        txs.sort_unstable_by_key(|(nonce, _)| *nonce);
+
+        Ok(txs)
+    }
+
     fn get_txs_sorted_by_fee(
         &self,
     ) -> Result<Box<dyn Iterator<Item = ledger::Transaction> + '_>> {
@@ -798,7 +996,23 @@ impl<'db, DB: DBAccess> Mempool for DBTransaction<'db, DB> {
     fn get_txs_ids_sorted_by_fee(
         &self,
     ) -> Result<Box<dyn Iterator<Item = (u64, [u8; 32])> + '_>> {
-        let iter = MemPoolFeeIterator::new(&self.inner, self.fees_cf);
+        let iter = MemPoolFeeIterator::new(
+            &self.inner,
+            self.fees_cf,
+            Direction::Reverse,
+        );
+
+        Ok(Box::new(iter))
+    }
+
+    fn get_txs_ids_sorted_by_low_fee(
+        &self,
+    ) -> Result<Box<dyn Iterator<Item = (u64, [u8; 32])> + '_>> {
+        let iter = MemPoolFeeIterator::new(
+            &self.inner,
+            self.fees_cf,
+            Direction::Forward,
+        );
 
         Ok(Box::new(iter))
     }
@@ -879,7 +1093,7 @@ impl<'db, DB: DBAccess, M: Mempool> MemPoolIterator<'db, DB, M> {
         fees_cf: &ColumnFamily,
         mempool: &'db M,
     ) -> Self {
-        let iter = MemPoolFeeIterator::new(db, fees_cf);
+        let iter = MemPoolFeeIterator::new(db, fees_cf, Direction::Reverse);
         MemPoolIterator { iter, mempool }
     }
 }
@@ -895,13 +1109,24 @@ impl<DB: DBAccess, M: Mempool> Iterator for MemPoolIterator<'_, DB, M> {
 
 pub struct MemPoolFeeIterator<'db, DB: DBAccess> {
     iter: DBRawIteratorWithThreadMode<'db, rocksdb_lib::Transaction<'db, DB>>,
+    direction: Direction,
 }
 
 impl<'db, DB: DBAccess> MemPoolFeeIterator<'db, DB> {
-    fn new(db: &'db Transaction<DB>, fees_cf: &ColumnFamily) -> Self {
+    /// Creates an iterator over the fees index, starting from the highest
+    /// gas price if `direction` is [`Direction::Reverse`], from the lowest
+    /// otherwise.
+    fn new(
+        db: &'db Transaction<DB>,
+        fees_cf: &ColumnFamily,
+        direction: Direction,
+    ) -> Self {
         let mut iter = db.raw_iterator_cf(fees_cf);
-        iter.seek_to_last();
-        MemPoolFeeIterator { iter }
+        match direction {
+            Direction::Forward => iter.seek_to_first(),
+            Direction::Reverse => iter.seek_to_last(),
+        }
+        MemPoolFeeIterator { iter, direction }
     }
 }
 
@@ -913,7 +1138,10 @@ impl<DB: DBAccess> Iterator for MemPoolFeeIterator<'_, DB> {
                 if let Some(key) = self.iter.key() {
                     let (gas_price, hash) =
                         deserialize_key(&mut &key.to_vec()[..]).ok()?;
-                    self.iter.prev();
+                    match self.direction {
+                        Direction::Forward => self.iter.next(),
+                        Direction::Reverse => self.iter.prev(),
+                    }
                     Some((gas_price, hash))
                 } else {
                     None
@@ -996,6 +1224,135 @@ impl<'db, DB: DBAccess> DBTransaction<'db, DB> {
     pub fn get_size(&self) -> usize {
         *self.cumulative_inner_size.borrow()
     }
+
+    /// Adds, or removes if `delete` is set, the entries indexing a ledger
+    /// transaction by Moonlight account and by called contract.
+    ///
+    /// Transactions not calling any contract are indexed under the transfer
+    /// contract.
+    fn index_tx(
+        &self,
+        height: u64,
+        tx: &ledger::Transaction,
+        delete: bool,
+    ) -> Result<()> {
+        let (Some(accounts_cf), Some(contracts_cf)) = (
+            self.ledger_txs_by_account_cf,
+            self.ledger_txs_by_contract_cf,
+        ) else {
+            return Ok(());
+        };
+        let tx_id = tx.id();
+
+        let mut keys = vec![];
+        if let ProtocolTransaction::Moonlight(m) = &tx.inner {
+            let from = m.from_account();
+            keys.push((
+                accounts_cf,
+                tx_index_key(&from.to_bytes(), height, tx_id),
+            ));
+            if let Some(to) = m.to_account().filter(|to| *to != from) {
+                keys.push((
+                    accounts_cf,
+                    tx_index_key(&to.to_bytes(), height, tx_id),
+                ));
+            }
+        }
+        let contract = tx
+            .inner
+            .call()
+            .map(|c| c.contract)
+            .unwrap_or(TRANSFER_CONTRACT);
+        keys.push((
+            contracts_cf,
+            tx_index_key(contract.as_bytes(), height, tx_id),
+        ));
+
+        for (cf, key) in keys {
+            if delete {
+                self.inner.delete_cf(cf, key)?;
+            } else {
+                self.put_cf(cf, key, b"")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the height of the first block stored with the transactions
+    /// index enabled, forgetting it as soon as a block is stored without.
+    fn update_tx_index_height(&self, height: u64) -> Result<()> {
+        let indexed = self.op_read(MD_TX_INDEX_HEIGHT_KEY)?.is_some();
+        match (self.ledger_txs_by_contract_cf.is_some(), indexed) {
+            (true, false) => {
+                self.op_write(MD_TX_INDEX_HEIGHT_KEY, height.to_le_bytes())
+            }
+            (false, true) => Ok(self
+                .inner
+                .delete_cf(self.metadata_cf, MD_TX_INDEX_HEIGHT_KEY)?),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns at most `limit` transactions indexed under `prefix` in the
+    /// given inclusive range of block heights, together with their heights.
+    fn fetch_indexed_txs(
+        &self,
+        cf: &ColumnFamily,
+        prefix: &[u8],
+        (from, to): (u64, u64),
+        limit: usize,
+    ) -> Result<Vec<(u64, [u8; 32])>> {
+        let start = tx_index_key(prefix, from, [0; 32]);
+        let iter = self
+            .snapshot
+            .iterator_cf(cf, IteratorMode::From(&start, Direction::Forward));
+
+        let mut txs = vec![];
+        for entry in iter {
+            if txs.len() >= limit {
+                break;
+            }
+
+            let (key, _) = entry?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+
+            let (height, tx_id) = key[prefix.len()..].split_at(8);
+            let height = u64::from_be_bytes(height.try_into()?);
+            if height > to {
+                break;
+            }
+            txs.push((height, tx_id.try_into()?));
+        }
+
+        Ok(txs)
+    }
+
+    /// Reports a block whose transactions or faults are missing as pruned,
+    /// unless it is more recent than the last pruned block.
+    fn pruned_block<T>(&self, header: &Header) -> Result<LedgerRecord<T>> {
+        match self.fetch_pruned_height()? {
+            Some(pruned_height) if header.height <= pruned_height => {
+                Ok(LedgerRecord::Pruned)
+            }
+            _ => Err(anyhow::anyhow!(
+                "Missing data of block {}",
+                hex::encode(header.hash)
+            )),
+        }
+    }
+}
+
+/// Key of a transaction in an index, so that the transactions under the same
+/// `prefix` are sorted by block height
+fn tx_index_key(prefix: &[u8], height: u64, tx_id: [u8; 32]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(prefix.len() + 8 + 32);
+    key.extend_from_slice(prefix);
+    key.extend_from_slice(&height.to_be_bytes());
+    key.extend_from_slice(&tx_id);
+    key
 }
 
 fn serialize_key(value: u64, hash: [u8; 32]) -> std::io::Result<Vec<u8>> {
@@ -1293,6 +1650,34 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_mempool_txs_sorted_by_low_fee() {
+        TestWrapper::new("test_mempool_txs_sorted_by_low_fee").run(|path| {
+            let db: Backend =
+                Backend::create_or_open(path, DatabaseOptions::default());
+
+            db.update(|txn| {
+                for gas_price in [5, 1, 3, 2, 4] {
+                    let t = ledger::faker::gen_dummy_moonlight_tx(
+                        gas_price, 0, gas_price,
+                    );
+                    txn.add_tx(&t, 0)?;
+                }
+                Ok(())
+            })
+            .unwrap();
+
+            db.view(|txn| {
+                let fees: Vec<_> = txn
+                    .get_txs_ids_sorted_by_low_fee()
+                    .expect("iter should return")
+                    .map(|(fee, _)| fee)
+                    .collect();
+                assert_eq!(fees, vec![1, 2, 3, 4, 5]);
+            });
+        });
+    }
+
     #[test]
     fn test_txs_count() {
         TestWrapper::new("test_txs_count").run(|path| {
@@ -1401,6 +1786,140 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_get_txs_by_account() {
+        TestWrapper::new("test_get_txs_by_account").run(|path| {
+            let db: Backend =
+                Backend::create_or_open(path, DatabaseOptions::default());
+
+            let txs: Vec<_> = [3, 1, 2]
+                .into_iter()
+                .map(|nonce| ledger::faker::gen_dummy_moonlight_tx(1, nonce, 1))
+                .collect();
+            let other = ledger::faker::gen_dummy_moonlight_tx(2, 1, 1);
+
+            db.update(|txn| {
+                for t in txs.iter().chain([&other]) {
+                    txn.add_tx(t, 0)?;
+                }
+                Ok(())
+            })
+            .unwrap();
+
+            let account = *txs[0].inner.from_account().unwrap();
+
+            db.view(|vq| {
+                let nonces: Vec<_> = vq
+                    .get_txs_by_account(&account)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(nonce, _)| nonce)
+                    .collect();
+                assert_eq!(nonces, vec![1, 2, 3]);
+
+                // Moonlight transactions are indexed by their spending id
+                let spend_ids = txs[1].to_spend_ids();
+                let found = vq.get_txs_by_spendable_ids(&spend_ids);
+                assert_eq!(found, HashSet::from([txs[1].id()]));
+            });
+
+            db.update(|txn| {
+                assert!(txn.delete_tx(txs[2].id())?);
+                Ok(())
+            })
+            .unwrap();
+
+            db.view(|vq| {
+                let txs_by_account = vq.get_txs_by_account(&account).unwrap();
+                assert_eq!(
+                    txs_by_account,
+                    vec![(1, txs[1].id()), (3, txs[0].id())]
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn test_tx_index() {
+        TestWrapper::new("test_tx_index").run(|path| {
+            let opts = DatabaseOptions {
+                enable_tx_index: true,
+                ..Default::default()
+            };
+            let db: Backend = Backend::create_or_open(path, opts);
+
+            let blocks: Vec<_> = (1..=3)
+                .map(|height| {
+                    let header = ledger::Header {
+                        height,
+                        ..Default::default()
+                    };
+                    let mut txs = vec![ledger::faker::gen_dummy_moonlight_tx(
+                        1, height, 1,
+                    )];
+                    if height == 2 {
+                        txs.push(ledger::faker::gen_dummy_moonlight_tx(
+                            2, 1, 1,
+                        ));
+                    }
+                    ledger::Block::new(header, txs, vec![]).unwrap()
+                })
+                .collect();
+
+            db.update(|txn| {
+                for b in blocks.iter() {
+                    txn.store_block(
+                        b.header(),
+                        &to_spent_txs(b.txs()),
+                        b.faults(),
+                        Label::Final(0),
+                    )?;
+                }
+                Ok(())
+            })
+            .unwrap();
+
+            let account = *blocks[0].txs()[0].inner.from_account().unwrap();
+            let expected: Vec<_> = blocks
+                .iter()
+                .map(|b| (b.header().height, b.txs()[0].id()))
+                .collect();
+
+            db.view(|v| {
+                assert_eq!(v.fetch_tx_index_height().unwrap(), Some(1));
+
+                let txs = v
+                    .fetch_tx_ids_by_account(&account, (0, u64::MAX), 10)
+                    .unwrap()
+                    .expect("index to be enabled");
+                assert_eq!(txs, expected);
+
+                let txs = v
+                    .fetch_tx_ids_by_account(&account, (2, 3), 1)
+                    .unwrap()
+                    .expect("index to be enabled");
+                assert_eq!(txs, vec![expected[1]]);
+
+                let txs = v
+                    .fetch_tx_ids_by_contract(&TRANSFER_CONTRACT, (2, 2), 10)
+                    .unwrap()
+                    .expect("index to be enabled");
+                assert_eq!(txs.len(), 2);
+            });
+
+            // Index entries are removed together with the block
+            db.update(|txn| txn.delete_block(&blocks[1])).unwrap();
+
+            db.view(|v| {
+                let txs = v
+                    .fetch_tx_ids_by_contract(&TRANSFER_CONTRACT, (0, 10), 10)
+                    .unwrap()
+                    .expect("index to be enabled");
+                assert_eq!(txs, vec![expected[0], expected[2]]);
+            });
+        });
+    }
+
     fn to_spent_txs(txs: &Vec<Transaction>) -> Vec<SpentTransaction> {
         txs.iter()
             .map(|t| SpentTransaction {
@@ -1511,6 +2030,65 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_prune_block() {
+        TestWrapper::new("test_prune_block").run(|path| {
+            let db: Backend =
+                Backend::create_or_open(path, DatabaseOptions::default());
+            let b: ledger::Block = Faker.fake();
+            assert!(!b.txs().is_empty());
+            let hash = b.header().hash;
+            let height = b.header().height;
+
+            db.update(|txn| {
+                txn.store_block(
+                    b.header(),
+                    &to_spent_txs(b.txs()),
+                    b.faults(),
+                    Label::Final(3),
+                )?;
+                Ok(())
+            })
+            .expect("block to be stored");
+
+            db.view(|v| {
+                assert!(matches!(
+                    v.fetch_block_record(&hash).unwrap(),
+                    LedgerRecord::Found(_)
+                ));
+                assert_eq!(v.fetch_pruned_height().unwrap(), None);
+            });
+
+            db.update(|txn| txn.prune_block(height))
+                .expect("block to be pruned");
+
+            db.view(|v| {
+                assert!(matches!(
+                    v.fetch_block_record(&hash).unwrap(),
+                    LedgerRecord::Pruned
+                ));
+                assert!(matches!(
+                    v.fetch_block_record(&[0; 32]).unwrap(),
+                    LedgerRecord::NotFound
+                ));
+                assert!(v.fetch_block(&hash).unwrap().is_none());
+                assert!(v
+                    .get_ledger_tx_by_hash(&b.txs()[0].id())
+                    .unwrap()
+                    .is_none());
+                assert!(v.fetch_faults(&[[0; 32]]).is_err());
+
+                // Header and height index are kept
+                assert!(v.fetch_block_header(&hash).unwrap().is_some());
+                assert_eq!(
+                    v.fetch_block_hash_by_height(height).unwrap(),
+                    Some(hash)
+                );
+                assert_eq!(v.fetch_pruned_height().unwrap(), Some(height));
+            });
+        });
+    }
+
     #[test]
     /// Ensures delete_block fn removes all keys of a single block
     fn test_delete_block() {
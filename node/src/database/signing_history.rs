@@ -0,0 +1,300 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use dusk_consensus::slashing_protection::{
+    SignedRecord, SigningHistory, SigningSlot,
+};
+use execution_core::stake::EPOCH;
+use node_data::StepName;
+use rocksdb_lib::{IteratorMode, Options, WriteBatch, WriteOptions};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Version of the format of the exported signing history
+const EXPORT_VERSION: u8 = 1;
+
+/// Number of rounds the records are kept for. Older rounds are finalized
+/// and cannot be signed anymore.
+const KEEP_ROUNDS: u64 = EPOCH;
+
+const KEY_SIZE: usize = 42;
+const VALUE_SIZE: usize = 32;
+
+/// Signing history of the consensus keys.
+///
+/// It is kept in its own database, next to the consensus keys file, so that
+/// it survives a resync of the chain database and it is not shared by two
+/// nodes running with the same keys on the same machine.
+pub struct SigningHistoryDB {
+    rocksdb: rocksdb_lib::DB,
+}
+
+impl SigningHistoryDB {
+    /// Returns the default location of the signing history of the given
+    /// consensus keys file.
+    pub fn default_path<P: AsRef<Path>>(keys_path: P) -> PathBuf {
+        let mut path = keys_path.as_ref().as_os_str().to_owned();
+        path.push(".history");
+        path.into()
+    }
+
+    /// Creates or opens the signing history located at this path.
+    ///
+    /// Fails if the history is already opened by another process.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        info!("Opening signing history in {path:?}");
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let rocksdb = rocksdb_lib::DB::open(&opts, path)?;
+
+        Ok(Self { rocksdb })
+    }
+
+    /// Exports all the records of the history.
+    pub fn export(&self) -> Result<SigningHistoryExport> {
+        let mut records = vec![];
+        for entry in self.rocksdb.iterator(IteratorMode::Start) {
+            let (key, value) = entry?;
+            let slot = decode_slot(&key)?;
+            let record = decode_record(&value)?;
+            records.push(ExportedRecord::new(&slot, &record));
+        }
+
+        Ok(SigningHistoryExport {
+            version: EXPORT_VERSION,
+            records,
+        })
+    }
+
+    /// Imports the records of an exported history, returning the number of
+    /// records imported.
+    ///
+    /// Nothing is imported if any of the records conflicts with the local
+    /// ones or with another imported record, since it means that the keys
+    /// already signed conflicting messages.
+    pub fn import(&self, history: &SigningHistoryExport) -> Result<usize> {
+        if history.version != EXPORT_VERSION {
+            return Err(anyhow!(
+                "unsupported signing history version {}",
+                history.version
+            ));
+        }
+
+        let mut pending = HashMap::new();
+        for exported in &history.records {
+            let (slot, record) = exported.decode()?;
+            let known = match pending.get(&slot) {
+                Some(imported) => Some(*imported),
+                None => self.get(&slot)?,
+            };
+            match known {
+                Some(known) if known.conflicts(&record) => {
+                    return Err(anyhow!(
+                        "conflicting record for round {} iteration {} {:?}",
+                        slot.round,
+                        slot.iteration,
+                        slot.step,
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    pending.insert(slot, record);
+                }
+            }
+        }
+
+        let mut batch = WriteBatch::default();
+        for (slot, record) in &pending {
+            batch.put(encode_slot(slot), encode_record(record));
+        }
+        self.rocksdb.write_opt(batch, &sync_write())?;
+        Ok(pending.len())
+    }
+}
+
+impl SigningHistory for SigningHistoryDB {
+    fn get(&self, slot: &SigningSlot) -> Result<Option<SignedRecord>> {
+        self.rocksdb
+            .get(encode_slot(slot))?
+            .map(|value| decode_record(&value))
+            .transpose()
+    }
+
+    fn put(&self, slot: &SigningSlot, record: &SignedRecord) -> Result<()> {
+        let mut batch = WriteBatch::default();
+
+        let expired = slot.round.saturating_sub(KEEP_ROUNDS);
+        if expired > 0 {
+            batch.delete_range(0u64.to_be_bytes(), expired.to_be_bytes());
+        }
+        batch.put(encode_slot(slot), encode_record(record));
+
+        self.rocksdb.write_opt(batch, &sync_write())?;
+        Ok(())
+    }
+}
+
+/// Records are flushed to disk before signing, to survive a crash
+fn sync_write() -> WriteOptions {
+    let mut opts = WriteOptions::default();
+    opts.set_sync(true);
+    opts
+}
+
+fn encode_slot(slot: &SigningSlot) -> [u8; KEY_SIZE] {
+    let mut key = [0u8; KEY_SIZE];
+    key[..8].copy_from_slice(&slot.round.to_be_bytes());
+    key[8] = slot.iteration;
+    key[9] = slot.step as u8;
+    key[10..].copy_from_slice(&slot.prev_block_hash);
+    key
+}
+
+fn decode_slot(key: &[u8]) -> Result<SigningSlot> {
+    if key.len() != KEY_SIZE {
+        return Err(anyhow!("invalid signing slot size {}", key.len()));
+    }
+    Ok(SigningSlot {
+        round: u64::from_be_bytes(key[..8].try_into()?),
+        iteration: key[8],
+        step: step_from_u8(key[9])?,
+        prev_block_hash: key[10..].try_into()?,
+    })
+}
+
+fn encode_record(record: &SignedRecord) -> [u8; VALUE_SIZE] {
+    record.digest
+}
+
+fn decode_record(value: &[u8]) -> Result<SignedRecord> {
+    if value.len() != VALUE_SIZE {
+        return Err(anyhow!("invalid signed record size {}", value.len()));
+    }
+    Ok(SignedRecord {
+        digest: value.try_into()?,
+    })
+}
+
+fn step_from_u8(step: u8) -> Result<StepName> {
+    match step {
+        0 => Ok(StepName::Proposal),
+        1 => Ok(StepName::Validation),
+        2 => Ok(StepName::Ratification),
+        _ => Err(anyhow!("invalid step {step}")),
+    }
+}
+
+/// Signing history in the format exchanged between nodes
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SigningHistoryExport {
+    pub version: u8,
+    pub records: Vec<ExportedRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ExportedRecord {
+    pub round: u64,
+    pub iteration: u8,
+    pub step: u8,
+    /// Hex encoded hash of the tip the message was signed on top of
+    pub prev_block_hash: String,
+    /// Hex encoded digest of the signed content
+    pub digest: String,
+}
+
+impl ExportedRecord {
+    fn new(slot: &SigningSlot, record: &SignedRecord) -> Self {
+        Self {
+            round: slot.round,
+            iteration: slot.iteration,
+            step: slot.step as u8,
+            prev_block_hash: hex::encode(slot.prev_block_hash),
+            digest: hex::encode(record.digest),
+        }
+    }
+
+    fn decode(&self) -> Result<(SigningSlot, SignedRecord)> {
+        let mut slot = SigningSlot {
+            round: self.round,
+            iteration: self.iteration,
+            step: step_from_u8(self.step)?,
+            prev_block_hash: [0; 32],
+        };
+        let mut record = SignedRecord { digest: [0; 32] };
+        hex::decode_to_slice(&self.prev_block_hash, &mut slot.prev_block_hash)?;
+        hex::decode_to_slice(&self.digest, &mut record.digest)?;
+        Ok((slot, record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempdir::TempDir;
+
+    fn slot(round: u64, tip: u8) -> SigningSlot {
+        SigningSlot {
+            round,
+            iteration: 1,
+            step: StepName::Ratification,
+            prev_block_hash: [tip; 32],
+        }
+    }
+
+    #[test]
+    fn test_export_import() {
+        let dir = TempDir::new("signing_history").unwrap();
+        let source = SigningHistoryDB::open(dir.path().join("source")).unwrap();
+        let target = SigningHistoryDB::open(dir.path().join("target")).unwrap();
+
+        let record = SignedRecord::new(b"vote");
+        source.put(&slot(10, 1), &record).unwrap();
+        assert_eq!(source.get(&slot(10, 1)).unwrap(), Some(record));
+
+        let exported = source.export().unwrap();
+        assert_eq!(target.import(&exported).unwrap(), 1);
+        assert_eq!(target.get(&slot(10, 1)).unwrap(), Some(record));
+
+        // Importing the same history twice is a no-op
+        assert_eq!(target.import(&exported).unwrap(), 0);
+
+        // Records signed on top of another tip are kept side by side
+        let fork = SignedRecord::new(b"other vote");
+        source.put(&slot(10, 2), &fork).unwrap();
+        let exported = source.export().unwrap();
+        assert_eq!(target.import(&exported).unwrap(), 1);
+        assert_eq!(target.get(&slot(10, 1)).unwrap(), Some(record));
+        assert_eq!(target.get(&slot(10, 2)).unwrap(), Some(fork));
+
+        // Conflicting records are refused
+        source.put(&slot(10, 1), &fork).unwrap();
+        let exported = source.export().unwrap();
+        assert!(target.import(&exported).is_err());
+        assert_eq!(target.get(&slot(10, 1)).unwrap(), Some(record));
+
+        // Records conflicting with each other are refused too
+        let fresh = SigningHistoryDB::open(dir.path().join("fresh")).unwrap();
+        let conflicting = SigningHistoryExport {
+            version: EXPORT_VERSION,
+            records: vec![
+                ExportedRecord::new(&slot(11, 1), &record),
+                ExportedRecord::new(&slot(11, 1), &fork),
+            ],
+        };
+        assert!(fresh.import(&conflicting).is_err());
+        assert_eq!(fresh.get(&slot(11, 1)).unwrap(), None);
+
+        // Expired records are pruned
+        source.put(&slot(10 + KEEP_ROUNDS + 1, 1), &record).unwrap();
+        assert_eq!(source.export().unwrap().records.len(), 1);
+    }
+}
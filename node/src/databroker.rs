@@ -6,7 +6,7 @@
 
 pub mod conf;
 
-use crate::database::{Candidate, Ledger, Mempool};
+use crate::database::{Candidate, Ledger, LedgerRecord, Mempool};
 use crate::{database, vm, Network};
 use crate::{LongLivedService, Message};
 use anyhow::{anyhow, Result};
@@ -285,13 +285,21 @@ impl DataBrokerSrv {
             .await
             .view(|t| {
                 let mut locator = t
-                    .fetch_block(&m.locator)?
+                    .fetch_block_header(&m.locator)?
                     .ok_or_else(|| {
                         anyhow::anyhow!("could not find locator block")
                     })?
-                    .header()
                     .height;
 
+                // Blocks whose data has been pruned cannot be served
+                if let Some(pruned_height) = t.fetch_pruned_height()? {
+                    if locator < pruned_height {
+                        return Err(anyhow::anyhow!(
+                            "requested blocks have been pruned"
+                        ));
+                    }
+                }
+
                 let mut prev_block_hash = m.locator;
 
                 loop {
@@ -451,9 +459,10 @@ impl DataBrokerSrv {
                 .filter_map(|i| match i.inv_type {
                     InvType::BlockFromHeight => {
                         if let InvParam::Height(height) = &i.param {
-                            Ledger::fetch_block_by_height(&t, *height)
+                            Ledger::fetch_block_hash_by_height(&t, *height)
                                 .ok()
                                 .flatten()
+                                .and_then(|hash| Self::fetch_block(&t, &hash))
                                 .map(Message::from)
                         } else {
                             None
@@ -461,10 +470,7 @@ impl DataBrokerSrv {
                     }
                     InvType::BlockFromHash => {
                         if let InvParam::Hash(hash) = &i.param {
-                            Ledger::fetch_block(&t, hash)
-                                .ok()
-                                .flatten()
-                                .map(Message::from)
+                            Self::fetch_block(&t, hash).map(Message::from)
                         } else {
                             None
                         }
@@ -528,4 +534,24 @@ impl DataBrokerSrv {
             Ok(res)
         })
     }
+
+    /// Fetches a block to be served to a peer, refusing the ones whose data
+    /// has been pruned.
+    fn fetch_block<L: Ledger>(
+        t: &L,
+        hash: &[u8; 32],
+    ) -> Option<node_data::ledger::Block> {
+        match t.fetch_block_record(hash) {
+            Ok(LedgerRecord::Found(block)) => Some(block),
+            Ok(LedgerRecord::Pruned) => {
+                debug!("refusing to serve pruned block {}", hex::encode(hash));
+                None
+            }
+            Ok(LedgerRecord::NotFound) => None,
+            Err(e) => {
+                warn!("error fetching block {}: {e}", hex::encode(hash));
+                None
+            }
+        }
+    }
 }
@@ -14,6 +14,7 @@ pub mod databroker;
 pub mod mempool;
 pub mod network;
 pub mod telemetry;
+pub mod transfer;
 pub mod vm;
 
 use async_trait::async_trait;
@@ -88,6 +89,9 @@ pub trait Network: Send + Sync + 'static {
 
     /// Retrieves number of alive nodes
     async fn alive_nodes_count(&self) -> usize;
+
+    /// Retrieves the addresses of a random set of alive nodes
+    async fn alive_nodes(&self, amount: usize) -> Vec<SocketAddr>;
 }
 
 /// Service processes specified set of messages and eventually produces a
@@ -168,6 +172,10 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> Node<N, DB, VM> {
         self.network.clone()
     }
 
+    pub fn vm_handler(&self) -> Arc<RwLock<VM>> {
+        self.vm_handler.clone()
+    }
+
     pub async fn initialize(
         &self,
         services: &mut [Box<dyn LongLivedService<N, DB, VM>>],
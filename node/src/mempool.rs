@@ -5,57 +5,142 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 pub mod conf;
+mod peers;
+mod pending;
+mod rbf;
 
 use crate::database::{Ledger, Mempool};
-use crate::mempool::conf::Params;
+use crate::mempool::conf::{EvictionPolicy, Params};
+use crate::vm::PreverificationError;
 use crate::{database, vm, LongLivedService, Message, Network};
 use async_trait::async_trait;
 use conf::{
     DEFAULT_DOWNLOAD_REDUNDANCY, DEFAULT_EXPIRY_TIME, DEFAULT_IDLE_INTERVAL,
+    DEFAULT_MAX_PENDING_PER_ACCOUNT, DEFAULT_MAX_PENDING_TXN_COUNT,
+    DEFAULT_MAX_REPLACEMENT_CHAIN_LEN, DEFAULT_MIN_REPLACEMENT_BUMP_PERCENT,
+    DEFAULT_PEER_BAN_DURATION, DEFAULT_PEER_MAX_TX_RATE,
+    DEFAULT_PENDING_CHECK_INTERVAL,
 };
+use execution_core::signatures::bls::PublicKey as AccountPublicKey;
+use metrics::{counter, gauge};
+use node_data::archive::MempoolTxEvent;
 use node_data::events::{Event, TransactionEvent};
 use node_data::get_current_timestamp;
 use node_data::ledger::Transaction;
 use node_data::message::{payload, AsyncQueue, Payload, Topics};
+use peers::{PeerScores, INVALID_TX_PENALTY};
+use pending::PendingPool;
+use rbf::{min_replacement_price, ReplacementError, ReplacementTracker};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::RwLock;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
+#[cfg(feature = "archive")]
+use {node_data::archive::ArchivalData, tokio::sync::mpsc};
 
 const TOPICS: &[u8] = &[Topics::Tx as u8];
 
+/// Time given to the peers asked for their mempool to send it, without being
+/// rate limited
+const MEMPOOL_SYNC_DURATION: Duration = Duration::from_secs(60);
+
+/// Interval to forget the peers that are neither banned nor throttled
+const PEERS_CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Error)]
 enum TxAcceptanceError {
     #[error("this transaction exists in the mempool")]
     AlreadyExistsInMempool,
     #[error("this transaction exists in the ledger")]
     AlreadyExistsInLedger,
-    #[error("this transaction's spendId exists in the mempool")]
-    SpendIdExistsInMempool,
     #[error("this transaction is invalid {0}")]
     VerificationFailed(String),
+    #[error("this transaction is forged {0}")]
+    Forged(String),
     #[error("Maximum count of transactions exceeded {0}")]
     MaxTxnCountExceeded(usize),
+    #[error("this transaction is already pending")]
+    AlreadyPending,
+    #[error("cannot hold transaction with nonce {0}, expected nonce {1}")]
+    PendingRejected(u64, u64),
+    #[error("cannot replace conflicting transaction: {0}")]
+    ReplacementRefused(ReplacementError),
     #[error("A generic error occurred {0}")]
     Generic(anyhow::Error),
 }
 
+impl TxAcceptanceError {
+    /// Returns the score penalty for the peer that sent a transaction
+    /// rejected with this error, if any.
+    ///
+    /// Only forged transactions are penalized, since honest peers relay known
+    /// transactions and can lose fee or nonce races.
+    fn peer_penalty(&self) -> Option<f64> {
+        match self {
+            Self::Forged(_) => Some(INVALID_TX_PENALTY),
+            _ => None,
+        }
+    }
+}
+
 impl From<anyhow::Error> for TxAcceptanceError {
     fn from(err: anyhow::Error) -> Self {
-        Self::Generic(err)
+        // Preserve the acceptance errors raised within a database transaction
+        match err.downcast::<TxAcceptanceError>() {
+            Ok(err) => err,
+            Err(err) => Self::Generic(err),
+        }
     }
 }
 
+/// Outcome of a successful transaction acceptance
+enum TxAcceptance {
+    /// The transaction has been added to the mempool
+    Accepted,
+    /// The transaction has been held in the pending sub-pool, waiting for the
+    /// previous nonces of its account
+    Pending,
+}
+
 pub struct MempoolSrv {
     inbound: AsyncQueue<Message>,
     conf: Params,
     event_sender: Sender<Event>,
+    /// Moonlight transactions waiting for a nonce gap to be filled
+    pending: PendingPool,
+    /// Replacement chain length of the mempool transactions
+    replacements: ReplacementTracker,
+    /// Rate limit and score of the peers sending transactions
+    peers: PeerScores,
+    #[cfg(feature = "archive")]
+    archive_sender: mpsc::Sender<ArchivalData>,
 }
 
 impl MempoolSrv {
-    pub fn new(conf: Params, event_sender: Sender<Event>) -> Self {
+    pub fn new(
+        conf: Params,
+        event_sender: Sender<Event>,
+        #[cfg(feature = "archive")] archive_sender: mpsc::Sender<ArchivalData>,
+    ) -> Self {
         info!("MempoolSrv::new with conf {}", conf);
+        let max_pending = conf
+            .max_pending_txn_per_account
+            .unwrap_or(DEFAULT_MAX_PENDING_PER_ACCOUNT);
+        let max_pending_count = conf
+            .max_pending_txn_count
+            .unwrap_or(DEFAULT_MAX_PENDING_TXN_COUNT);
+        let bump_percent = conf
+            .min_replacement_bump_percent
+            .unwrap_or(DEFAULT_MIN_REPLACEMENT_BUMP_PERCENT);
+        let max_chain_len = conf
+            .max_replacement_chain_len
+            .unwrap_or(DEFAULT_MAX_REPLACEMENT_CHAIN_LEN);
+        let peer_max_tx_rate =
+            conf.peer_max_tx_rate.unwrap_or(DEFAULT_PEER_MAX_TX_RATE);
+        let peer_ban_duration =
+            conf.peer_ban_duration.unwrap_or(DEFAULT_PEER_BAN_DURATION);
         Self {
             inbound: AsyncQueue::bounded(
                 conf.max_queue_size,
@@ -63,6 +148,15 @@ impl MempoolSrv {
             ),
             conf,
             event_sender,
+            pending: PendingPool::new(
+                max_pending,
+                max_pending_count,
+                bump_percent,
+            ),
+            replacements: ReplacementTracker::new(max_chain_len),
+            peers: PeerScores::new(peer_max_tx_rate, peer_ban_duration),
+            #[cfg(feature = "archive")]
+            archive_sender,
         }
     }
 }
@@ -97,8 +191,17 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
             .unwrap_or(DEFAULT_EXPIRY_TIME)
             .as_secs();
 
+        let pending_check_interval = self
+            .conf
+            .pending_check_interval
+            .unwrap_or(DEFAULT_PENDING_CHECK_INTERVAL);
+
         // Mempool service loop
         let mut on_idle_event = tokio::time::interval(idle_interval);
+        let mut on_pending_check =
+            tokio::time::interval(pending_check_interval);
+        let mut on_peers_cleanup =
+            tokio::time::interval(PEERS_CLEANUP_INTERVAL);
         loop {
             tokio::select! {
                 biased;
@@ -119,26 +222,90 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
                                 if let Err(e) = self.event_sender.try_send(event.into()) {
                                     warn!("cannot notify mempool removed transaction {e}")
                                 };
+                                #[cfg(feature = "archive")]
+                                self.archive_tx_events(vec![(tx_id, MempoolTxEvent::Expired)]);
                             }
                         }
                         Ok(())
                     })?;
 
+                    // Remove expired transactions from the pending sub-pool
+                    for tx_id in self.pending.remove_expired(expiration_time) {
+                        info!(event = "expired_pending_tx", hash = hex::encode(tx_id));
+                        let event = TransactionEvent::Removed(tx_id);
+                        if let Err(e) = self.event_sender.try_send(event.into()) {
+                            warn!("cannot notify mempool removed transaction {e}")
+                        };
+                        #[cfg(feature = "archive")]
+                        self.archive_tx_events(vec![(tx_id, MempoolTxEvent::Expired)]);
+                    }
+                    gauge!("dusk_mempool_pending_txs").set(self.pending.len() as f64);
+
+                    // Forget the replacement chains of the transactions
+                    // neither in the mempool nor pending
+                    let replacements = &mut self.replacements;
+                    let pending = &self.pending;
+                    db.read().await.view(|view| {
+                        replacements.retain(|tx_id| {
+                            pending.contains(*tx_id)
+                                || view.get_tx_exists(*tx_id).unwrap_or_default()
+                        });
+                    });
+                },
+                _ = on_peers_cleanup.tick() => {
+                    let banned_peers = self.peers.cleanup(Instant::now());
+                    gauge!("dusk_mempool_banned_peers").set(banned_peers as f64);
+                },
+                _ = on_pending_check.tick() => {
+                    // The nonce gap of pending transactions could have been
+                    // filled by the accepted blocks
+                    for account in self.pending.accounts() {
+                        self.promote_pending::<N, DB, VM>(&network, &db, &vm, &account).await;
+                    }
                 },
                 msg = self.inbound.recv() => {
                     if let Ok(msg) = msg {
                         match &msg.payload {
                             Payload::Transaction(tx) => {
+                                // Transactions received from the network are
+                                // accounted to the sending peer
+                                let src_addr = msg.metadata.as_ref().map(|m| m.src_addr);
+                                if let Some(src_addr) = src_addr {
+                                    if let Err(e) = self.peers.check(src_addr, Instant::now()) {
+                                        debug!(event = "tx dropped", src = ?src_addr, reason = %e);
+                                        counter!("dusk_mempool_peer_dropped_txs").increment(1);
+                                        continue;
+                                    }
+                                }
+
                                 let accept = self.accept_tx::<DB, VM>(&db, &vm, tx);
-                                if let Err(e) = accept.await {
-                                    error!("{}", e);
-                                    continue;
+                                match accept.await {
+                                    Ok(TxAcceptance::Accepted) => {}
+                                    Ok(TxAcceptance::Pending) => continue,
+                                    Err(e) => {
+                                        error!("{}", e);
+                                        if let (Some(src_addr), Some(penalty)) = (src_addr, e.peer_penalty()) {
+                                            if self.peers.penalize(src_addr, penalty, Instant::now()) {
+                                                warn!(event = "peer banned", src = ?src_addr);
+                                                counter!("dusk_mempool_peer_bans").increment(1);
+                                            }
+                                        }
+                                        continue;
+                                    }
                                 }
 
-                                let network = network.read().await;
-                                if let Err(e) = network.broadcast(&msg).await {
-                                    warn!("Unable to broadcast accepted tx: {e}")
-                                };
+                                {
+                                    let network = network.read().await;
+                                    if let Err(e) = network.broadcast(&msg).await {
+                                        warn!("Unable to broadcast accepted tx: {e}")
+                                    };
+                                }
+
+                                // A new nonce could fill the gap of the pending
+                                // transactions of the same account
+                                if let Some((account, _)) = tx.moonlight_nonce() {
+                                    self.promote_pending::<N, DB, VM>(&network, &db, &vm, &account).await;
+                                }
                             }
                             _ => error!("invalid inbound message payload"),
                         }
@@ -160,14 +327,39 @@ impl MempoolSrv {
         db: &Arc<RwLock<DB>>,
         vm: &Arc<RwLock<VM>>,
         tx: &Transaction,
-    ) -> Result<(), TxAcceptanceError> {
+    ) -> Result<TxAcceptance, TxAcceptanceError> {
         let tx_id = tx.id();
 
+        if self.pending.contains(tx_id) {
+            return Err(TxAcceptanceError::AlreadyPending);
+        }
+
         // Perform basic checks on the transaction
         db.read().await.view(|view| {
             let count = view.txs_count();
             if count >= self.conf.max_mempool_txn_count {
-                return Err(TxAcceptanceError::MaxTxnCountExceeded(count));
+                match self.conf.eviction_policy {
+                    EvictionPolicy::Reject => {
+                        return Err(TxAcceptanceError::MaxTxnCountExceeded(
+                            count,
+                        ));
+                    }
+                    EvictionPolicy::EvictLowestFee => {
+                        // Fail fast if there is no cheaper transaction to
+                        // evict
+                        let lowest =
+                            view.get_txs_ids_sorted_by_low_fee()?.next();
+                        if let Some((gas_price, _)) = lowest {
+                            if gas_price >= tx.gas_price() {
+                                return Err(
+                                    TxAcceptanceError::MaxTxnCountExceeded(
+                                        count,
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                }
             }
 
             // ensure transaction does not exist in the mempool
@@ -185,27 +377,149 @@ impl MempoolSrv {
 
         // VM Preverify call
         if let Err(e) = vm.read().await.preverify(tx) {
-            Err(TxAcceptanceError::VerificationFailed(format!("{e:?}")))?;
+            let err = format!("{e:?}");
+            if e.is::<PreverificationError>() {
+                Err(TxAcceptanceError::Forged(err))?;
+            }
+            Err(TxAcceptanceError::VerificationFailed(err))?;
+        }
+
+        // Hold Moonlight transactions that cannot be executed until the
+        // previous nonces of the same account are known
+        if let Some((account, nonce)) = tx.moonlight_nonce() {
+            let next_nonce = self.next_nonce(db, vm, &account).await?;
+            if nonce > next_nonce {
+                // A pending transaction with the same nonce is subject to the
+                // same replacement rules as the mempool ones
+                let replaced: Vec<_> =
+                    self.pending.conflicting(tx).into_iter().collect();
+                let chain_len = self
+                    .replacements
+                    .chain_len(&replaced)
+                    .map_err(TxAcceptanceError::ReplacementRefused)?;
+
+                let now = get_current_timestamp();
+                if !self.pending.insert(tx.clone(), now) {
+                    return Err(TxAcceptanceError::PendingRejected(
+                        nonce, next_nonce,
+                    ));
+                }
+                self.replacements.insert(tx_id, chain_len);
+
+                info!(
+                    event = "transaction pending",
+                    hash = hex::encode(tx_id),
+                    nonce,
+                    next_nonce
+                );
+                gauge!("dusk_mempool_pending_txs")
+                    .set(self.pending.len() as f64);
+
+                let mut history = vec![];
+                for m_tx_id in replaced {
+                    info!(event = "replaced tx", hash = hex::encode(m_tx_id));
+                    let event = TransactionEvent::Removed(m_tx_id);
+                    if let Err(e) = self.event_sender.try_send(event.into()) {
+                        warn!("cannot notify mempool removed transaction {e}")
+                    };
+                    history.push((m_tx_id, MempoolTxEvent::Replaced(tx_id)));
+                }
+                #[cfg(feature = "archive")]
+                self.archive_tx_events(history);
+
+                return Ok(TxAcceptance::Pending);
+            }
         }
 
         let mut events = vec![];
+        let mut history = vec![];
 
         // Try to add the transaction to the mempool
-        db.read().await.update(|db| {
+        let chain_len = db.read().await.update(|db| {
             let spend_ids = tx.to_spend_ids();
-
-            // ensure spend_ids do not exist in the mempool
+            let bump_percent = self
+                .conf
+                .min_replacement_bump_percent
+                .unwrap_or(DEFAULT_MIN_REPLACEMENT_BUMP_PERCENT);
+
+            // ensure spend_ids do not exist in the mempool, unless the
+            // transaction pays enough to replace the conflicting ones.
+            // This includes Moonlight transactions with the same account and
+            // nonce.
+            let mut replaced = vec![];
             for m_tx_id in db.get_txs_by_spendable_ids(&spend_ids) {
                 if let Some(m_tx) = db.get_tx(m_tx_id)? {
-                    if m_tx.inner.gas_price() < tx.inner.gas_price() {
-                        if db.delete_tx(m_tx_id)? {
-                            events.push(TransactionEvent::Removed(m_tx_id));
-                        };
-                    } else {
+                    let m_gas_price = m_tx.inner.gas_price();
+                    let min_price =
+                        min_replacement_price(m_gas_price, bump_percent);
+                    if tx.inner.gas_price() < min_price {
+                        let err = ReplacementError::Underpriced(
+                            tx.inner.gas_price(),
+                            min_price,
+                        );
                         return Err(
-                            TxAcceptanceError::SpendIdExistsInMempool.into()
+                            TxAcceptanceError::ReplacementRefused(err).into()
                         );
                     }
+
+                    replaced.push(m_tx_id);
+                }
+            }
+
+            let chain_len = self
+                .replacements
+                .chain_len(&replaced)
+                .map_err(TxAcceptanceError::ReplacementRefused)?;
+
+            for m_tx_id in replaced {
+                if db.delete_tx(m_tx_id)? {
+                    info!(event = "replaced tx", hash = hex::encode(m_tx_id));
+                    events.push(TransactionEvent::Removed(m_tx_id));
+                    history.push((m_tx_id, MempoolTxEvent::Replaced(tx_id)));
+                };
+            }
+
+            // Make room for the new transaction by evicting the ones with the
+            // lowest gas price
+            if self.conf.eviction_policy == EvictionPolicy::EvictLowestFee {
+                let count = db.txs_count();
+                let max_count = self.conf.max_mempool_txn_count;
+                if count >= max_count {
+                    let to_evict = count + 1 - max_count;
+                    let mut evicted = vec![];
+                    for (gas_price, m_tx_id) in
+                        db.get_txs_ids_sorted_by_low_fee()?
+                    {
+                        if evicted.len() == to_evict
+                            || gas_price >= tx.gas_price()
+                        {
+                            break;
+                        }
+                        // Evicting a nonce followed by others of the same
+                        // account would make them unexecutable
+                        if !strands_later_nonces(db, m_tx_id, &evicted, tx)? {
+                            evicted.push(m_tx_id);
+                        }
+                    }
+
+                    if evicted.len() < to_evict {
+                        return Err(TxAcceptanceError::MaxTxnCountExceeded(
+                            count,
+                        )
+                        .into());
+                    }
+
+                    for m_tx_id in evicted {
+                        if db.delete_tx(m_tx_id)? {
+                            info!(
+                                event = "evicted tx",
+                                hash = hex::encode(m_tx_id)
+                            );
+                            counter!("dusk_mempool_evicted_txs").increment(1);
+                            events.push(TransactionEvent::Removed(m_tx_id));
+                            history.push((m_tx_id, MempoolTxEvent::Evicted));
+                        }
+                    }
                 }
             }
 
@@ -214,9 +528,13 @@ impl MempoolSrv {
 
             let now = get_current_timestamp();
 
-            db.add_tx(tx, now)
+            db.add_tx(tx, now)?;
+
+            Ok(chain_len)
         })?;
 
+        self.replacements.insert(tx_id, chain_len);
+
         tracing::info!(
             event = "transaction accepted",
             hash = hex::encode(tx_id)
@@ -229,26 +547,151 @@ impl MempoolSrv {
             };
         }
 
-        Ok(())
+        history.push((tx_id, MempoolTxEvent::Included));
+        #[cfg(feature = "archive")]
+        self.archive_tx_events(history);
+
+        Ok(TxAcceptance::Accepted)
+    }
+
+    /// Sends the lifecycle events of mempool transactions to the archivist
+    #[cfg(feature = "archive")]
+    fn archive_tx_events(&self, history: Vec<([u8; 32], MempoolTxEvent)>) {
+        let now = get_current_timestamp();
+        for (tx_id, event) in history {
+            let data = ArchivalData::MempoolTx(tx_id, event, now);
+            if let Err(e) = self.archive_sender.try_send(data) {
+                warn!("cannot send mempool event to archivist {e}")
+            };
+        }
+    }
+
+    /// Returns the lowest nonce of `account` that can be executed after all
+    /// the account transactions in the mempool.
+    async fn next_nonce<DB: database::DB, VM: vm::VMExecution>(
+        &self,
+        db: &Arc<RwLock<DB>>,
+        vm: &Arc<RwLock<VM>>,
+        account: &AccountPublicKey,
+    ) -> Result<u64, TxAcceptanceError> {
+        let mut next_nonce = vm.read().await.get_account_nonce(account)? + 1;
+
+        let mempool_nonces = db
+            .read()
+            .await
+            .view(|view| view.get_txs_by_account(account))?;
+
+        // Skip all the consecutive nonces already in the mempool
+        for (nonce, _) in mempool_nonces {
+            if nonce == next_nonce {
+                next_nonce += 1;
+            } else if nonce > next_nonce {
+                break;
+            }
+        }
+
+        Ok(next_nonce)
+    }
+
+    /// Moves to the mempool all the pending transactions of `account` whose
+    /// nonce gap has been filled, propagating them to the network in nonce
+    /// order.
+    async fn promote_pending<
+        N: Network,
+        DB: database::DB,
+        VM: vm::VMExecution,
+    >(
+        &mut self,
+        network: &Arc<RwLock<N>>,
+        db: &Arc<RwLock<DB>>,
+        vm: &Arc<RwLock<VM>>,
+        account: &AccountPublicKey,
+    ) {
+        loop {
+            let next_nonce = match self.next_nonce(db, vm, account).await {
+                Ok(next_nonce) => next_nonce,
+                Err(e) => {
+                    warn!("Unable to get account nonce: {e}");
+                    break;
+                }
+            };
+
+            let Some(tx) = self.pending.pop_ready(account, next_nonce) else {
+                break;
+            };
+
+            let tx_id = hex::encode(tx.id());
+            match self.accept_tx::<DB, VM>(db, vm, &tx).await {
+                Ok(TxAcceptance::Accepted) => {
+                    info!(event = "pending transaction promoted", hash = tx_id);
+                    let msg = Message::from(tx);
+                    if let Err(e) = network.read().await.broadcast(&msg).await {
+                        warn!("Unable to broadcast promoted tx: {e}")
+                    };
+                }
+                Ok(TxAcceptance::Pending) => {}
+                Err(e) => {
+                    info!(event = "pending transaction discarded", hash = tx_id, err = %e);
+                }
+            }
+        }
+
+        gauge!("dusk_mempool_pending_txs").set(self.pending.len() as f64);
     }
 
     /// Requests full mempool data from N alive peers
     ///
     /// Message flow:
     /// GetMempool -> Inv -> GetResource -> Tx
-    async fn request_mempool<N: Network>(&self, network: &Arc<RwLock<N>>) {
+    ///
+    /// The requested peers are not rate limited for
+    /// [`MEMPOOL_SYNC_DURATION`].
+    async fn request_mempool<N: Network>(&mut self, network: &Arc<RwLock<N>>) {
         let max_peers = self
             .conf
             .mempool_download_redundancy
             .unwrap_or(DEFAULT_DOWNLOAD_REDUNDANCY);
-        let msg = payload::GetMempool::default().into();
-        if let Err(err) = network
-            .read()
-            .await
-            .send_to_alive_peers(msg, max_peers)
-            .await
-        {
-            error!("could not request mempool from network: {err}");
+
+        let network = network.read().await;
+        let peers = network.alive_nodes(max_peers).await;
+        for peer in &peers {
+            let msg = payload::GetMempool::default().into();
+            if let Err(err) = network.send_to_peer(msg, *peer).await {
+                error!("could not request mempool from {peer}: {err}");
+            }
+        }
+
+        let until = Instant::now() + MEMPOOL_SYNC_DURATION;
+        self.peers.start_sync(peers, until);
+    }
+}
+
+/// Returns true if a later nonce of the Moonlight account that sent the
+/// transaction `tx_id` is either in the mempool, other than the `evicted`
+/// ones, or is the `incoming` transaction.
+fn strands_later_nonces<M: Mempool>(
+    db: &M,
+    tx_id: [u8; 32],
+    evicted: &[[u8; 32]],
+    incoming: &Transaction,
+) -> anyhow::Result<bool> {
+    let Some((account, nonce)) = db
+        .get_tx(tx_id)?
+        .as_ref()
+        .and_then(Transaction::moonlight_nonce)
+    else {
+        return Ok(false);
+    };
+
+    if let Some((in_account, in_nonce)) = incoming.moonlight_nonce() {
+        if in_account == account && in_nonce > nonce {
+            return Ok(true);
         }
     }
+
+    let stranded = db
+        .get_txs_by_account(&account)?
+        .into_iter()
+        .any(|(n, id)| n > nonce && !evicted.contains(&id));
+    Ok(stranded)
 }
@@ -11,6 +11,28 @@ use std::{fmt::Formatter, time::Duration};
 pub const DEFAULT_EXPIRY_TIME: Duration = Duration::from_secs(3 * 60 * 60 * 24); /* 3 days */
 pub const DEFAULT_IDLE_INTERVAL: Duration = Duration::from_secs(60 * 60); /* 1 hour */
 pub const DEFAULT_DOWNLOAD_REDUNDANCY: usize = 5;
+pub const DEFAULT_MAX_PENDING_PER_ACCOUNT: usize = 64;
+pub const DEFAULT_MAX_PENDING_TXN_COUNT: usize = 10_000;
+pub const DEFAULT_PENDING_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+pub const DEFAULT_MIN_REPLACEMENT_BUMP_PERCENT: u64 = 10;
+pub const DEFAULT_MAX_REPLACEMENT_CHAIN_LEN: usize = 5;
+pub const DEFAULT_PEER_MAX_TX_RATE: u32 = 100;
+pub const DEFAULT_PEER_BAN_DURATION: Duration = Duration::from_secs(10 * 60); /* 10 minutes */
+
+/// Policy applied when a new transaction is received while the mempool holds
+/// `max_mempool_txn_count` transactions
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    /// Reject the new transaction
+    Reject,
+    /// Evict the transactions with the lowest gas price, as long as the new
+    /// transaction pays a higher one
+    #[default]
+    EvictLowestFee,
+}
 
 #[derive(Serialize, Deserialize, Copy, Clone)]
 pub struct Params {
@@ -20,6 +42,10 @@ pub struct Params {
     /// Maximum number of transactions that can be accepted/stored in mempool
     pub max_mempool_txn_count: usize,
 
+    /// Policy applied when `max_mempool_txn_count` is reached
+    #[serde(default)]
+    pub eviction_policy: EvictionPolicy,
+
     /// Interval to check for expired transactions
     #[serde(with = "humantime_serde")]
     pub idle_interval: Option<Duration>,
@@ -30,6 +56,37 @@ pub struct Params {
 
     /// max number of peers to request mempool from
     pub mempool_download_redundancy: Option<usize>,
+
+    /// Maximum number of future-nonce Moonlight transactions held per account
+    /// until the missing nonces are filled
+    pub max_pending_txn_per_account: Option<usize>,
+
+    /// Maximum number of future-nonce Moonlight transactions held across all
+    /// the accounts
+    pub max_pending_txn_count: Option<usize>,
+
+    /// Interval to check if pending Moonlight transactions can be promoted
+    /// to the mempool
+    #[serde(with = "humantime_serde")]
+    #[serde(default)]
+    pub pending_check_interval: Option<Duration>,
+
+    /// Minimum gas price increase, in percent, required to replace a
+    /// conflicting transaction in the mempool
+    pub min_replacement_bump_percent: Option<u64>,
+
+    /// Maximum number of consecutive replacements of a conflicting
+    /// transaction
+    pub max_replacement_chain_len: Option<usize>,
+
+    /// Maximum number of transactions per second accepted from a single
+    /// network peer
+    pub peer_max_tx_rate: Option<u32>,
+
+    /// Duration for which the transactions of a misbehaving peer are dropped
+    #[serde(with = "humantime_serde")]
+    #[serde(default)]
+    pub peer_ban_duration: Option<Duration>,
 }
 
 impl Default for Params {
@@ -37,9 +94,19 @@ impl Default for Params {
         Self {
             max_queue_size: 1000,
             max_mempool_txn_count: 10_000,
+            eviction_policy: EvictionPolicy::default(),
             idle_interval: Some(DEFAULT_IDLE_INTERVAL),
             mempool_expiry: Some(DEFAULT_EXPIRY_TIME),
             mempool_download_redundancy: Some(DEFAULT_DOWNLOAD_REDUNDANCY),
+            max_pending_txn_per_account: Some(DEFAULT_MAX_PENDING_PER_ACCOUNT),
+            max_pending_txn_count: Some(DEFAULT_MAX_PENDING_TXN_COUNT),
+            pending_check_interval: Some(DEFAULT_PENDING_CHECK_INTERVAL),
+            min_replacement_bump_percent: Some(
+                DEFAULT_MIN_REPLACEMENT_BUMP_PERCENT,
+            ),
+            max_replacement_chain_len: Some(DEFAULT_MAX_REPLACEMENT_CHAIN_LEN),
+            peer_max_tx_rate: Some(DEFAULT_PEER_MAX_TX_RATE),
+            peer_ban_duration: Some(DEFAULT_PEER_BAN_DURATION),
         }
     }
 }
@@ -48,13 +115,25 @@ impl std::fmt::Display for Params {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "max_queue_size: {}, max_mempool_txn_count: {},
-         idle_interval: {:?}, mempool_expiry: {:?}, mempool_download_redundancy: {:?}",
+            "max_queue_size: {}, max_mempool_txn_count: {}, eviction_policy: {:?},
+         idle_interval: {:?}, mempool_expiry: {:?}, mempool_download_redundancy: {:?},
+         max_pending_txn_per_account: {:?}, max_pending_txn_count: {:?},
+         pending_check_interval: {:?},
+         min_replacement_bump_percent: {:?}, max_replacement_chain_len: {:?},
+         peer_max_tx_rate: {:?}, peer_ban_duration: {:?}",
             self.max_queue_size,
             self.max_mempool_txn_count,
+            self.eviction_policy,
             self.idle_interval,
             self.mempool_expiry,
-            self.mempool_download_redundancy
+            self.mempool_download_redundancy,
+            self.max_pending_txn_per_account,
+            self.max_pending_txn_count,
+            self.pending_check_interval,
+            self.min_replacement_bump_percent,
+            self.max_replacement_chain_len,
+            self.peer_max_tx_rate,
+            self.peer_ban_duration
         )
     }
 }
@@ -0,0 +1,243 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+/// Score of a well-behaving peer
+const MAX_SCORE: f64 = 100.0;
+/// Score points recovered every second
+const SCORE_RECOVERY_PER_SEC: f64 = 1.0;
+
+/// Score penalty for a transaction with an invalid proof or signature
+pub(crate) const INVALID_TX_PENALTY: f64 = 20.0;
+
+#[derive(Debug, Error, PartialEq)]
+pub(crate) enum PeerRejection {
+    #[error("peer is banned")]
+    Banned,
+    #[error("peer exceeded its transaction rate")]
+    Throttled,
+}
+
+struct PeerState {
+    score: f64,
+    tokens: f64,
+    last_update: Instant,
+    banned_until: Option<Instant>,
+}
+
+/// Per-peer accounting of the transactions received from the network.
+///
+/// Each peer is allowed to send up to `max_rate` transactions per second,
+/// with bursts of the same size, unless it is answering one of our mempool
+/// requests. Every transaction that is provably forged decreases the peer
+/// score, which slowly recovers over time. Once the score drops to zero, all
+/// the transactions from the peer are dropped for `ban_duration`.
+pub(crate) struct PeerScores {
+    max_rate: f64,
+    ban_duration: Duration,
+    peers: HashMap<SocketAddr, PeerState>,
+    /// Peers asked for their mempool, with the end of their exemption from
+    /// the rate limit
+    syncing: HashMap<SocketAddr, Instant>,
+}
+
+impl PeerScores {
+    pub(crate) fn new(max_rate: u32, ban_duration: Duration) -> Self {
+        Self {
+            max_rate: max_rate as f64,
+            ban_duration,
+            peers: HashMap::new(),
+            syncing: HashMap::new(),
+        }
+    }
+
+    /// Exempts `addrs` from the rate limit until `until`, so that they can
+    /// send their whole mempool in response to our request.
+    pub(crate) fn start_sync(
+        &mut self,
+        addrs: impl IntoIterator<Item = SocketAddr>,
+        until: Instant,
+    ) {
+        for addr in addrs {
+            self.syncing.insert(addr, until);
+        }
+    }
+
+    fn peer(&mut self, addr: SocketAddr, now: Instant) -> &mut PeerState {
+        let max_rate = self.max_rate;
+        let peer = self.peers.entry(addr).or_insert(PeerState {
+            score: MAX_SCORE,
+            tokens: max_rate,
+            last_update: now,
+            banned_until: None,
+        });
+
+        // Refill the rate limit tokens and recover the score
+        let elapsed = now.saturating_duration_since(peer.last_update);
+        let elapsed = elapsed.as_secs_f64();
+        peer.tokens = (peer.tokens + elapsed * max_rate).min(max_rate);
+        peer.score =
+            (peer.score + elapsed * SCORE_RECOVERY_PER_SEC).min(MAX_SCORE);
+        peer.last_update = now;
+
+        if matches!(peer.banned_until, Some(until) if until <= now) {
+            peer.banned_until = None;
+            peer.score = MAX_SCORE;
+        }
+
+        peer
+    }
+
+    /// Accounts a transaction received from `addr`, returning an error if it
+    /// should be dropped without being processed.
+    pub(crate) fn check(
+        &mut self,
+        addr: SocketAddr,
+        now: Instant,
+    ) -> Result<(), PeerRejection> {
+        let syncing =
+            matches!(self.syncing.get(&addr), Some(until) if *until > now);
+        let peer = self.peer(addr, now);
+
+        if peer.banned_until.is_some() {
+            return Err(PeerRejection::Banned);
+        }
+
+        if syncing {
+            return Ok(());
+        }
+
+        if peer.tokens < 1.0 {
+            return Err(PeerRejection::Throttled);
+        }
+        peer.tokens -= 1.0;
+
+        Ok(())
+    }
+
+    /// Decreases the score of `addr` by `penalty`.
+    ///
+    /// Returns `true` if the peer has been banned as a consequence.
+    pub(crate) fn penalize(
+        &mut self,
+        addr: SocketAddr,
+        penalty: f64,
+        now: Instant,
+    ) -> bool {
+        let ban_duration = self.ban_duration;
+        let peer = self.peer(addr, now);
+
+        if peer.banned_until.is_some() {
+            return false;
+        }
+
+        peer.score -= penalty;
+        if peer.score <= 0.0 {
+            peer.banned_until = Some(now + ban_duration);
+            return true;
+        }
+
+        false
+    }
+
+    /// Forgets all the peers that are neither banned, penalized nor
+    /// throttled, and returns the number of banned peers.
+    pub(crate) fn cleanup(&mut self, now: Instant) -> usize {
+        self.syncing.retain(|_, until| *until > now);
+
+        let max_rate = self.max_rate;
+        let addrs: Vec<_> = self.peers.keys().copied().collect();
+        for addr in addrs {
+            let peer = self.peer(addr, now);
+            if peer.banned_until.is_none()
+                && peer.score >= MAX_SCORE
+                && peer.tokens >= max_rate
+            {
+                self.peers.remove(&addr);
+            }
+        }
+
+        self.peers
+            .values()
+            .filter(|p| p.banned_until.is_some())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit() {
+        let addr: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let other: SocketAddr = "127.0.0.1:9001".parse().unwrap();
+        let now = Instant::now();
+        let mut peers = PeerScores::new(2, Duration::from_secs(60));
+
+        assert_eq!(peers.check(addr, now), Ok(()));
+        assert_eq!(peers.check(addr, now), Ok(()));
+        assert_eq!(peers.check(addr, now), Err(PeerRejection::Throttled));
+        assert_eq!(peers.check(other, now), Ok(()));
+
+        let later = now + Duration::from_millis(500);
+        assert_eq!(peers.check(addr, later), Ok(()));
+        assert_eq!(peers.check(addr, later), Err(PeerRejection::Throttled));
+    }
+
+    #[test]
+    fn test_ban() {
+        let addr: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let now = Instant::now();
+        let ban_duration = Duration::from_secs(60);
+        let mut peers = PeerScores::new(100, ban_duration);
+
+        for _ in 0..4 {
+            assert!(!peers.penalize(addr, INVALID_TX_PENALTY, now));
+        }
+        assert_eq!(peers.cleanup(now), 0);
+
+        assert!(peers.penalize(addr, INVALID_TX_PENALTY, now));
+        assert_eq!(peers.check(addr, now), Err(PeerRejection::Banned));
+        assert_eq!(peers.cleanup(now), 1);
+
+        let later = now + ban_duration;
+        assert_eq!(peers.check(addr, later), Ok(()));
+        assert_eq!(peers.cleanup(later), 0);
+        assert!(!peers.peers.is_empty());
+
+        assert_eq!(peers.cleanup(later + Duration::from_secs(1)), 0);
+        assert!(peers.peers.is_empty());
+    }
+
+    #[test]
+    fn test_sync_exemption() {
+        let addr: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let other: SocketAddr = "127.0.0.1:9001".parse().unwrap();
+        let now = Instant::now();
+        let until = now + Duration::from_secs(60);
+        let mut peers = PeerScores::new(1, Duration::from_secs(120));
+
+        peers.start_sync([addr], until);
+        for _ in 0..10 {
+            assert_eq!(peers.check(addr, now), Ok(()));
+        }
+        assert_eq!(peers.check(other, now), Ok(()));
+        assert_eq!(peers.check(other, now), Err(PeerRejection::Throttled));
+
+        // Banned peers are not exempted
+        peers.penalize(addr, MAX_SCORE, now);
+        assert_eq!(peers.check(addr, now), Err(PeerRejection::Banned));
+
+        assert_eq!(peers.cleanup(until), 1);
+        assert!(peers.syncing.is_empty());
+    }
+}
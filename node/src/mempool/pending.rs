@@ -0,0 +1,218 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use dusk_bytes::Serializable;
+use execution_core::signatures::bls::PublicKey as AccountPublicKey;
+use node_data::bls::PublicKeyBytes;
+use node_data::ledger::Transaction;
+
+use super::rbf::min_replacement_price;
+
+struct PendingTx {
+    tx: Transaction,
+    timestamp: u64,
+}
+
+struct PendingAccount {
+    account: AccountPublicKey,
+    txs: BTreeMap<u64, PendingTx>,
+}
+
+/// Sub-pool of Moonlight transactions whose nonce cannot be executed yet,
+/// because one or more preceding nonces of the same account are neither in
+/// the mempool nor in the ledger.
+///
+/// Pending transactions are neither propagated nor offered to the block
+/// generator. They are promoted to the mempool as soon as the nonce gap is
+/// filled.
+pub(crate) struct PendingPool {
+    max_per_account: usize,
+    max_count: usize,
+    bump_percent: u64,
+    accounts: HashMap<PublicKeyBytes, PendingAccount>,
+    /// Ids of all the pending transactions
+    ids: HashSet<[u8; 32]>,
+}
+
+impl PendingPool {
+    pub(crate) fn new(
+        max_per_account: usize,
+        max_count: usize,
+        bump_percent: u64,
+    ) -> Self {
+        Self {
+            max_per_account,
+            max_count,
+            bump_percent,
+            accounts: HashMap::new(),
+            ids: HashSet::new(),
+        }
+    }
+
+    /// Holds a Moonlight transaction until its nonce becomes executable.
+    ///
+    /// A transaction with the same nonce as an already pending one replaces it
+    /// only if it bumps the gas price by at least `bump_percent` percent.
+    /// Otherwise it is held only if neither the account nor the pool are full.
+    ///
+    /// Returns `false` if the transaction has not been held.
+    pub(crate) fn insert(&mut self, tx: Transaction, timestamp: u64) -> bool {
+        let Some((account, nonce)) = tx.moonlight_nonce() else {
+            return false;
+        };
+
+        let key = PublicKeyBytes(account.to_bytes());
+        let held = self.accounts.get(&key);
+        match held.and_then(|p| p.txs.get(&nonce)) {
+            Some(p) => {
+                let min_price =
+                    min_replacement_price(p.tx.gas_price(), self.bump_percent);
+                if tx.gas_price() < min_price {
+                    return false;
+                }
+            }
+            None => {
+                let account_len = held.map_or(0, |p| p.txs.len());
+                if account_len >= self.max_per_account
+                    || self.ids.len() >= self.max_count
+                {
+                    return false;
+                }
+            }
+        }
+
+        let pending = self.accounts.entry(key).or_insert(PendingAccount {
+            account,
+            txs: BTreeMap::new(),
+        });
+        self.ids.insert(tx.id());
+        if let Some(replaced) =
+            pending.txs.insert(nonce, PendingTx { tx, timestamp })
+        {
+            self.ids.remove(&replaced.tx.id());
+        }
+        true
+    }
+
+    /// Returns the id of the pending transaction with the same account and
+    /// nonce as `tx`, which `tx` would replace.
+    pub(crate) fn conflicting(&self, tx: &Transaction) -> Option<[u8; 32]> {
+        let (account, nonce) = tx.moonlight_nonce()?;
+        let key = PublicKeyBytes(account.to_bytes());
+        let pending = self.accounts.get(&key)?.txs.get(&nonce)?;
+        Some(pending.tx.id())
+    }
+
+    /// Removes and returns the lowest-nonce pending transaction of `account`,
+    /// if its nonce is not greater than `next_nonce`.
+    pub(crate) fn pop_ready(
+        &mut self,
+        account: &AccountPublicKey,
+        next_nonce: u64,
+    ) -> Option<Transaction> {
+        let key = PublicKeyBytes(account.to_bytes());
+        let pending = self.accounts.get_mut(&key)?;
+
+        let entry = pending.txs.first_entry()?;
+        if *entry.key() > next_nonce {
+            return None;
+        }
+        let tx = entry.remove().tx;
+        self.ids.remove(&tx.id());
+
+        if pending.txs.is_empty() {
+            self.accounts.remove(&key);
+        }
+
+        Some(tx)
+    }
+
+    /// Returns true if a transaction with the given hash is pending.
+    pub(crate) fn contains(&self, tx_id: [u8; 32]) -> bool {
+        self.ids.contains(&tx_id)
+    }
+
+    /// Returns all the accounts with at least one pending transaction.
+    pub(crate) fn accounts(&self) -> Vec<AccountPublicKey> {
+        self.accounts.values().map(|p| p.account).collect()
+    }
+
+    /// Removes all pending transactions received before `timestamp`,
+    /// returning their hashes.
+    pub(crate) fn remove_expired(&mut self, timestamp: u64) -> Vec<[u8; 32]> {
+        let mut expired = vec![];
+        let ids = &mut self.ids;
+        self.accounts.retain(|_, pending| {
+            pending.txs.retain(|_, p| {
+                if p.timestamp <= timestamp {
+                    let tx_id = p.tx.id();
+                    ids.remove(&tx_id);
+                    expired.push(tx_id);
+                    return false;
+                }
+                true
+            });
+            !pending.txs.is_empty()
+        });
+        expired
+    }
+
+    /// Number of pending transactions.
+    pub(crate) fn len(&self) -> usize {
+        self.ids.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use node_data::ledger::faker::{gen_dummy_moonlight_tx, gen_dummy_tx};
+
+    #[test]
+    fn test_pending_pool() {
+        let mut pool = PendingPool::new(2, 3, 100);
+
+        // Phoenix transactions have no nonce
+        assert!(!pool.insert(gen_dummy_tx(1), 0));
+
+        let tx_3 = gen_dummy_moonlight_tx(1, 3, 1);
+        let tx_5 = gen_dummy_moonlight_tx(1, 5, 1);
+        let (account, _) = tx_3.moonlight_nonce().unwrap();
+
+        assert!(pool.insert(tx_5.clone(), 10));
+        assert!(pool.insert(tx_3.clone(), 20));
+        assert!(pool.contains(tx_3.id()));
+
+        // Account limit reached, without leaving an empty account behind
+        assert!(!pool.insert(gen_dummy_moonlight_tx(1, 4, 1), 0));
+
+        // Pool limit reached
+        assert!(pool.insert(gen_dummy_moonlight_tx(2, 3, 1), 0));
+        assert!(!pool.insert(gen_dummy_moonlight_tx(3, 3, 1), 0));
+        assert_eq!(pool.accounts().len(), 2);
+        assert_eq!(pool.remove_expired(0).len(), 1);
+
+        // Same nonce is replaced only by a bumped gas price
+        assert!(!pool.insert(gen_dummy_moonlight_tx(1, 3, 1), 0));
+        let tx_3_bump = gen_dummy_moonlight_tx(1, 3, 2);
+        assert_eq!(pool.conflicting(&tx_3_bump), Some(tx_3.id()));
+        assert_eq!(pool.conflicting(&gen_dummy_moonlight_tx(1, 4, 2)), None);
+        assert!(pool.insert(tx_3_bump.clone(), 20));
+        assert!(!pool.contains(tx_3.id()));
+
+        // Nonces are popped in order, only once the gap is filled
+        assert!(pool.pop_ready(&account, 2).is_none());
+        assert_eq!(pool.pop_ready(&account, 3), Some(tx_3_bump));
+        assert!(pool.pop_ready(&account, 4).is_none());
+        assert_eq!(pool.len(), 1);
+
+        assert_eq!(pool.remove_expired(10), vec![tx_5.id()]);
+        assert_eq!(pool.len(), 0);
+        assert!(pool.accounts().is_empty());
+    }
+}
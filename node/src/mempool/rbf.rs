@@ -0,0 +1,121 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+/// Reasons for refusing the replacement of a mempool transaction
+#[derive(Debug, Error)]
+pub(crate) enum ReplacementError {
+    #[error("gas price {0} is lower than the minimum replacement price {1}")]
+    Underpriced(u64, u64),
+    #[error("maximum replacement chain length {0} reached")]
+    ChainTooLong(usize),
+}
+
+/// Returns the minimum gas price a transaction has to pay in order to replace
+/// a conflicting one paying `gas_price`.
+///
+/// The price must be bumped by at least `bump_percent` percent, and in any
+/// case by at least 1 LUX.
+pub(crate) fn min_replacement_price(gas_price: u64, bump_percent: u64) -> u64 {
+    let bump = (gas_price as u128 * bump_percent as u128).div_ceil(100);
+    let bump = u64::try_from(bump).unwrap_or(u64::MAX).max(1);
+    gas_price.saturating_add(bump)
+}
+
+/// Keeps track of how many times the transactions in the mempool have
+/// replaced a conflicting one, so that the same spending slot cannot be
+/// replaced indefinitely.
+pub(crate) struct ReplacementTracker {
+    max_chain_len: usize,
+    chains: HashMap<[u8; 32], usize>,
+}
+
+impl ReplacementTracker {
+    pub(crate) fn new(max_chain_len: usize) -> Self {
+        Self {
+            max_chain_len,
+            chains: HashMap::new(),
+        }
+    }
+
+    /// Returns the replacement chain length of a transaction replacing all
+    /// the `replaced` ones, or an error if it would exceed the maximum
+    /// allowed.
+    pub(crate) fn chain_len(
+        &self,
+        replaced: &[[u8; 32]],
+    ) -> Result<usize, ReplacementError> {
+        if replaced.is_empty() {
+            return Ok(0);
+        }
+
+        let chain_len = replaced
+            .iter()
+            .map(|tx_id| self.chains.get(tx_id).copied().unwrap_or_default())
+            .max()
+            .unwrap_or_default()
+            + 1;
+
+        if chain_len > self.max_chain_len {
+            return Err(ReplacementError::ChainTooLong(self.max_chain_len));
+        }
+
+        Ok(chain_len)
+    }
+
+    /// Records the replacement chain length of an accepted transaction.
+    pub(crate) fn insert(&mut self, tx_id: [u8; 32], chain_len: usize) {
+        if chain_len > 0 {
+            self.chains.insert(tx_id, chain_len);
+        }
+    }
+
+    /// Drops the chain length of all the transactions for which `f` returns
+    /// `false`.
+    pub(crate) fn retain(&mut self, mut f: impl FnMut(&[u8; 32]) -> bool) {
+        self.chains.retain(|tx_id, _| f(tx_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_replacement_price() {
+        assert_eq!(min_replacement_price(100, 10), 110);
+        assert_eq!(min_replacement_price(101, 10), 112);
+        assert_eq!(min_replacement_price(1, 10), 2);
+        assert_eq!(min_replacement_price(100, 0), 101);
+        assert_eq!(min_replacement_price(u64::MAX, 10), u64::MAX);
+    }
+
+    #[test]
+    fn test_replacement_chain() {
+        let mut tracker = ReplacementTracker::new(2);
+
+        assert_eq!(tracker.chain_len(&[]).unwrap(), 0);
+
+        let len = tracker.chain_len(&[[1; 32]]).unwrap();
+        assert_eq!(len, 1);
+        tracker.insert([2; 32], len);
+
+        let len = tracker.chain_len(&[[2; 32], [9; 32]]).unwrap();
+        assert_eq!(len, 2);
+        tracker.insert([3; 32], len);
+
+        assert!(matches!(
+            tracker.chain_len(&[[3; 32]]),
+            Err(ReplacementError::ChainTooLong(2))
+        ));
+
+        tracker.retain(|tx_id| tx_id != &[3; 32]);
+        assert_eq!(tracker.chain_len(&[[3; 32]]).unwrap(), 1);
+    }
+}
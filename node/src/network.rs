@@ -315,4 +315,8 @@ impl<const N: usize> crate::Network for Kadcast<N> {
         // TODO: This call should be replaced with no-copy Kadcast API
         self.peer.alive_nodes(u16::MAX as usize).await.len()
     }
+
+    async fn alive_nodes(&self, amount: usize) -> Vec<SocketAddr> {
+        self.peer.alive_nodes(amount).await
+    }
 }
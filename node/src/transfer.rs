@@ -0,0 +1,99 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use execution_core::signatures::bls::PublicKey as AccountPublicKey;
+use execution_core::transfer::withdraw::WithdrawReceiver;
+use execution_core::transfer::{
+    ConvertEvent, DepositEvent, MoonlightTransactionEvent,
+    TransferToAccountEvent, WithdrawEvent,
+};
+
+/// Direction of a movement of funds, from the account point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    In,
+    Out,
+}
+
+/// A movement of funds to or from a Moonlight account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoonlightTransfer {
+    pub account: AccountPublicKey,
+    pub direction: Direction,
+    pub value: u64,
+    /// Gas spent by the transaction, for the sender of a `moonlight` event
+    pub gas_spent: Option<u64>,
+}
+
+impl MoonlightTransfer {
+    fn new(
+        account: AccountPublicKey,
+        direction: Direction,
+        value: u64,
+    ) -> Self {
+        Self {
+            account,
+            direction,
+            value,
+            gas_spent: None,
+        }
+    }
+}
+
+/// Decodes the movements of funds to and from Moonlight accounts out of an
+/// event of the transfer contract.
+///
+/// Returns `None` if the event cannot be decoded, and no movement if its
+/// topic never moves Moonlight funds.
+pub fn moonlight_transfers(
+    topic: &str,
+    data: &[u8],
+) -> Option<Vec<MoonlightTransfer>> {
+    use Direction::{In, Out};
+
+    let moonlight_receiver = |receiver: &WithdrawReceiver, value| {
+        let WithdrawReceiver::Moonlight(receiver) = receiver else {
+            return None;
+        };
+        Some(MoonlightTransfer::new(*receiver, In, value))
+    };
+
+    let transfers = match topic {
+        "moonlight" => {
+            let e = rkyv::from_bytes::<MoonlightTransactionEvent>(data).ok()?;
+            let sender = MoonlightTransfer {
+                gas_spent: Some(e.gas_spent),
+                ..MoonlightTransfer::new(e.from, Out, e.value)
+            };
+            let receiver =
+                e.to.map(|to| MoonlightTransfer::new(to, In, e.value));
+            [Some(sender), receiver]
+        }
+        "transfer_to_account" => {
+            let e = rkyv::from_bytes::<TransferToAccountEvent>(data).ok()?;
+            [Some(MoonlightTransfer::new(e.receiver, In, e.value)), None]
+        }
+        "convert" => {
+            let e = rkyv::from_bytes::<ConvertEvent>(data).ok()?;
+            let sender =
+                e.sender.map(|s| MoonlightTransfer::new(s, Out, e.value));
+            [sender, moonlight_receiver(&e.receiver, e.value)]
+        }
+        "deposit" => {
+            let e = rkyv::from_bytes::<DepositEvent>(data).ok()?;
+            let sender =
+                e.sender.map(|s| MoonlightTransfer::new(s, Out, e.value));
+            [sender, None]
+        }
+        "withdraw" | "mint" => {
+            let e = rkyv::from_bytes::<WithdrawEvent>(data).ok()?;
+            [None, moonlight_receiver(&e.receiver, e.value)]
+        }
+        _ => [None, None],
+    };
+
+    Some(transfers.into_iter().flatten().collect())
+}
@@ -15,6 +15,19 @@ use node_data::ledger::{Block, SpentTransaction, Transaction};
 #[derive(Default)]
 pub struct Config {}
 
+/// Preverification failures proving that a transaction has not been created
+/// by the owner of the funds it spends.
+///
+/// Unlike the other preverification errors, they cannot be caused by a race
+/// with another transaction or block.
+#[derive(Debug, thiserror::Error)]
+pub enum PreverificationError {
+    #[error("Invalid proof")]
+    InvalidProof,
+    #[error("Invalid signature")]
+    InvalidSignature,
+}
+
 pub trait VMExecution: Send + Sync + 'static {
     fn execute_state_transition<I: Iterator<Item = Transaction>>(
         &self,
@@ -44,6 +57,11 @@ pub trait VMExecution: Send + Sync + 'static {
         to_delete: Vec<[u8; 32]>,
     ) -> anyhow::Result<()>;
 
+    /// Performs the stateless checks of a transaction and the ones against
+    /// the current state.
+    ///
+    /// Errors proving that the transaction is forged are
+    /// [`PreverificationError`]s.
     fn preverify(&self, tx: &Transaction) -> anyhow::Result<()>;
 
     fn get_provisioners(
@@ -61,6 +79,9 @@ pub trait VMExecution: Send + Sync + 'static {
         pk: &BlsPublicKey,
     ) -> anyhow::Result<Option<Stake>>;
 
+    /// Returns the last nonce used by a Moonlight account
+    fn get_account_nonce(&self, pk: &BlsPublicKey) -> anyhow::Result<u64>;
+
     fn get_state_root(&self) -> anyhow::Result<[u8; 32]>;
 
     fn move_to_commit(&self, commit: [u8; 32]) -> anyhow::Result<()>;
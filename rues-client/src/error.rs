@@ -0,0 +1,34 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+/// Errors returned by the client
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The node responded with an error
+    #[error("{status}: {message}")]
+    Rusk {
+        /// HTTP status code of the response
+        status: u16,
+        /// Error message sent by the node
+        message: String,
+    },
+    /// The request could not be performed
+    #[error("A request error occurred: {0}")]
+    Request(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The response is not the expected JSON
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// The response carries invalid hex encoded data
+    #[error(transparent)]
+    Hex(#[from] hex::FromHexError),
+}
+
+impl Error {
+    // Not a `From` implementation, to keep `reqwest` out of the public API
+    pub(crate) fn request(err: reqwest::Error) -> Self {
+        Self::Request(Box::new(err))
+    }
+}
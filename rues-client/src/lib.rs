@@ -0,0 +1,400 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Async client for the endpoints served by a Rusk node through the Rusk
+//! Universal Event System (RUES).
+//!
+//! Every endpoint is reached by dispatching an event to the
+//! `/on/{target}[:{entity}]/{topic}` location of the node. The full
+//! description of the endpoints is served by the node itself, at
+//! [`OPENAPI_LOCATION`].
+
+#![deny(missing_docs)]
+
+mod error;
+mod types;
+
+use futures_util::{Stream, TryStreamExt};
+use reqwest::{Body, Response};
+use serde::de::DeserializeOwned;
+
+pub use error::Error;
+pub use types::{
+    BatchQuery, ContractEvent, GasEstimate, GasPercentiles, GasPrice, NodeInfo,
+    Provisioner, Simulation,
+};
+
+/// Result of the requests to a Rusk node
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+/// Location of the OpenAPI description of the node endpoints
+pub const OPENAPI_LOCATION: &str = "/static/openapi.json";
+
+/// Target for contracts
+pub const CONTRACTS_TARGET: &str = "contracts";
+
+/// Header requesting the results of a feeder query to be streamed
+pub const RUSK_FEEDER_HEADER: &str = "Rusk-Feeder";
+
+/// Header requesting a query to be run at the state of a given block height
+pub const RUSK_HEIGHT_HEADER: &str = "Rusk-Height";
+
+const RUSK_VERSION_HEADER: &str = "Rusk-Version";
+
+const CONTENT_TYPE_BINARY: &str = "application/octet-stream";
+const CONTENT_TYPE_JSON: &str = "application/json";
+const CONTENT_TYPE_TEXT: &str = "text/plain";
+
+/// Async HTTP client of a Rusk node
+#[derive(Debug, Clone)]
+pub struct RuesClient {
+    uri: String,
+    version_req: Option<String>,
+    client: reqwest::Client,
+}
+
+impl RuesClient {
+    /// Creates a client of the node at the given URI.
+    pub fn new<S: Into<String>>(uri: S) -> Self {
+        let uri = uri.into().trim_end_matches('/').to_string();
+        Self {
+            uri,
+            version_req: None,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Requires the node version to match the given semver requirement,
+    /// failing every request otherwise.
+    pub fn with_version_req<S: Into<String>>(mut self, version_req: S) -> Self {
+        self.version_req = Some(version_req.into());
+        self
+    }
+
+    /// URI of the node
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Checks if the node is reachable.
+    pub async fn check_connection(&self) -> Result<()> {
+        self.client
+            .post(&self.uri)
+            .send()
+            .await
+            .map_err(Error::request)?;
+        Ok(())
+    }
+
+    /// Fetches the OpenAPI description of the node endpoints.
+    pub async fn openapi(&self) -> Result<serde_json::Value> {
+        let response = self
+            .client
+            .get(format!("{}{OPENAPI_LOCATION}", self.uri))
+            .send()
+            .await
+            .map_err(Error::request)?;
+        let bytes = body(check_status(response).await?).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Dispatches an event to the given location, returning the binary
+    /// response.
+    ///
+    /// The data is sent as binary, together with the given headers.
+    pub async fn call_with_headers(
+        &self,
+        target: &str,
+        entity: Option<&str>,
+        topic: &str,
+        data: &[u8],
+        headers: &[(&str, &str)],
+    ) -> Result<Vec<u8>> {
+        let response = self
+            .dispatch(
+                target,
+                entity,
+                topic,
+                data.to_vec(),
+                CONTENT_TYPE_BINARY,
+                headers,
+            )
+            .await?;
+        body(response).await
+    }
+
+    /// Dispatches an event to the given location, returning the binary
+    /// response.
+    pub async fn call(
+        &self,
+        target: &str,
+        entity: Option<&str>,
+        topic: &str,
+        data: &[u8],
+    ) -> Result<Vec<u8>> {
+        self.call_with_headers(target, entity, topic, data, &[])
+            .await
+    }
+
+    /// Queries a contract, given its hex encoded ID, with the serialized
+    /// argument.
+    pub async fn contract_query(
+        &self,
+        contract: &str,
+        fn_name: &str,
+        fn_args: &[u8],
+    ) -> Result<Vec<u8>> {
+        self.call(CONTRACTS_TARGET, Some(contract), fn_name, fn_args)
+            .await
+    }
+
+    /// Queries a contract at the state of the block at the given height.
+    pub async fn contract_query_at(
+        &self,
+        contract: &str,
+        fn_name: &str,
+        fn_args: &[u8],
+        height: u64,
+    ) -> Result<Vec<u8>> {
+        let height = height.to_string();
+        self.call_with_headers(
+            CONTRACTS_TARGET,
+            Some(contract),
+            fn_name,
+            fn_args,
+            &[(RUSK_HEIGHT_HEADER, &height)],
+        )
+        .await
+    }
+
+    /// Runs a feeder query on a contract, returning the stream of the
+    /// results.
+    ///
+    /// The results are streamed in chunks of bytes as they are received,
+    /// regardless of the boundaries between them.
+    pub async fn contract_feed(
+        &self,
+        contract: &str,
+        fn_name: &str,
+        fn_args: &[u8],
+    ) -> Result<impl Stream<Item = Result<Vec<u8>>>> {
+        let response = self
+            .dispatch(
+                CONTRACTS_TARGET,
+                Some(contract),
+                fn_name,
+                fn_args.to_vec(),
+                CONTENT_TYPE_BINARY,
+                &[(RUSK_FEEDER_HEADER, "1")],
+            )
+            .await?;
+        Ok(response
+            .bytes_stream()
+            .map_ok(|chunk| chunk.to_vec())
+            .map_err(Error::request))
+    }
+
+    /// Runs several contract queries against the same state, returning the
+    /// result or the error of each of them, in order.
+    pub async fn contract_batch(
+        &self,
+        queries: &[BatchQuery],
+    ) -> Result<Vec<Result<Vec<u8>, String>>> {
+        let body = serde_json::to_vec(queries)?;
+        let results: Vec<types::BatchResult> = self
+            .call_json(CONTRACTS_TARGET, None, "batch", body, CONTENT_TYPE_JSON)
+            .await?;
+
+        results
+            .into_iter()
+            .map(|result| match result {
+                types::BatchResult::Data(data) => Ok(Ok(hex::decode(data)?)),
+                types::BatchResult::Error(error) => Ok(Err(error)),
+            })
+            .collect()
+    }
+
+    /// Runs a GraphQL query on the ledger.
+    pub async fn graphql(&self, query: &str) -> Result<serde_json::Value> {
+        let body = query.as_bytes().to_vec();
+        self.call_json("graphql", None, "query", body, CONTENT_TYPE_TEXT)
+            .await
+    }
+
+    /// Propagates a serialized transaction to the network.
+    pub async fn propagate(&self, tx: &[u8]) -> Result<()> {
+        self.call("transactions", None, "propagate", tx).await?;
+        Ok(())
+    }
+
+    /// Checks the validity of a serialized transaction, without propagating
+    /// it.
+    pub async fn preverify(&self, tx: &[u8]) -> Result<()> {
+        self.call("transactions", None, "preverify", tx).await?;
+        Ok(())
+    }
+
+    /// Executes a serialized transaction on top of the tip of the chain,
+    /// without persisting nor propagating it.
+    pub async fn simulate(&self, tx: &[u8]) -> Result<Simulation> {
+        let body = tx.to_vec();
+        self.call_json(
+            "transactions",
+            None,
+            "simulate",
+            body,
+            CONTENT_TYPE_BINARY,
+        )
+        .await
+    }
+
+    /// Proves the execution of a serialized Phoenix transaction circuit.
+    pub async fn prove(&self, circuit: &[u8]) -> Result<Vec<u8>> {
+        self.call("prover", None, "prove", circuit).await
+    }
+
+    /// Lists the addresses of at most `amount` alive peers of the node.
+    pub async fn peers(&self, amount: usize) -> Result<Vec<String>> {
+        let body = amount.to_string().into_bytes();
+        self.call_json("network", None, "peers", body, CONTENT_TYPE_TEXT)
+            .await
+    }
+
+    /// Fetches the version and network configuration of the node.
+    pub async fn node_info(&self) -> Result<NodeInfo> {
+        self.call_json("node", None, "info", vec![], CONTENT_TYPE_TEXT)
+            .await
+    }
+
+    /// Lists the current provisioners.
+    pub async fn provisioners(&self) -> Result<Vec<Provisioner>> {
+        self.call_json("node", None, "provisioners", vec![], CONTENT_TYPE_TEXT)
+            .await
+    }
+
+    /// Fetches the serialized common reference string.
+    pub async fn crs(&self) -> Result<Vec<u8>> {
+        self.call("node", None, "crs", &[]).await
+    }
+
+    /// Fetches the statistics of the gas prices of the transactions in the
+    /// mempool, considering at most the given number of transactions by
+    /// descending gas price.
+    pub async fn gas_price(
+        &self,
+        max_transactions: Option<usize>,
+    ) -> Result<GasPrice> {
+        let body = max_transactions
+            .map(|max| max.to_string().into_bytes())
+            .unwrap_or_default();
+        self.call_json("blocks", None, "gas-price", body, CONTENT_TYPE_TEXT)
+            .await
+    }
+
+    /// Fetches the gas prices suggested from the recent blocks and the
+    /// mempool backlog.
+    pub async fn gas_estimate(&self) -> Result<GasEstimate> {
+        self.call_json(
+            "blocks",
+            None,
+            "gas-estimate",
+            vec![],
+            CONTENT_TYPE_TEXT,
+        )
+        .await
+    }
+
+    async fn call_json<T: DeserializeOwned>(
+        &self,
+        target: &str,
+        entity: Option<&str>,
+        topic: &str,
+        body: Vec<u8>,
+        content_type: &str,
+    ) -> Result<T> {
+        let response = self
+            .dispatch(target, entity, topic, body, content_type, &[])
+            .await?;
+        let bytes = self::body(response).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    async fn dispatch(
+        &self,
+        target: &str,
+        entity: Option<&str>,
+        topic: &str,
+        body: Vec<u8>,
+        content_type: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<Response> {
+        let mut request = self
+            .client
+            .post(location(&self.uri, target, entity, topic))
+            .body(Body::from(body))
+            .header("Content-Type", content_type);
+
+        if let Some(version_req) = &self.version_req {
+            request = request.header(RUSK_VERSION_HEADER, version_req);
+        }
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        check_status(request.send().await.map_err(Error::request)?).await
+    }
+}
+
+/// Location of the RUES endpoint at the node with the given URI.
+fn location(
+    uri: &str,
+    target: &str,
+    entity: Option<&str>,
+    topic: &str,
+) -> String {
+    let entity = entity.map(|e| format!(":{e}")).unwrap_or_default();
+    format!("{uri}/on/{target}{entity}/{topic}")
+}
+
+/// Turns the error responses of the node into [`Error::Rusk`].
+async fn check_status(response: Response) -> Result<Response> {
+    let status = response.status();
+    if status.is_client_error() || status.is_server_error() {
+        let message = body(response).await?;
+        let message =
+            String::from_utf8(message).unwrap_or("unparsable error".into());
+        let status = status.as_u16();
+        Err(Error::Rusk { status, message })
+    } else {
+        Ok(response)
+    }
+}
+
+/// Reads the whole body of a response.
+async fn body(response: Response) -> Result<Vec<u8>> {
+    let bytes = response.bytes().await.map_err(Error::request)?;
+    Ok(bytes.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_location() {
+        let client = RuesClient::new("http://localhost:8080/");
+        assert_eq!(client.uri(), "http://localhost:8080");
+
+        assert_eq!(
+            location(client.uri(), "node", None, "info"),
+            "http://localhost:8080/on/node/info"
+        );
+        assert_eq!(
+            location(client.uri(), CONTRACTS_TARGET, Some("0200"), "root"),
+            "http://localhost:8080/on/contracts:0200/root"
+        );
+    }
+}
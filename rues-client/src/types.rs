@@ -0,0 +1,145 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Version and network configuration of a node
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct NodeInfo {
+    /// Version of the node
+    pub version: String,
+    /// Build of the node version
+    pub version_build: String,
+    /// Addresses of the nodes used to bootstrap the network
+    pub bootstrapping_nodes: Vec<String>,
+    /// ID of the chain the node is part of
+    pub chain_id: u8,
+    /// Public address of the node in the network
+    pub kadcast_address: String,
+}
+
+/// A provisioner and its stake
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Provisioner {
+    /// Base58 encoded public key of the provisioner
+    pub key: String,
+    /// Amount staked
+    pub amount: u64,
+    /// Block height from which the stake is eligible
+    pub eligibility: u64,
+    /// Accumulated reward
+    pub reward: u64,
+}
+
+/// Statistics of the gas prices of the transactions in the mempool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct GasPrice {
+    /// Average gas price
+    pub average: u64,
+    /// Highest gas price
+    pub max: u64,
+    /// Median gas price
+    pub median: u64,
+    /// Lowest gas price
+    pub min: u64,
+}
+
+/// Gas prices suggested from the recent blocks and the mempool backlog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct GasEstimate {
+    /// Gas price to be included in the next block
+    pub next_block: u64,
+    /// Gas price to be included within the next few blocks
+    pub within_few_blocks: u64,
+    /// Percentiles of the gas prices paid in recent blocks
+    pub percentiles: GasPercentiles,
+    /// Average utilisation of the block gas limit in recent blocks, in
+    /// percent
+    pub utilisation: u64,
+}
+
+/// Percentiles of the gas prices paid in recent blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[allow(missing_docs)]
+pub struct GasPercentiles {
+    pub p10: u64,
+    pub p25: u64,
+    pub p50: u64,
+    pub p75: u64,
+    pub p90: u64,
+}
+
+/// Outcome of the simulated execution of a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Simulation {
+    /// Gas spent by the transaction
+    pub gas_spent: u64,
+    /// Gas limit of the transaction
+    pub gas_limit: u64,
+    /// Return value of the contract call, if it succeeded
+    #[serde(deserialize_with = "hex_option")]
+    pub data: Option<Vec<u8>>,
+    /// Error of the contract call, if it failed
+    pub error: Option<String>,
+    /// Events emitted during the execution
+    pub events: Vec<ContractEvent>,
+}
+
+/// Event emitted by a contract
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ContractEvent {
+    /// Hex encoded ID of the contract emitting the event
+    pub target: String,
+    /// Topic of the event
+    pub topic: String,
+    /// Data of the event
+    #[serde(with = "hex")]
+    pub data: Vec<u8>,
+}
+
+/// A contract query of a batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BatchQuery {
+    /// Hex encoded ID of the contract
+    pub contract: String,
+    /// Name of the queried function
+    pub fn_name: String,
+    /// Serialized argument of the query
+    #[serde(with = "hex")]
+    pub fn_args: Vec<u8>,
+}
+
+impl BatchQuery {
+    /// Creates a query of a contract, given its hex encoded ID.
+    pub fn new<C, F>(contract: C, fn_name: F, fn_args: Vec<u8>) -> Self
+    where
+        C: Into<String>,
+        F: Into<String>,
+    {
+        Self {
+            contract: contract.into(),
+            fn_name: fn_name.into(),
+            fn_args,
+        }
+    }
+}
+
+/// Either the hex encoded result of a query of a batch, or its error
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum BatchResult {
+    Data(String),
+    Error(String),
+}
+
+fn hex_option<'de, D>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|data| hex::decode(data).map_err(serde::de::Error::custom))
+        .transpose()
+}
@@ -17,7 +17,7 @@ use crate::{WalletFile, WalletPath};
 use execution_core::{stake::StakeData, BlsScalar};
 use rusk_wallet::{
     currency::{Dusk, Lux},
-    gas::{Gas, DEFAULT_LIMIT, DEFAULT_PRICE},
+    gas::{Gas, DEFAULT_LIMIT},
     Address, Error, Wallet, EPOCH, MAX_ADDRESSES,
 };
 use wallet_core::BalanceInfo;
@@ -100,9 +100,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Stake DUSK through Phoenix
@@ -119,9 +120,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_STAKE_GAS_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Unstake using Phoenix
@@ -135,9 +137,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_STAKE_GAS_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Withdraw accumulated rewards for a stake key using Phoenix
@@ -151,9 +154,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_STAKE_GAS_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Deploy a contract using Phoenix
@@ -174,9 +178,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_STAKE_GAS_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Call a contract using Phoenix
@@ -201,9 +206,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_STAKE_GAS_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Check your stake information
@@ -236,9 +242,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Stake DUSK using Moonlight
@@ -255,9 +262,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_STAKE_GAS_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Unstake using Moonlight
@@ -271,9 +279,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_STAKE_GAS_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Withdraw accumulated rewards for a stake key using Moonlight
@@ -291,9 +300,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_STAKE_GAS_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Deploy a contract using Moonlight
@@ -315,9 +325,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Call a contract using Moonlight
@@ -342,9 +353,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     // Conversion commands
@@ -362,9 +374,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_STAKE_GAS_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Convert Moonlight DUSK to Phoenix for the same owned address
@@ -381,9 +394,10 @@ pub(crate) enum Command {
         #[clap(short = 'l', long, default_value_t= DEFAULT_STAKE_GAS_LIMIT)]
         gas_limit: u64,
 
-        /// Price you're going to pay for each gas unit (in LUX)
-        #[clap(short = 'p', long, default_value_t= DEFAULT_PRICE)]
-        gas_price: Lux,
+        /// Price you're going to pay for each gas unit (in LUX) [default:
+        /// estimated by the node]
+        #[clap(short = 'p', long)]
+        gas_price: Option<Lux>,
     },
 
     /// Export BLS provisioner key-pair
@@ -469,6 +483,7 @@ impl Command {
                     Some(addr) => wallet.claim_as_address(addr)?,
                     None => wallet.default_address(),
                 };
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 let tx =
@@ -482,6 +497,7 @@ impl Command {
                 gas_limit,
                 gas_price,
             } => {
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
                 let sender = match sndr {
                     Some(addr) => wallet.claim_as_address(addr)?,
@@ -500,6 +516,7 @@ impl Command {
                 gas_price,
             } => {
                 wallet.sync().await?;
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
                 let addr = match addr {
                     Some(addr) => wallet.claim_as_address(addr)?,
@@ -532,6 +549,7 @@ impl Command {
                     None => wallet.default_address(),
                 };
 
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 let tx = wallet.phoenix_unstake(addr, gas).await?;
@@ -548,6 +566,7 @@ impl Command {
                     None => wallet.default_address(),
                 };
 
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 let tx = wallet.phoenix_stake_withdraw(addr, gas).await?;
@@ -595,6 +614,7 @@ impl Command {
                     None => wallet.default_address(),
                 };
 
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 let tx = wallet.phoenix_to_moonlight(addr, amt, gas).await?;
@@ -612,6 +632,7 @@ impl Command {
                     None => wallet.default_address(),
                 };
 
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 let tx = wallet.moonlight_to_phoenix(addr, amt, gas).await?;
@@ -628,6 +649,7 @@ impl Command {
                     None => wallet.default_address(),
                 };
 
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 let tx = wallet.moonlight_stake(addr, amt, gas).await?;
@@ -643,6 +665,7 @@ impl Command {
                     None => wallet.default_address(),
                 };
 
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 let tx = wallet.moonlight_unstake(addr, gas).await?;
@@ -659,6 +682,7 @@ impl Command {
                     None => wallet.default_address(),
                 };
 
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 let tx =
@@ -679,6 +703,7 @@ impl Command {
                     None => wallet.default_address(),
                 };
 
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 let contract_id: [u8; 32] = contract_id
@@ -707,6 +732,7 @@ impl Command {
                     None => wallet.default_address(),
                 };
 
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 let contract_id: [u8; 32] = contract_id
@@ -741,6 +767,7 @@ impl Command {
                     None => wallet.default_address(),
                 };
 
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 if code.extension().unwrap_or_default() != "wasm" {
@@ -767,6 +794,7 @@ impl Command {
                     None => wallet.default_address(),
                 };
 
+                let gas_price = wallet.gas_price(gas_price).await?;
                 let gas = Gas::new(gas_limit).with_price(gas_price);
 
                 if code.extension().unwrap_or_default() != "wasm" {
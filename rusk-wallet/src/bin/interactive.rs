@@ -7,7 +7,7 @@
 use bip39::{Language, Mnemonic, MnemonicType};
 use requestty::Question;
 use rusk_wallet::{
-    currency::Dusk,
+    currency::{Dusk, Lux},
     dat::{DatFileVersion, LATEST_VERSION},
     gas, Address, Error, Wallet, WalletPath, MAX_ADDRESSES,
 };
@@ -99,7 +99,14 @@ pub(crate) async fn run_loop(
             // request operation to perform
             let op = match wallet.is_online().await {
                 true => {
-                    menu_op(addr.clone(), spendable, moonlight_bal, settings)
+                    let gas_price = wallet.gas_price(None).await?;
+                    menu_op(
+                        addr.clone(),
+                        spendable,
+                        moonlight_bal,
+                        gas_price,
+                        settings,
+                    )
                 }
                 false => menu_op_offline(addr.clone(), settings),
             };
@@ -240,6 +247,7 @@ fn menu_op(
     addr: Address,
     phoenix_balance: Dusk,
     moonlight_balance: Dusk,
+    gas_price: Lux,
     settings: &Settings,
 ) -> anyhow::Result<AddrOp> {
     use CommandMenuItem as CMI;
@@ -284,7 +292,7 @@ fn menu_op(
                 rcvr: prompt::request_rcvr_addr("recipient")?,
                 amt: prompt::request_token_amt("transfer", phoenix_balance)?,
                 gas_limit: prompt::request_gas_limit(gas::DEFAULT_LIMIT)?,
-                gas_price: prompt::request_gas_price()?,
+                gas_price: Some(prompt::request_gas_price(gas_price)?),
             }))
         }
         CMI::MoonlightTransfer => {
@@ -293,7 +301,7 @@ fn menu_op(
                 rcvr: prompt::request_rcvr_addr("recipient")?,
                 amt: prompt::request_token_amt("transfer", moonlight_balance)?,
                 gas_limit: prompt::request_gas_limit(gas::DEFAULT_LIMIT)?,
-                gas_price: prompt::request_gas_price()?,
+                gas_price: Some(prompt::request_gas_price(gas_price)?),
             }))
         }
         CMI::PhoenixContractDeploy => {
@@ -302,26 +310,26 @@ fn menu_op(
                 code: prompt::request_contract_code()?,
                 init_args: prompt::request_bytes("init arguments")?,
                 gas_limit: prompt::request_gas_limit(gas::DEFAULT_LIMIT)?,
-                gas_price: prompt::request_gas_price()?,
+                gas_price: Some(prompt::request_gas_price(gas_price)?),
             }))
         }
         CMI::PhoenixStake => AddrOp::Run(Box::new(Command::PhoenixStake {
             addr: Some(addr),
             amt: prompt::request_token_amt("stake", phoenix_balance)?,
             gas_limit: prompt::request_gas_limit(DEFAULT_STAKE_GAS_LIMIT)?,
-            gas_price: prompt::request_gas_price()?,
+            gas_price: Some(prompt::request_gas_price(gas_price)?),
         })),
         CMI::MoonlightStake => AddrOp::Run(Box::new(Command::MoonlightStake {
             addr: Some(addr),
             amt: prompt::request_token_amt("stake", moonlight_balance)?,
             gas_limit: prompt::request_gas_limit(DEFAULT_STAKE_GAS_LIMIT)?,
-            gas_price: prompt::request_gas_price()?,
+            gas_price: Some(prompt::request_gas_price(gas_price)?),
         })),
         CMI::MoonlightUnstake => {
             AddrOp::Run(Box::new(Command::MoonlightUnstake {
                 addr: Some(addr),
                 gas_limit: prompt::request_gas_limit(DEFAULT_STAKE_GAS_LIMIT)?,
-                gas_price: prompt::request_gas_price()?,
+                gas_price: Some(prompt::request_gas_price(gas_price)?),
             }))
         }
         CMI::MoonlightContractDeploy => {
@@ -330,7 +338,7 @@ fn menu_op(
                 code: prompt::request_contract_code()?,
                 init_args: prompt::request_bytes("init arguments")?,
                 gas_limit: prompt::request_gas_limit(gas::DEFAULT_LIMIT)?,
-                gas_price: prompt::request_gas_price()?,
+                gas_price: Some(prompt::request_gas_price(gas_price)?),
             }))
         }
         CMI::MoonlightWithdraw => {
@@ -338,7 +346,7 @@ fn menu_op(
                 addr: Some(addr),
                 amt: prompt::request_token_amt("withdraw", moonlight_balance)?,
                 gas_limit: prompt::request_gas_limit(DEFAULT_STAKE_GAS_LIMIT)?,
-                gas_price: prompt::request_gas_price()?,
+                gas_price: Some(prompt::request_gas_price(gas_price)?),
             }))
         }
         CMI::PhoenixContractCall => {
@@ -350,7 +358,7 @@ fn menu_op(
                     "arguments of calling function",
                 )?,
                 gas_limit: prompt::request_gas_limit(gas::DEFAULT_LIMIT)?,
-                gas_price: prompt::request_gas_price()?,
+                gas_price: Some(prompt::request_gas_price(gas_price)?),
             }))
         }
         CMI::MoonlightContractCall => {
@@ -362,7 +370,7 @@ fn menu_op(
                     "arguments of calling function",
                 )?,
                 gas_limit: prompt::request_gas_limit(gas::DEFAULT_LIMIT)?,
-                gas_price: prompt::request_gas_price()?,
+                gas_price: Some(prompt::request_gas_price(gas_price)?),
             }))
         }
         CMI::StakeInfo => AddrOp::Run(Box::new(Command::StakeInfo {
@@ -372,13 +380,13 @@ fn menu_op(
         CMI::PhoenixUnstake => AddrOp::Run(Box::new(Command::PhoenixUnstake {
             addr: Some(addr),
             gas_limit: prompt::request_gas_limit(DEFAULT_STAKE_GAS_LIMIT)?,
-            gas_price: prompt::request_gas_price()?,
+            gas_price: Some(prompt::request_gas_price(gas_price)?),
         })),
         CMI::PhoenixWithdraw => {
             AddrOp::Run(Box::new(Command::PhoenixWithdraw {
                 addr: Some(addr),
                 gas_limit: prompt::request_gas_limit(DEFAULT_STAKE_GAS_LIMIT)?,
-                gas_price: prompt::request_gas_price()?,
+                gas_price: Some(prompt::request_gas_price(gas_price)?),
             }))
         }
         CMI::MoonlightToPhoenix => {
@@ -386,7 +394,7 @@ fn menu_op(
                 addr: Some(addr),
                 amt: prompt::request_token_amt("convert", moonlight_balance)?,
                 gas_limit: prompt::request_gas_limit(gas::DEFAULT_LIMIT)?,
-                gas_price: prompt::request_gas_price()?,
+                gas_price: Some(prompt::request_gas_price(gas_price)?),
             }))
         }
         CMI::PhoenixToMoonlight => {
@@ -394,7 +402,7 @@ fn menu_op(
                 addr: Some(addr),
                 amt: prompt::request_token_amt("convert", phoenix_balance)?,
                 gas_limit: prompt::request_gas_limit(gas::DEFAULT_LIMIT)?,
-                gas_price: prompt::request_gas_price()?,
+                gas_price: Some(prompt::request_gas_price(gas_price)?),
             }))
         }
         CMI::Export => AddrOp::Run(Box::new(Command::Export {
@@ -572,7 +580,7 @@ fn confirm(cmd: &Command) -> anyhow::Result<bool> {
             gas_price,
         } => {
             let sndr = sndr.as_ref().expect("sender to be a valid address");
-            let max_fee = gas_limit * gas_price;
+            let max_fee = gas_limit * gas_price.expect("gas price to be set");
             println!("   > Send from = {}", sndr.preview());
             println!("   > Recipient = {}", rcvr.preview());
             println!("   > Amount to transfer = {} DUSK", amt);
@@ -587,7 +595,7 @@ fn confirm(cmd: &Command) -> anyhow::Result<bool> {
             gas_price,
         } => {
             let sndr = sndr.as_ref().expect("sender to be a valid address");
-            let max_fee = gas_limit * gas_price;
+            let max_fee = gas_limit * gas_price.expect("gas price to be set");
             println!("   > Send from = {}", sndr.preview());
             println!("   > Recipient = {}", rcvr.preview());
             println!("   > Amount to transfer = {} DUSK", amt);
@@ -602,7 +610,7 @@ fn confirm(cmd: &Command) -> anyhow::Result<bool> {
             gas_price,
         } => {
             let addr = addr.as_ref().expect("address to be valid");
-            let max_fee = gas_limit * gas_price;
+            let max_fee = gas_limit * gas_price.expect("gas price to be set");
             println!("   > Stake from {}", addr.preview());
             println!("   > Amount to stake = {} DUSK", amt);
             println!("   > Max fee = {} DUSK", Dusk::from(max_fee));
@@ -614,7 +622,7 @@ fn confirm(cmd: &Command) -> anyhow::Result<bool> {
             gas_price,
         } => {
             let addr = addr.as_ref().expect("address to be valid");
-            let max_fee = gas_limit * gas_price;
+            let max_fee = gas_limit * gas_price.expect("gas price to be set");
             println!("   > Unstake from {}", addr.preview());
             println!("   > Max fee = {} DUSK", Dusk::from(max_fee));
             prompt::ask_confirm()
@@ -625,7 +633,7 @@ fn confirm(cmd: &Command) -> anyhow::Result<bool> {
             gas_price,
         } => {
             let addr = addr.as_ref().expect("address to be valid");
-            let max_fee = gas_limit * gas_price;
+            let max_fee = gas_limit * gas_price.expect("gas price to be set");
             println!("   > Reward from {}", addr.preview());
             println!("   > Max fee = {} DUSK", Dusk::from(max_fee));
             prompt::ask_confirm()
@@ -39,6 +39,7 @@ use self::sync::sync_db;
 use super::{cache::Cache, *};
 
 use crate::{
+    currency::Lux,
     rusk::{RuskHttpClient, RuskRequest},
     store::LocalStore,
     Error, MAX_ADDRESSES,
@@ -328,6 +329,18 @@ impl State {
         Ok(res)
     }
 
+    /// Queries the node for the gas price suggested to be included in the
+    /// next block.
+    pub(crate) async fn fetch_gas_price(&self) -> Result<Lux, Error> {
+        let status = self.status;
+        status("Fetching gas price estimate...");
+
+        let estimate = self.client.client().gas_estimate().await?;
+        status("Gas price estimate received!");
+
+        Ok(estimate.next_block)
+    }
+
     /// Queries the node to find the opening for a specific note.
     async fn fetch_opening(&self, note: &Note) -> Result<NoteOpening, Error> {
         let status = self.status;
@@ -4,8 +4,9 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::pin::pin;
+
 use futures::StreamExt;
-use rues::CONTRACTS_TARGET;
 
 use crate::clients::{Cache, TRANSFER_CONTRACT};
 use crate::Error;
@@ -44,22 +45,17 @@ pub(crate) async fn sync_db(
         .map_err(|_| Error::Rkyv)?
         .to_vec();
 
-    let mut stream = client
-        .call_raw(
-            CONTRACTS_TARGET,
-            TRANSFER_CONTRACT,
-            "leaves_from_pos",
-            &req,
-            true,
-        )
-        .await?
-        .bytes_stream();
+    let stream = client
+        .client()
+        .contract_feed(TRANSFER_CONTRACT, "leaves_from_pos", &req)
+        .await?;
+    let mut stream = pin!(stream);
 
     status("Connection established...");
 
     status("Streaming notes...");
 
-    // This buffer is needed because `.bytes_stream();` introduce additional
+    // This buffer is needed because the stream introduces additional
     // spliting of chunks according to it's own buffer
     let mut buffer = vec![];
     let mut note_data = Vec::new();
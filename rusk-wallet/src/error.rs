@@ -139,6 +139,15 @@ impl From<dusk_bytes::Error> for Error {
     }
 }
 
+impl From<rues_client::Error> for Error {
+    fn from(e: rues_client::Error) -> Self {
+        match e {
+            rues_client::Error::Json(e) => Self::Json(e),
+            e => Self::Rusk(e.to_string()),
+        }
+    }
+}
+
 impl From<block_modes::InvalidKeyIvLength> for Error {
     fn from(_: block_modes::InvalidKeyIvLength) -> Self {
         Self::WalletFileCorrupted
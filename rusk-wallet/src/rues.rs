@@ -4,27 +4,33 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use reqwest::{Body, Response};
 use rkyv::Archive;
+use rues_client::RuesClient;
 
 use crate::Error;
 
+pub use rues_client::CONTRACTS_TARGET;
+
 /// Supported Rusk version
 const REQUIRED_RUSK_VERSION: &str = ">=0.8.0";
 
-/// Target for contracts
-pub const CONTRACTS_TARGET: &str = "contracts";
-
 #[derive(Clone)]
 /// Rusk HTTP Binary Client
 pub struct RuesHttpClient {
-    uri: String,
+    client: RuesClient,
 }
 
 impl RuesHttpClient {
     /// Create a new HTTP Client
     pub fn new(uri: String) -> Self {
-        Self { uri }
+        let client =
+            RuesClient::new(uri).with_version_req(REQUIRED_RUSK_VERSION);
+        Self { client }
+    }
+
+    /// Typed client of the node endpoints
+    pub fn client(&self) -> &RuesClient {
+        &self.client
     }
 
     /// Utility for querying the rusk VM
@@ -41,17 +47,12 @@ impl RuesHttpClient {
     {
         let data = rkyv::to_bytes(value).map_err(|_| Error::Rkyv)?.to_vec();
 
-        let response = self
-            .call_raw(CONTRACTS_TARGET, contract.into(), method, &data, false)
-            .await?;
-
-        Ok(response.bytes().await?.to_vec())
+        self.call(CONTRACTS_TARGET, contract, method, &data).await
     }
 
     /// Check rusk connection
-    pub async fn check_connection(&self) -> Result<(), reqwest::Error> {
-        reqwest::Client::new().post(&self.uri).send().await?;
-        Ok(())
+    pub async fn check_connection(&self) -> Result<(), Error> {
+        Ok(self.client.check_connection().await?)
     }
 
     /// Send a RuskRequest to a specific target.
@@ -67,52 +68,10 @@ impl RuesHttpClient {
     where
         E: Into<Option<&'static str>>,
     {
-        let response =
-            self.call_raw(target, entity, topic, request, false).await?;
-        let data = response.bytes().await?;
-        Ok(data.to_vec())
-    }
-
-    /// Send a RuskRequest to a specific target without parsing the response
-    pub async fn call_raw<E>(
-        &self,
-        target: &str,
-        entity: E,
-        topic: &str,
-        data: &[u8],
-        feed: bool,
-    ) -> Result<Response, Error>
-    where
-        E: Into<Option<&'static str>>,
-    {
-        let uri = &self.uri;
-        let client = reqwest::Client::new();
-        let entity = entity.into().map(|e| format!(":{e}")).unwrap_or_default();
-
-        let rues_prefix = if uri.ends_with('/') { "on" } else { "/on" };
-        let mut request = client
-            .post(format!("{uri}{rues_prefix}/{target}{entity}/{topic}"))
-            .body(Body::from(data.to_vec()))
-            .header("Content-Type", "application/octet-stream")
-            .header("rusk-version", REQUIRED_RUSK_VERSION);
-
-        if feed {
-            request = request.header("Rusk-Feeder", "1");
-        }
-        let response = request.send().await?;
-
-        let status = response.status();
-        if status.is_client_error() || status.is_server_error() {
-            let error = &response.bytes().await?;
-
-            let error = String::from_utf8(error.to_vec())
-                .unwrap_or("unparsable error".into());
-
-            let msg = format!("{status}: {error}");
-
-            Err(Error::Rusk(msg))
-        } else {
-            Ok(response)
-        }
+        let data = self
+            .client
+            .call(target, entity.into(), topic, request)
+            .await?;
+        Ok(data)
     }
 }
@@ -54,7 +54,7 @@ use super::*;
 use crate::{
     clients::{Prover, State},
     crypto::encrypt,
-    currency::Dusk,
+    currency::{Dusk, Lux},
     dat::{
         self, version_bytes, DatFileVersion, FILE_TYPE, LATEST_VERSION, MAGIC,
         RESERVED,
@@ -322,6 +322,15 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
         self.state.is_some()
     }
 
+    /// Returns the given gas price, or the one suggested by the node to be
+    /// included in the next block if none is given
+    pub async fn gas_price(&self, price: Option<Lux>) -> Result<Lux, Error> {
+        match price {
+            Some(price) => Ok(price),
+            None => self.state()?.fetch_gas_price().await,
+        }
+    }
+
     /// Fetches the notes from the state.
     pub async fn get_all_notes(
         &self,
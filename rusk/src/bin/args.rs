@@ -6,6 +6,19 @@
 
 #[cfg(any(feature = "recovery-state", feature = "recovery-keys"))]
 mod command;
+#[cfg(all(feature = "archive", feature = "recovery-state"))]
+mod reindex;
+#[cfg(all(
+    feature = "chain",
+    unix,
+    any(feature = "recovery-state", feature = "recovery-keys")
+))]
+mod signer;
+#[cfg(all(
+    feature = "chain",
+    any(feature = "recovery-state", feature = "recovery-keys")
+))]
+mod signing_history;
 #[cfg(feature = "recovery-state")]
 mod state;
 
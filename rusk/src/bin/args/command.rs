@@ -10,6 +10,8 @@ use rusk_recovery_tools::Theme;
 use std::io;
 use tracing::info;
 
+use crate::config::Config;
+
 #[allow(clippy::large_enum_variant)]
 #[derive(PartialEq, Eq, Hash, Clone, Subcommand, Debug)]
 pub enum Command {
@@ -43,6 +45,54 @@ pub enum Command {
         #[clap(short, long, value_parser, num_args(1))]
         output: Option<super::PathBuf>,
     },
+
+    /// Rebuilds the archive replaying the finalized blocks of the ledger,
+    /// resuming from the last reindexed block, if any.
+    #[cfg(all(feature = "archive", feature = "recovery-state"))]
+    ReindexArchive {
+        /// Directory of the VM state used to replay the blocks. The genesis
+        /// state is built in it if it does not exist.
+        #[clap(short, long, value_parser)]
+        state: super::PathBuf,
+
+        /// Build the genesis state applying the init config specified in
+        /// this file.
+        #[clap(short, long, value_parser, env = "RUSK_RECOVERY_INPUT")]
+        init: Option<super::PathBuf>,
+
+        /// Height of the last block to replay. Defaults to the last finalized
+        /// block.
+        #[clap(long)]
+        to: Option<u64>,
+    },
+
+    /// Exports the signing history of the consensus keys, so that it can be
+    /// imported on the machine the node is migrated to.
+    #[cfg(feature = "chain")]
+    ExportSigningHistory {
+        /// File the history is written to, as JSON
+        #[clap(short, long, value_parser)]
+        output: super::PathBuf,
+    },
+
+    /// Imports a signing history exported from another machine. The node
+    /// must not be running.
+    #[cfg(feature = "chain")]
+    ImportSigningHistory {
+        /// File the history is read from
+        #[clap(short, long, value_parser)]
+        input: super::PathBuf,
+    },
+
+    /// Runs a signer process holding the consensus keys, for a node started
+    /// with DUSK_CONSENSUS_SIGNER_SOCKET. It refuses to sign messages
+    /// conflicting with its signing history.
+    #[cfg(all(feature = "chain", unix))]
+    ConsensusSigner {
+        /// Unix socket the signer listens on
+        #[clap(short, long, value_parser)]
+        socket: super::PathBuf,
+    },
 }
 
 impl Command {
@@ -59,7 +109,11 @@ impl Command {
         Ok(())
     }
 
-    pub fn run(self) -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg_attr(not(feature = "chain"), allow(unused_variables))]
+    pub fn run(
+        self,
+        config: &Config,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let theme = Theme::default();
 
         Self::display_env(&theme)?;
@@ -75,6 +129,22 @@ impl Command {
             Self::RecoveryKeys { keep, crs_url } => {
                 rusk_recovery_tools::keys::exec(keep, crs_url)
             }
+            #[cfg(all(feature = "archive", feature = "recovery-state"))]
+            Self::ReindexArchive { state, init, to } => {
+                super::reindex::reindex_archive(config, state, init, to)
+            }
+            #[cfg(feature = "chain")]
+            Self::ExportSigningHistory { output } => {
+                super::signing_history::export(config, output)
+            }
+            #[cfg(feature = "chain")]
+            Self::ImportSigningHistory { input } => {
+                super::signing_history::import(config, input)
+            }
+            #[cfg(all(feature = "chain", unix))]
+            Self::ConsensusSigner { socket } => {
+                super::signer::serve(config, socket)
+            }
         };
 
         if let Err(e) = &result {
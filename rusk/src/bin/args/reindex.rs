@@ -0,0 +1,107 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::*;
+
+use std::fs;
+use std::sync::Arc;
+
+use node::archivist;
+use node::database::archive::SQLiteArchive;
+use node::database::{rocksdb, DB};
+use rusk::Rusk;
+use rusk_recovery_tools::state::deploy;
+use rusk_recovery_tools::Theme;
+use tokio::runtime::Handle;
+use tokio::sync::{broadcast, mpsc, RwLock};
+use tracing::info;
+
+use crate::config::Config;
+
+/// Blocks are replayed one at a time, so the channel only needs to hold the
+/// archival data of a single block
+const ARCHIVE_CHANNEL_CAP: usize = 16;
+
+/// Rebuilds the archive replaying the finalized blocks of the ledger in a VM
+/// whose state is kept in `state_dir`.
+///
+/// The node must not be running, since the ledger is opened for the whole
+/// duration of the reindex.
+pub fn reindex_archive(
+    config: &Config,
+    state_dir: PathBuf,
+    init: Option<PathBuf>,
+    to: Option<u64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let theme = Theme::default();
+
+    // The replay starts from the genesis state, unless a previous reindex
+    // left its state in the directory
+    if !rusk_profile::to_rusk_state_id_path(&state_dir).exists() {
+        let snapshot = match &init {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|_| format!("file {path:?} not found"))?,
+            None => rusk_recovery_tools::state::DEFAULT_SNAPSHOT.into(),
+        };
+        let snapshot = toml::from_str(&snapshot)?;
+
+        info!("{} genesis state", theme.info("Building"));
+        let (_, commit_id) = deploy(&state_dir, &snapshot, |_| {})?;
+        info!(
+            "{} {}",
+            theme.action("Genesis Root"),
+            hex::encode(commit_id)
+        );
+    }
+
+    let chain_id = kadcast::config::Config::from(config.kadcast.clone())
+        .kadcast_id
+        .unwrap_or_default();
+    let (event_sender, _) = broadcast::channel(1);
+    let (archive_sender, mut archive_receiver) =
+        mpsc::channel(ARCHIVE_CHANNEL_CAP);
+
+    let consensus_params = config.chain.consensus_params()?;
+    let vm = Rusk::new(
+        &state_dir,
+        chain_id,
+        config.chain.generation_timeout(),
+        config.chain.gas_per_deploy_byte(),
+        config.chain.min_deployment_gas_price(),
+        config.chain.block_gas_limit(),
+        config.http.feeder_call_gas,
+        consensus_params,
+        event_sender,
+        archive_sender,
+    )?;
+
+    let db_path = config.chain.db_path();
+    let db_options = config.chain.db_options()?;
+
+    let height = tokio::task::block_in_place(|| {
+        Handle::current().block_on(async {
+            let db = rocksdb::Backend::create_or_open(&db_path, db_options);
+            let archive = SQLiteArchive::create_or_open(&db_path).await;
+
+            archivist::reindex(
+                Arc::new(RwLock::new(db)),
+                &vm,
+                &archive,
+                &mut archive_receiver,
+                to,
+                &consensus_params,
+            )
+            .await
+        })
+    })?;
+
+    info!(
+        "{} archive up to height {height}",
+        theme.success("Reindexed")
+    );
+
+    Ok(())
+}
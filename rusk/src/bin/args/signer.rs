@@ -0,0 +1,70 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::*;
+
+use std::os::unix::net::UnixListener;
+use std::sync::Arc;
+use std::{env, thread};
+
+use dusk_consensus::signer::{serve_connection, InMemorySigner};
+use dusk_consensus::slashing_protection::SlashingProtection;
+use node::database::signing_history::SigningHistoryDB;
+use rusk_recovery_tools::Theme;
+use tracing::{info, warn};
+
+use crate::config::Config;
+
+/// Runs the signer process of the configured consensus keys, serving the
+/// node connecting to the given Unix socket.
+///
+/// The keys are decrypted with the password from env var
+/// DUSK_CONSENSUS_KEYS_PASS. Their signing history is kept next to them, so
+/// the node does not need access to either.
+pub fn serve(
+    config: &Config,
+    socket: PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let theme = Theme::default();
+    if socket.exists() {
+        Err("Socket already exists")?
+    }
+
+    let keys_path = config.chain.consensus_keys_path();
+    let pwd = env::var("DUSK_CONSENSUS_KEYS_PASS")
+        .map_err(|_| "DUSK_CONSENSUS_KEYS_PASS not set")?;
+    let history =
+        SigningHistoryDB::open(SigningHistoryDB::default_path(&keys_path))?;
+    let (secret_key, public_key) = node_data::bls::load_keys(keys_path, pwd)?;
+
+    let signer = Arc::new(InMemorySigner::new(secret_key));
+    let protection = SlashingProtection::new(
+        Arc::new(history),
+        config.chain.consensus_params()?,
+    );
+
+    let listener = UnixListener::bind(&socket)?;
+    info!(
+        "{} {} on {}",
+        theme.success("Signing"),
+        public_key.to_bs58(),
+        socket.display()
+    );
+
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        let signer = signer.clone();
+        let protection = protection.clone();
+        thread::spawn(move || {
+            if let Err(e) = serve_connection(&mut stream, &signer, &protection)
+            {
+                let theme = Theme::default();
+                warn!("{} {e}", theme.warn("Connection closed"));
+            }
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::*;
+
+use std::fs;
+
+use node::database::signing_history::{SigningHistoryDB, SigningHistoryExport};
+use rusk_recovery_tools::Theme;
+use tracing::info;
+
+use crate::config::Config;
+
+fn open_history(
+    config: &Config,
+) -> Result<SigningHistoryDB, Box<dyn std::error::Error>> {
+    let keys_path = config.chain.consensus_keys_path();
+    let path = SigningHistoryDB::default_path(keys_path);
+    Ok(SigningHistoryDB::open(path)?)
+}
+
+/// Exports the signing history of the configured consensus keys to the
+/// given file.
+pub fn export(
+    config: &Config,
+    output: PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let theme = Theme::default();
+    if output.exists() {
+        Err("Output already exists")?
+    }
+
+    let history = open_history(config)?.export()?;
+    fs::write(&output, serde_json::to_vec_pretty(&history)?)?;
+
+    info!(
+        "{} {} records to {}",
+        theme.success("Exported"),
+        history.records.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Imports the signing history in the given file into the one of the
+/// configured consensus keys.
+pub fn import(
+    config: &Config,
+    input: PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let theme = Theme::default();
+
+    let history: SigningHistoryExport =
+        serde_json::from_slice(&fs::read(&input)?)?;
+    let imported = open_history(config)?.import(&history)?;
+
+    info!("{} {imported} records", theme.success("Imported"));
+    Ok(())
+}
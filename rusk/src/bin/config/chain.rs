@@ -9,6 +9,7 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+use dusk_consensus::config::ConsensusParams;
 use node::database::DatabaseOptions;
 use serde::{Deserialize, Serialize};
 
@@ -39,6 +40,33 @@ pub(crate) struct ChainConfig {
     #[serde(with = "humantime_serde")]
     #[serde(default)]
     genesis_timestamp: Option<SystemTime>,
+
+    // NB: changing any consensus parameter is equivalent to forking the chain.
+    #[serde(default)]
+    consensus: ConsensusConfig,
+}
+
+/// Overrides of the mainnet consensus parameters, for private networks
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub(crate) struct ConsensusConfig {
+    validation_committee_credits: Option<usize>,
+    ratification_committee_credits: Option<usize>,
+    max_number_of_transactions: Option<usize>,
+    max_block_size: Option<usize>,
+
+    #[serde(with = "humantime_serde")]
+    #[serde(default)]
+    min_step_timeout: Option<Duration>,
+    #[serde(with = "humantime_serde")]
+    #[serde(default)]
+    max_step_timeout: Option<Duration>,
+    #[serde(with = "humantime_serde")]
+    #[serde(default)]
+    timeout_increase: Option<Duration>,
+
+    emergency_mode_iteration_threshold: Option<u8>,
+    minimum_block_time: Option<u64>,
+    candidate_fault_activation_height: Option<u64>,
 }
 
 impl ChainConfig {
@@ -78,8 +106,10 @@ impl ChainConfig {
             .to_string()
     }
 
-    pub(crate) fn db_options(&self) -> DatabaseOptions {
-        self.db_options.clone().unwrap_or_default()
+    pub(crate) fn db_options(&self) -> Result<DatabaseOptions, &'static str> {
+        let options = self.db_options.clone().unwrap_or_default();
+        options.validate()?;
+        Ok(options)
     }
 
     pub(crate) fn generation_timeout(&self) -> Option<Duration> {
@@ -102,6 +132,45 @@ impl ChainConfig {
         self.block_gas_limit.unwrap_or(DEFAULT_BLOCK_GAS_LIMIT)
     }
 
+    pub(crate) fn consensus_params(
+        &self,
+    ) -> Result<ConsensusParams, &'static str> {
+        let c = &self.consensus;
+        let default = ConsensusParams::default();
+        let params = ConsensusParams {
+            validation_committee_credits: c
+                .validation_committee_credits
+                .unwrap_or(default.validation_committee_credits),
+            ratification_committee_credits: c
+                .ratification_committee_credits
+                .unwrap_or(default.ratification_committee_credits),
+            max_number_of_transactions: c
+                .max_number_of_transactions
+                .unwrap_or(default.max_number_of_transactions),
+            max_block_size: c.max_block_size.unwrap_or(default.max_block_size),
+            min_step_timeout: c
+                .min_step_timeout
+                .unwrap_or(default.min_step_timeout),
+            max_step_timeout: c
+                .max_step_timeout
+                .unwrap_or(default.max_step_timeout),
+            timeout_increase: c
+                .timeout_increase
+                .unwrap_or(default.timeout_increase),
+            emergency_mode_iteration_threshold: c
+                .emergency_mode_iteration_threshold
+                .unwrap_or(default.emergency_mode_iteration_threshold),
+            minimum_block_time: c
+                .minimum_block_time
+                .unwrap_or(default.minimum_block_time),
+            candidate_fault_activation_height: c
+                .candidate_fault_activation_height
+                .unwrap_or(default.candidate_fault_activation_height),
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
     pub(crate) fn genesis_timestamp(&self) -> u64 {
         self.genesis_timestamp
             .map(|t| {
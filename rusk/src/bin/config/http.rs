@@ -5,7 +5,9 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use std::path::PathBuf;
+use std::time::Duration;
 
+use rusk::http::{GqlLimits, RequestQuota};
 use serde::{Deserialize, Serialize};
 
 use crate::args::Args;
@@ -23,6 +25,24 @@ pub struct HttpConfig {
     pub ws_sub_channel_cap: usize,
     #[serde(default = "default_ws_event_channel_cap")]
     pub ws_event_channel_cap: usize,
+    /// Number of events kept to be replayed to RUES clients resuming after
+    /// a reconnection
+    #[serde(default = "default_event_log_cap")]
+    pub event_log_cap: usize,
+    #[serde(default = "default_gql_max_depth")]
+    gql_max_depth: usize,
+    #[serde(default = "default_gql_max_complexity")]
+    gql_max_complexity: usize,
+    #[serde(default = "default_gql_max_range")]
+    gql_max_range: u64,
+    #[serde(default = "default_gql_max_items")]
+    gql_max_items: u64,
+    /// Maximum number of requests per client IP within `quota_window`. No
+    /// quota is enforced if not set
+    requests_per_ip: Option<u32>,
+    #[serde(with = "humantime_serde")]
+    #[serde(default = "default_quota_window")]
+    quota_window: Duration,
 }
 
 impl Default for HttpConfig {
@@ -35,6 +55,13 @@ impl Default for HttpConfig {
             listen_address: None,
             ws_sub_channel_cap: default_ws_sub_channel_cap(),
             ws_event_channel_cap: default_ws_event_channel_cap(),
+            event_log_cap: default_event_log_cap(),
+            gql_max_depth: default_gql_max_depth(),
+            gql_max_complexity: default_gql_max_complexity(),
+            gql_max_range: default_gql_max_range(),
+            gql_max_items: default_gql_max_items(),
+            requests_per_ip: None,
+            quota_window: default_quota_window(),
         }
     }
 }
@@ -55,6 +82,30 @@ const fn default_ws_event_channel_cap() -> usize {
     1024
 }
 
+const fn default_event_log_cap() -> usize {
+    4096
+}
+
+fn default_gql_max_depth() -> usize {
+    GqlLimits::default().max_depth
+}
+
+fn default_gql_max_complexity() -> usize {
+    GqlLimits::default().max_complexity
+}
+
+fn default_gql_max_range() -> u64 {
+    GqlLimits::default().max_range
+}
+
+fn default_gql_max_items() -> u64 {
+    GqlLimits::default().max_items
+}
+
+const fn default_quota_window() -> Duration {
+    Duration::from_secs(60)
+}
+
 impl HttpConfig {
     pub fn listen_addr(&self) -> String {
         self.listen_address
@@ -62,6 +113,22 @@ impl HttpConfig {
             .unwrap_or("127.0.0.1:8080".into())
     }
 
+    pub fn gql_limits(&self) -> GqlLimits {
+        GqlLimits {
+            max_depth: self.gql_max_depth,
+            max_complexity: self.gql_max_complexity,
+            max_range: self.gql_max_range,
+            max_items: self.gql_max_items,
+        }
+    }
+
+    pub fn request_quota(&self) -> Option<RequestQuota> {
+        self.requests_per_ip.map(|max_requests| RequestQuota {
+            max_requests,
+            window: self.quota_window,
+        })
+    }
+
     pub(crate) fn merge(&mut self, args: &Args) {
         // Overwrite config ws-listen-addr
         if let Some(http_listen_addr) = &args.http_listen_addr {
@@ -38,7 +38,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Set custom tracing format if subcommand is specified
     if let Some(command) = args.command {
         log.register()?;
-        command.run()?;
+        command.run(&config)?;
         return Ok(());
     }
 
@@ -69,13 +69,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         node_builder = node_builder
             .with_feeder_call_gas(config.http.feeder_call_gas)
             .with_db_path(db_path)
-            .with_db_options(config.chain.db_options())
+            .with_db_options(config.chain.db_options()?)
             .with_kadcast(config.kadcast)
             .with_consensus_keys(config.chain.consensus_keys_path())
             .with_databroker(config.databroker)
             .with_telemetry(config.telemetry.listen_addr())
             .with_chain_queue_size(config.chain.max_queue_size())
             .with_genesis_timestamp(config.chain.genesis_timestamp())
+            .with_consensus_params(config.chain.consensus_params()?)
             .with_mempool(config.mempool.into())
             .with_state_dir(state_dir)
             .with_generation_timeout(config.chain.generation_timeout())
@@ -89,9 +90,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     if config.http.listen {
         let http_builder = HttpServerConfig {
             address: config.http.listen_addr(),
+            gql_limits: config.http.gql_limits(),
+            request_quota: config.http.request_quota(),
             cert: config.http.cert,
             key: config.http.key,
             ws_event_channel_cap: config.http.ws_event_channel_cap,
+            event_log_cap: config.http.event_log_cap,
         };
         node_builder = node_builder.with_http(http_builder)
     }
@@ -43,8 +43,10 @@ impl RuskHttpBuilder {
                     handler,
                     rues_receiver,
                     http.ws_event_channel_cap,
+                    http.event_log_cap,
                     http.address,
                     cert_and_key,
+                    http.request_quota,
                 )
                 .await?,
             );
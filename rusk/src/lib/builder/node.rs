@@ -7,6 +7,7 @@
 use std::path::PathBuf;
 use std::time::Duration;
 
+use dusk_consensus::config::ConsensusParams;
 use kadcast::config::Config as KadcastConfig;
 use node::chain::ChainSrv;
 use node::database::rocksdb;
@@ -39,6 +40,7 @@ pub struct RuskNodeBuilder {
     db_options: DatabaseOptions,
     max_chain_queue_size: usize,
     genesis_timestamp: u64,
+    consensus_params: ConsensusParams,
 
     generation_timeout: Option<Duration>,
     gas_per_deploy_byte: Option<u64>,
@@ -105,6 +107,14 @@ impl RuskNodeBuilder {
         self
     }
 
+    pub fn with_consensus_params(
+        mut self,
+        consensus_params: ConsensusParams,
+    ) -> Self {
+        self.consensus_params = consensus_params;
+        self
+    }
+
     pub fn with_generation_timeout(
         mut self,
         generation_timeout: Option<Duration>,
@@ -168,6 +178,7 @@ impl RuskNodeBuilder {
             self.min_deployment_gas_price,
             self.block_gas_limit,
             self.feeder_call_gas,
+            self.consensus_params,
             rues_sender.clone(),
             #[cfg(feature = "archive")]
             archive_sender.clone(),
@@ -175,22 +186,35 @@ impl RuskNodeBuilder {
         .map_err(|e| anyhow::anyhow!("Cannot instantiate VM {e}"))?;
         info!("Rusk VM loaded");
 
+        #[cfg(feature = "archive")]
+        let archive = SQLiteArchive::create_or_open(self.db_path.clone()).await;
+
         let node = {
             let db = rocksdb::Backend::create_or_open(
                 self.db_path.clone(),
                 self.db_options.clone(),
             );
             let net = Kadcast::new(self.kadcast.clone())?;
-            RuskNode::new(Node::new(net, db, rusk.clone()))
+            RuskNode::new(
+                Node::new(net, db, rusk.clone()),
+                #[cfg(feature = "archive")]
+                archive.clone(),
+            )
         };
 
         let mut service_list: Vec<Box<Services>> = vec![
-            Box::new(MempoolSrv::new(self.mempool, node_sender.clone())),
+            Box::new(MempoolSrv::new(
+                self.mempool,
+                node_sender.clone(),
+                #[cfg(feature = "archive")]
+                archive_sender.clone(),
+            )),
             Box::new(ChainSrv::new(
                 self.consensus_keys_path,
                 self.max_chain_queue_size,
                 node_sender.clone(),
                 self.genesis_timestamp,
+                self.consensus_params,
             )),
             Box::new(DataBrokerSrv::new(self.databroker)),
             Box::new(TelemetrySrv::new(self.telemetry_address)),
@@ -209,7 +233,9 @@ impl RuskNodeBuilder {
 
             let mut handler = DataSources::default();
             handler.sources.push(Box::new(rusk.clone()));
-            handler.sources.push(Box::new(node.clone()));
+            handler
+                .sources
+                .push(Box::new(node.clone().with_gql_limits(http.gql_limits)));
 
             #[cfg(feature = "prover")]
             handler.sources.push(Box::new(rusk_prover::LocalProver));
@@ -224,8 +250,10 @@ impl RuskNodeBuilder {
                     handler,
                     rues_receiver,
                     http.ws_event_channel_cap,
+                    http.event_log_cap,
                     http.address,
                     cert_and_key,
+                    http.request_quota,
                 )
                 .await?,
             );
@@ -234,8 +262,7 @@ impl RuskNodeBuilder {
         #[cfg(feature = "archive")]
         service_list.push(Box::new(ArchivistSrv {
             archive_receiver,
-            archivist: SQLiteArchive::create_or_open(self.db_path.clone())
-                .await,
+            archivist: archive,
         }));
 
         node.inner().initialize(&mut service_list).await?;
@@ -9,15 +9,19 @@
 #[cfg(feature = "chain")]
 mod chain;
 mod event;
+mod filter;
+mod limits;
 #[cfg(feature = "prover")]
 mod prover;
+mod replay;
+mod route;
 #[cfg(feature = "chain")]
 mod rusk;
 mod stream;
 
 pub(crate) use event::{
-    BinaryWrapper, DataType, ExecutionError, MessageResponse as EventResponse,
-    RequestData, Target,
+    find_header, BinaryWrapper, DataType, ExecutionError,
+    MessageResponse as EventResponse, RequestData, Target,
 };
 
 use execution_core::Event;
@@ -25,16 +29,17 @@ use tokio::task::JoinError;
 use tracing::{debug, info, warn};
 
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::convert::Infallible;
 use std::future::Future;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::str::FromStr;
 use std::sync::mpsc as std_mpsc;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use async_trait::async_trait;
 
@@ -42,11 +47,13 @@ use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::ToSocketAddrs;
 use tokio::sync::{broadcast, mpsc, oneshot, RwLock};
 use tokio::{io, task};
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 use tokio_stream::wrappers::{BroadcastStream, ReceiverStream};
 use tokio_stream::StreamExt;
 use tokio_util::either::Either;
 
 use http_body_util::{BodyExt, Full};
+use hyper::http::header::SEC_WEBSOCKET_PROTOCOL;
 use hyper::http::{HeaderName, HeaderValue};
 use hyper::service::Service;
 use hyper::{
@@ -60,9 +67,10 @@ use tungstenite::protocol::frame::coding::CloseCode;
 use tungstenite::protocol::{CloseFrame, Message};
 
 use futures_util::stream::iter as stream_iter;
-use futures_util::{SinkExt, TryStreamExt};
+use futures_util::{Sink, SinkExt, TryStreamExt};
 
 use anyhow::Error as AnyhowError;
+use async_graphql::http::WebSocketProtocols;
 use hyper_util::rt::TokioIo;
 use rand::rngs::OsRng;
 
@@ -73,12 +81,24 @@ use crate::http::event::FullOrStreamBody;
 use crate::VERSION;
 
 pub use self::event::{RuesDispatchEvent, RuesEvent, RUES_LOCATION_PREFIX};
+pub use self::filter::RuesFilter;
+pub use self::limits::{ClientQuota, GqlLimits, RequestQuota};
 
 use self::event::{MessageRequest, ResponseData, RuesEventUri, SessionId};
+use self::filter::RUSK_FILTER_HEADER;
+use self::limits::QuotaTracker;
+use self::replay::{
+    event_seq, sequence_events, EventLog, RUSK_LAST_SEQ_HEADER,
+    RUSK_REPLAY_HEIGHT_HEADER, RUSK_SEQ_HEADER,
+};
+use self::route::RuesRoute;
 use self::stream::{Listener, Stream};
 
 const RUSK_VERSION_HEADER: &str = "Rusk-Version";
 
+/// Location of the OpenAPI description of the endpoints served by the node
+pub const OPENAPI_LOCATION: &str = "/static/openapi.json";
+
 pub struct HttpServer {
     handle: task::JoinHandle<()>,
     local_addr: SocketAddr,
@@ -90,6 +110,10 @@ pub struct HttpServerConfig {
     pub cert: Option<PathBuf>,
     pub key: Option<PathBuf>,
     pub ws_event_channel_cap: usize,
+    /// Number of events kept to be replayed to reconnecting RUES clients
+    pub event_log_cap: usize,
+    pub gql_limits: GqlLimits,
+    pub request_quota: Option<RequestQuota>,
 }
 
 impl HttpServer {
@@ -97,12 +121,18 @@ impl HttpServer {
         self.handle.await
     }
 
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
     pub async fn bind<A, H, P1, P2>(
         handler: H,
         event_receiver: broadcast::Receiver<RuesEvent>,
         ws_event_channel_cap: usize,
+        event_log_cap: usize,
         addr: A,
         cert_and_key: Option<(P1, P2)>,
+        request_quota: Option<RequestQuota>,
     ) -> io::Result<Self>
     where
         A: ToSocketAddrs,
@@ -121,12 +151,21 @@ impl HttpServer {
 
         info!("Starting HTTP Listener to {local_addr}");
 
+        let event_log = Arc::new(EventLog::new(event_log_cap));
+        let event_receiver = sequence_events(
+            event_receiver,
+            event_log.clone(),
+            shutdown_receiver.resubscribe(),
+        );
+
         let handle = task::spawn(listening_loop(
             handler,
             listener,
             event_receiver,
+            event_log,
             shutdown_receiver,
             ws_event_channel_cap,
+            request_quota,
         ));
 
         Ok(Self {
@@ -181,6 +220,42 @@ impl HandleRequest for DataSources {
         }
         Err(anyhow::anyhow!("unsupported location"))
     }
+
+    fn can_handle_gql_ws(&self) -> bool {
+        self.sources.iter().any(|s| s.can_handle_gql_ws())
+    }
+
+    async fn handle_gql_ws(
+        &self,
+        websocket: HyperWebsocket,
+        protocol: WebSocketProtocols,
+        events: broadcast::Receiver<RuesEvent>,
+        shutdown: broadcast::Receiver<Infallible>,
+        quota: Option<ClientQuota>,
+    ) {
+        if let Some(h) = self.sources.iter().find(|s| s.can_handle_gql_ws()) {
+            h.handle_gql_ws(websocket, protocol, events, shutdown, quota)
+                .await;
+        }
+    }
+
+    fn can_replay_rues(&self, uri: &RuesEventUri) -> bool {
+        self.sources.iter().any(|s| s.can_replay_rues(uri))
+    }
+
+    async fn replay_rues(
+        &self,
+        uri: &RuesEventUri,
+        from_height: u64,
+        cursor: Option<String>,
+    ) -> anyhow::Result<(Vec<RuesEvent>, Option<String>)> {
+        for h in &self.sources {
+            if h.can_replay_rues(uri) {
+                return h.replay_rues(uri, from_height, cursor).await;
+            }
+        }
+        Err(anyhow::anyhow!("Replay is not supported for {uri}"))
+    }
 }
 
 #[derive(Clone)]
@@ -200,8 +275,10 @@ async fn listening_loop<H>(
     handler: H,
     listener: Listener,
     events: broadcast::Receiver<RuesEvent>,
+    event_log: Arc<EventLog>,
     mut shutdown: broadcast::Receiver<Infallible>,
     ws_event_channel_cap: usize,
+    request_quota: Option<RequestQuota>,
 ) where
     H: HandleRequest,
 {
@@ -212,8 +289,11 @@ async fn listening_loop<H>(
         sources: sources.clone(),
         sockets_map: sockets_map.clone(),
         events: events.resubscribe(),
+        event_log,
         shutdown: shutdown.resubscribe(),
         ws_event_channel_cap,
+        quota: request_quota.map(|q| Arc::new(QuotaTracker::new(q))),
+        remote_ip: None,
     };
 
     loop {
@@ -222,15 +302,16 @@ async fn listening_loop<H>(
                 break;
             }
             r = listener.accept() => {
-                let stream = match r {
-                    Ok(stream) => stream,
+                let (stream, remote_addr) = match r {
+                    Ok(accepted) => accepted,
                     Err(_) => break,
                 };
 
                 let http = HttpBuilder::new(TokioExecutor);
 
                 let stream = TokioIo::new(stream);
-                let service = service.clone();
+                let mut service = service.clone();
+                service.remote_ip = Some(remote_addr.ip());
 
                 task::spawn(async move {
                     let conn = http.serve_connection_with_upgrades(stream, service);
@@ -241,11 +322,23 @@ async fn listening_loop<H>(
     }
 }
 
+/// Returns the frame closing a websocket whose client exceeded its request
+/// quota.
+fn quota_exceeded_frame(retry_after: Duration) -> CloseFrame<'static> {
+    let retry_after = retry_after.as_secs().max(1);
+    CloseFrame {
+        code: CloseCode::Policy,
+        reason: format!("Request quota exceeded, retry after {retry_after}s")
+            .into(),
+    }
+}
+
 async fn handle_stream<H: HandleRequest>(
     sources: Arc<H>,
     websocket: HyperWebsocket,
     target: Target,
     mut shutdown: broadcast::Receiver<Infallible>,
+    quota: Option<ClientQuota>,
 ) {
     let mut stream = match websocket.await {
         Ok(stream) => stream,
@@ -369,6 +462,12 @@ async fn handle_stream<H: HandleRequest>(
                 match req {
                     // We received a valid request and should spawn a new task to handle it
                     Ok(mut req) => {
+                        // Each message is charged to the client quota, as a
+                        // request would be
+                        if let Some(Err(retry_after)) = quota.as_ref().map(ClientQuota::check) {
+                            let _ = stream.close(Some(quota_exceeded_frame(retry_after))).await;
+                            break;
+                        }
                         req.event.target=target.clone();
                         task::spawn(handle_execution(
                             sources.clone(),
@@ -395,8 +494,12 @@ struct ExecutionService<H> {
     sockets_map:
         Arc<RwLock<HashMap<SessionId, mpsc::Sender<SubscriptionAction>>>>,
     events: broadcast::Receiver<RuesEvent>,
+    event_log: Arc<EventLog>,
     shutdown: broadcast::Receiver<Infallible>,
     ws_event_channel_cap: usize,
+    quota: Option<Arc<QuotaTracker>>,
+    /// IP of the client connected to this service, if known
+    remote_ip: Option<IpAddr>,
 }
 
 impl<H> Clone for ExecutionService<H> {
@@ -405,8 +508,11 @@ impl<H> Clone for ExecutionService<H> {
             sources: self.sources.clone(),
             sockets_map: self.sockets_map.clone(),
             events: self.events.resubscribe(),
+            event_log: self.event_log.clone(),
             shutdown: self.shutdown.resubscribe(),
             ws_event_channel_cap: self.ws_event_channel_cap,
+            quota: self.quota.clone(),
+            remote_ip: self.remote_ip,
         }
     }
 }
@@ -431,9 +537,33 @@ where
     /// the former case, the request is handled on the spot, while in the
     /// latter task running the stream handler loop is spawned.
     fn call(&self, mut req: Request<Incoming>) -> Self::Future {
+        let quota = self
+            .quota
+            .clone()
+            .zip(self.remote_ip)
+            .map(|(tracker, ip)| ClientQuota::new(tracker, ip));
+        if let Some(quota) = &quota {
+            if let Err(retry_after) = quota.check() {
+                let retry_after = retry_after.as_secs().max(1);
+                let body = serde_json::json!({
+                    "error": "Request quota exceeded",
+                    "retry_after": retry_after,
+                });
+                return Box::pin(async move {
+                    Ok(Response::builder()
+                        .status(StatusCode::TOO_MANY_REQUESTS)
+                        .header("Retry-After", retry_after)
+                        .header("Content-Type", "application/json")
+                        .body(Full::new(body.to_string().into()).into())
+                        .expect("Failed to build response"))
+                });
+            }
+        }
+
         let sources = self.sources.clone();
         let sockets_map = self.sockets_map.clone();
         let events = self.events.resubscribe();
+        let event_log = self.event_log.clone();
         let shutdown = self.shutdown.resubscribe();
         let ws_event_channel_cap = self.ws_event_channel_cap;
 
@@ -443,8 +573,10 @@ where
                 sources,
                 sockets_map,
                 events,
+                event_log,
                 shutdown,
                 ws_event_channel_cap,
+                quota,
             )
             .await;
             response.map(Into::into).or_else(|error| {
@@ -458,14 +590,149 @@ where
 }
 
 enum SubscriptionAction {
-    Subscribe(RuesEventUri),
+    Subscribe(RuesEventUri, RuesFilter, Option<Replay>),
     Unsubscribe(RuesEventUri),
 }
 
+/// Events to be replayed to a client subscribing after a reconnection
+enum Replay {
+    /// The events recorded in the log after the given sequence number
+    Since(u64),
+    /// Finalized events fetched from the archive, followed by all the events
+    /// in the log. Archived events may be delivered again from the log
+    Archived(ArchivedReplay),
+}
+
+/// Replay of the archived events emitted from a block height onwards, of
+/// which the first batch has already been fetched
+struct ArchivedReplay {
+    height: u64,
+    events: Vec<RuesEvent>,
+    next: Option<String>,
+}
+
+/// Checks if the event matches any of the subscriptions, and passes its
+/// filter.
+fn is_subscribed(
+    subscriptions: &HashMap<RuesEventUri, RuesFilter>,
+    event: &RuesEvent,
+) -> bool {
+    subscriptions
+        .iter()
+        .any(|(sub, filter)| sub.matches(event) && filter.matches(event))
+}
+
+/// Replays the events to a new subscription, streaming the archived ones
+/// batch by batch, and updating the sequence number of the last event
+/// processed by the session.
+///
+/// Events already processed are only replayed if they were not delivered to
+/// any other subscription, while the ones still to be processed are replayed
+/// here and skipped when received live.
+///
+/// Returns the frame to close the stream with if the events cannot be
+/// replayed, for the client not to miss them silently.
+async fn replay_events<H: HandleRequest, S: Sink<Message> + Unpin>(
+    replay: Replay,
+    (subscription, filter): (&RuesEventUri, &RuesFilter),
+    subscriptions: &HashMap<RuesEventUri, RuesFilter>,
+    event_log: &EventLog,
+    last_seq: &mut u64,
+    handler: &H,
+    stream: &mut S,
+) -> Result<(), CloseFrame<'static>> {
+    let failed_sending = || CloseFrame {
+        code: CloseCode::Error,
+        reason: Cow::from("Failed sending event"),
+    };
+
+    let since = match replay {
+        Replay::Since(seq) => seq,
+        Replay::Archived(archived) => {
+            // Archived events carry the sequence number preceding the logged
+            // events replayed after them
+            let since = event_log.oldest_seq().saturating_sub(1);
+
+            let ArchivedReplay {
+                height,
+                mut events,
+                mut next,
+            } = archived;
+            loop {
+                let batch = events
+                    .into_iter()
+                    .filter(|e| filter.matches(e))
+                    .map(|mut e| {
+                        e.add_header(RUSK_SEQ_HEADER, since);
+                        e
+                    })
+                    .collect();
+                send_rues_events(stream, batch)
+                    .await
+                    .map_err(|_| failed_sending())?;
+
+                let Some(cursor) = next else {
+                    break;
+                };
+                (events, next) = handler
+                    .replay_rues(subscription, height, Some(cursor))
+                    .await
+                    .map_err(|err| CloseFrame {
+                        code: CloseCode::Error,
+                        reason: format!("Failed replaying events: {err}")
+                            .into(),
+                    })?;
+            }
+
+            since
+        }
+    };
+
+    let logged = event_log.since(since).ok_or_else(|| CloseFrame {
+        code: CloseCode::Again,
+        reason: Cow::from("Events to replay have been evicted"),
+    })?;
+
+    let mut replayed = vec![];
+    for (seq, event) in logged {
+        let is_new = subscription.matches(&event) && filter.matches(&event);
+        let is_other = is_subscribed(subscriptions, &event);
+        let deliver = match seq <= *last_seq {
+            true => is_new && !is_other,
+            false => is_new || is_other,
+        };
+        if deliver {
+            replayed.push(event);
+        }
+        *last_seq = (*last_seq).max(seq);
+    }
+
+    send_rues_events(stream, replayed)
+        .await
+        .map_err(|_| failed_sending())
+}
+
+/// Sends the events to the client, stopping at the first failure.
+async fn send_rues_events<S>(
+    stream: &mut S,
+    events: Vec<RuesEvent>,
+) -> Result<(), S::Error>
+where
+    S: Sink<Message> + Unpin,
+{
+    for mut event in events {
+        event.add_header("Content-Location", event.uri.to_string());
+        stream.send(Message::Binary(event.to_bytes())).await?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn handle_stream_rues<H: HandleRequest>(
     sid: SessionId,
     websocket: HyperWebsocket,
     events: broadcast::Receiver<RuesEvent>,
+    event_log: Arc<EventLog>,
     mut subscriptions: mpsc::Receiver<SubscriptionAction>,
     mut shutdown: broadcast::Receiver<Infallible>,
     handler: Arc<H>,
@@ -491,7 +758,11 @@ async fn handle_stream_rues<H: HandleRequest>(
     // FIXME make this a configuration parameter
     const DISPATCH_BUFFER_SIZE: usize = 16;
 
-    let mut subscription_set = HashSet::new();
+    let mut subscriptions_map = HashMap::new();
+
+    // Sequence number of the last event processed by the session. Events
+    // recorded before the session started are never delivered live.
+    let mut last_seq = event_log.last_seq();
 
     let mut events = BroadcastStream::new(events);
 
@@ -546,11 +817,28 @@ async fn handle_stream_rues<H: HandleRequest>(
                 };
 
                 match subscription {
-                    SubscriptionAction::Subscribe(subscription) => {
-                        subscription_set.insert(subscription);
+                    SubscriptionAction::Subscribe(subscription, filter, replay) => {
+                        if let Some(replay) = replay {
+                            let replayed = replay_events(
+                                replay,
+                                (&subscription, &filter),
+                                &subscriptions_map,
+                                &event_log,
+                                &mut last_seq,
+                                handler.as_ref(),
+                                &mut stream,
+                            ).await;
+                            if let Err(frame) = replayed {
+                                warn!("Closing stream for {sid}: {}", frame.reason);
+                                let _ = stream.close(Some(frame)).await;
+                                break;
+                            }
+                        }
+
+                        subscriptions_map.insert(subscription, filter);
                     },
                     SubscriptionAction::Unsubscribe(subscription) => {
-                        subscription_set.remove(&subscription);
+                        subscriptions_map.remove(&subscription);
                     },
                 }
             }
@@ -558,30 +846,61 @@ async fn handle_stream_rues<H: HandleRequest>(
             Some(event) = events.next() => {
                 let mut event = match event {
                     Ok(event) => event,
-                    Err(err) => {
-                        // If the event channel is closed, it means the
-                        // server has stopped producing events, so we
-                        // should inform the client and stop.
-                        let _ = stream.close(Some(CloseFrame {
-                            code: CloseCode::Away,
-                            reason: Cow::from("Shutting down"),
-                        })).await;
-                        break;
+                    Err(BroadcastStreamRecvError::Lagged(missed)) => {
+                        // If the session lagged behind, the missed events are
+                        // recovered from the log. If they are no longer there
+                        // we inform the client, which may resume with a new
+                        // session.
+                        let recovered = match event_log.since(last_seq) {
+                            Some(recovered) => recovered,
+                            None => {
+                                let _ = stream.close(Some(CloseFrame {
+                                    code: CloseCode::Again,
+                                    reason: format!("Missed {missed} events").into(),
+                                })).await;
+                                break;
+                            }
+                        };
+
+                        let mut subscribed = vec![];
+                        for (seq, event) in recovered {
+                            if is_subscribed(&subscriptions_map, &event) {
+                                subscribed.push(event);
+                            }
+                            last_seq = seq;
+                        }
 
+                        if send_rues_events(&mut stream, subscribed).await.is_err() {
+                            let _ = stream.close(Some(CloseFrame {
+                                code: CloseCode::Error,
+                                reason: Cow::from("Failed sending event"),
+                            })).await;
+                            break;
+                        }
+                        continue;
                     }
                 };
 
-                // The event is subscribed to if it matches any of the subscriptions.
-                let mut is_subscribed = false;
-                for sub in &subscription_set {
-                    if sub.matches(&event) {
-                        is_subscribed = true;
+                // Events already delivered from the log are skipped, while a
+                // gap in the sequence numbers means that events have been
+                // missed before being recorded
+                if let Some(seq) = event_seq(&event) {
+                    if seq <= last_seq {
+                        continue;
+                    }
+                    if seq > last_seq + 1 {
+                        let missed = seq - last_seq - 1;
+                        let _ = stream.close(Some(CloseFrame {
+                            code: CloseCode::Again,
+                            reason: format!("Missed {missed} events").into(),
+                        })).await;
                         break;
                     }
+                    last_seq = seq;
                 }
 
                 // If the event is subscribed, we send it to the client.
-                if is_subscribed {
+                if is_subscribed(&subscriptions_map, &event) {
                     event.add_header("Content-Location", event.uri.to_string());
                     let event = event.to_bytes();
 
@@ -655,9 +974,17 @@ async fn handle_request_rues<H: HandleRequest>(
         RwLock<HashMap<SessionId, mpsc::Sender<SubscriptionAction>>>,
     >,
     events: broadcast::Receiver<RuesEvent>,
+    event_log: Arc<EventLog>,
     shutdown: broadcast::Receiver<Infallible>,
     ws_event_channel_cap: usize,
+    quota: Option<ClientQuota>,
 ) -> Result<Response<FullOrStreamBody>, ExecutionError> {
+    if hyper_tungstenite::is_upgrade_request(&req)
+        && req.uri().path() == RuesRoute::GraphqlSubscribe.path()
+    {
+        return handle_request_gql_ws(req, handler, events, shutdown, quota);
+    }
+
     if hyper_tungstenite::is_upgrade_request(&req) {
         let (subscription_sender, subscriptions) =
             mpsc::channel(ws_event_channel_cap);
@@ -678,6 +1005,7 @@ async fn handle_request_rues<H: HandleRequest>(
             sid,
             websocket,
             events,
+            event_log,
             subscriptions,
             shutdown,
             handler.clone(),
@@ -743,8 +1071,74 @@ async fn handle_request_rues<H: HandleRequest>(
             }
         };
 
+        let filter = match headers.get(RUSK_FILTER_HEADER) {
+            Some(filter) => {
+                let filter = filter
+                    .to_str()
+                    .map_err(anyhow::Error::from)
+                    .and_then(RuesFilter::parse);
+                match filter {
+                    Ok(filter) => filter,
+                    Err(err) => {
+                        let body = serde_json::json!({
+                            "error": format!("Invalid filter: {err}"),
+                        });
+                        return response(
+                            StatusCode::BAD_REQUEST,
+                            body.to_string(),
+                        );
+                    }
+                }
+            }
+            None => RuesFilter::default(),
+        };
+
+        let last_seq = match parse_u64_header(headers, RUSK_LAST_SEQ_HEADER) {
+            Ok(last_seq) => last_seq,
+            Err(err) => return response(StatusCode::BAD_REQUEST, err),
+        };
+        let replay_height =
+            match parse_u64_header(headers, RUSK_REPLAY_HEIGHT_HEADER) {
+                Ok(height) => height,
+                Err(err) => return response(StatusCode::BAD_REQUEST, err),
+            };
+
+        // Events following the last one seen by the client are replayed from
+        // the log if possible, falling back to the archive otherwise
+        let replay = match (last_seq, replay_height) {
+            (Some(seq), _) if event_log.covers(seq) => Some(Replay::Since(seq)),
+            (_, Some(height)) => {
+                match handler.replay_rues(&uri, height, None).await {
+                    Ok((events, next)) => {
+                        Some(Replay::Archived(ArchivedReplay {
+                            height,
+                            events,
+                            next,
+                        }))
+                    }
+                    Err(err) => {
+                        let body = serde_json::json!({
+                            "error": format!("Failed replaying events: {err}"),
+                        });
+                        return response(
+                            StatusCode::BAD_REQUEST,
+                            body.to_string(),
+                        );
+                    }
+                }
+            }
+            (Some(seq), None) => {
+                let body = serde_json::json!({
+                    "error": format!("Events after {seq} are not available"),
+                    "oldest_seq": event_log.oldest_seq(),
+                });
+                return response(StatusCode::GONE, body.to_string());
+            }
+            (None, None) => None,
+        };
+
         let action = match *req.method() {
-            Method::GET => SubscriptionAction::Subscribe(uri),
+            Method::GET => SubscriptionAction::Subscribe(uri, filter, replay),
             Method::DELETE => SubscriptionAction::Unsubscribe(uri),
             _ => {
                 return response(
@@ -765,6 +1159,75 @@ async fn handle_request_rues<H: HandleRequest>(
     }
 }
 
+/// Parses the numeric value of a header, if present.
+fn parse_u64_header(
+    headers: &hyper::HeaderMap,
+    name: &str,
+) -> Result<Option<u64>, String> {
+    headers
+        .get(name)
+        .map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .ok_or_else(|| {
+                    serde_json::json!({ "error": format!("Invalid {name}") })
+                        .to_string()
+                })
+        })
+        .transpose()
+}
+
+/// Upgrades the request to a websocket serving GraphQL subscriptions, using
+/// the protocol requested by the client amongst the ones supported.
+fn handle_request_gql_ws<H: HandleRequest>(
+    mut req: Request<Incoming>,
+    handler: Arc<H>,
+    events: broadcast::Receiver<RuesEvent>,
+    shutdown: broadcast::Receiver<Infallible>,
+    quota: Option<ClientQuota>,
+) -> Result<Response<FullOrStreamBody>, ExecutionError> {
+    if !handler.can_handle_gql_ws() {
+        return response(
+            StatusCode::NOT_FOUND,
+            "{\"error\":\"GraphQL subscriptions are not supported\"}",
+        );
+    }
+
+    let protocol = req
+        .headers()
+        .get(SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|p| p.to_str().ok())
+        .and_then(|p| {
+            p.split(',')
+                .find_map(|p| WebSocketProtocols::from_str(p.trim()).ok())
+        });
+    let protocol = match protocol {
+        Some(protocol) => protocol,
+        None => {
+            return response(
+                StatusCode::BAD_REQUEST,
+                "{\"error\":\"Unsupported websocket protocol\"}",
+            );
+        }
+    };
+
+    let (mut response, websocket) = hyper_tungstenite::upgrade(&mut req, None)?;
+    response.headers_mut().insert(
+        SEC_WEBSOCKET_PROTOCOL,
+        HeaderValue::from_static(protocol.sec_websocket_protocol()),
+    );
+
+    task::spawn(async move {
+        handler
+            .handle_gql_ws(websocket, protocol, events, shutdown, quota)
+            .await
+    });
+
+    Ok(response.map(Into::into))
+}
+
 async fn handle_request<H>(
     mut req: Request<Incoming>,
     sources: Arc<H>,
@@ -772,8 +1235,10 @@ async fn handle_request<H>(
         RwLock<HashMap<SessionId, mpsc::Sender<SubscriptionAction>>>,
     >,
     events: broadcast::Receiver<RuesEvent>,
+    event_log: Arc<EventLog>,
     shutdown: broadcast::Receiver<Infallible>,
     ws_event_channel_cap: usize,
+    quota: Option<ClientQuota>,
 ) -> Result<Response<FullOrStreamBody>, ExecutionError>
 where
     H: HandleRequest,
@@ -787,12 +1252,24 @@ where
             sources.clone(),
             sockets_map,
             events,
+            event_log,
             shutdown,
             ws_event_channel_cap,
+            quota,
         )
         .await;
     }
 
+    if path == OPENAPI_LOCATION {
+        let openapi = include_str!("../assets/openapi.json");
+        let mut response = Response::new(Full::from(openapi).into());
+        response.headers_mut().append(
+            "Content-Type",
+            HeaderValue::from_static("application/json"),
+        );
+        return Ok(response);
+    }
+
     #[cfg(feature = "http-wasm")]
     if path == "/static/drivers/wallet-core.wasm" {
         let wallet_wasm = include_bytes!(
@@ -811,7 +1288,7 @@ where
         let target = req.uri().path().try_into()?;
 
         let (response, websocket) = hyper_tungstenite::upgrade(&mut req, None)?;
-        task::spawn(handle_stream(sources, websocket, target, shutdown));
+        task::spawn(handle_stream(sources, websocket, target, shutdown, quota));
 
         Ok(response.map(Into::into))
     } else {
@@ -911,6 +1388,38 @@ pub trait HandleRequest: Send + Sync + 'static {
         &self,
         request: &RuesDispatchEvent,
     ) -> anyhow::Result<ResponseData>;
+
+    fn can_handle_gql_ws(&self) -> bool {
+        false
+    }
+    /// Serves GraphQL subscriptions over the websocket, until either the
+    /// client or the server closes it.
+    ///
+    /// Each message sent by the client is charged to its quota, if any.
+    async fn handle_gql_ws(
+        &self,
+        _websocket: HyperWebsocket,
+        _protocol: WebSocketProtocols,
+        _events: broadcast::Receiver<RuesEvent>,
+        _shutdown: broadcast::Receiver<Infallible>,
+        _quota: Option<ClientQuota>,
+    ) {
+    }
+
+    fn can_replay_rues(&self, _uri: &RuesEventUri) -> bool {
+        false
+    }
+    /// Returns a batch of the archived events at the given location, emitted
+    /// from the block at the given height onwards and following the given
+    /// cursor, together with the cursor of the next batch, if any.
+    async fn replay_rues(
+        &self,
+        uri: &RuesEventUri,
+        _from_height: u64,
+        _cursor: Option<String>,
+    ) -> anyhow::Result<(Vec<RuesEvent>, Option<String>)> {
+        Err(anyhow::anyhow!("Replay is not supported for {uri}"))
+    }
 }
 
 #[cfg(test)]
@@ -937,6 +1446,9 @@ mod tests {
         b"I am call data 3",
     ];
 
+    const ARCHIVED_DATA: &[&[u8]] =
+        &[b"archived 0", b"archived 1", b"archived 2"];
+
     #[async_trait]
     impl HandleRequest for TestHandle {
         fn can_handle(&self, _request: &MessageRequest) -> bool {
@@ -971,6 +1483,54 @@ mod tests {
             };
             Ok(response)
         }
+
+        fn can_replay_rues(&self, uri: &RuesEventUri) -> bool {
+            uri.component == "contracts"
+        }
+
+        /// Replays the archived data one event at a time
+        async fn replay_rues(
+            &self,
+            uri: &RuesEventUri,
+            _from_height: u64,
+            cursor: Option<String>,
+        ) -> anyhow::Result<(Vec<RuesEvent>, Option<String>)> {
+            let index = cursor.map(|c| c.parse()).transpose()?.unwrap_or(0);
+            let event = RuesEvent {
+                uri: uri.clone(),
+                headers: Default::default(),
+                data: ARCHIVED_DATA[index].to_vec().into(),
+            };
+            let next = index + 1;
+            let next = (next < ARCHIVED_DATA.len()).then(|| next.to_string());
+            Ok((vec![event], next))
+        }
+    }
+
+    #[test]
+    fn openapi_describes_every_route() {
+        let openapi: serde_json::Value =
+            serde_json::from_str(include_str!("../assets/openapi.json"))
+                .expect("The OpenAPI description should be valid JSON");
+        let paths = openapi["paths"]
+            .as_object()
+            .expect("The OpenAPI description should have paths");
+
+        let routes: Vec<_> = RuesRoute::ALL.iter().map(|r| r.path()).collect();
+        for route in &routes {
+            assert!(paths.contains_key(*route), "{route} is not described");
+        }
+
+        // Subscriptions are described once for every component and topic
+        let described = paths.keys().filter(|p| {
+            p.starts_with("/on/") && *p != "/on/{component}/{topic}"
+        });
+        for path in described {
+            assert!(
+                routes.contains(&path.as_str()),
+                "{path} is described but not served"
+            );
+        }
     }
 
     #[tokio::test]
@@ -984,8 +1544,10 @@ mod tests {
             TestHandle,
             event_receiver,
             ws_event_channel_cap,
+            16,
             "localhost:0",
             cert_and_key,
+            None,
         )
         .await
         .expect("Binding the server to the address should succeed");
@@ -1038,8 +1600,10 @@ mod tests {
             TestHandle,
             event_receiver,
             ws_event_channel_cap,
+            16,
             "localhost:0",
             Some((cert_path, key_path)),
+            None,
         )
         .await
         .expect("Binding the server to the address should succeed");
@@ -1095,8 +1659,10 @@ mod tests {
             TestHandle,
             event_receiver,
             ws_event_channel_cap,
+            16,
             "localhost:0",
             cert_and_key,
+            None,
         )
         .await
         .expect("Binding the server to the address should succeed");
@@ -1178,8 +1744,10 @@ mod tests {
             TestHandle,
             event_receiver,
             ws_event_channel_cap,
+            16,
             "localhost:0",
             cert_and_key,
+            None,
         )
         .await
         .expect("Binding the server to the address should succeed");
@@ -1328,6 +1896,208 @@ mod tests {
         assert_eq!(received_event, event, "Event should be the same");
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn websocket_rues_replay() {
+        let cert_and_key: Option<(String, String)> = None;
+
+        let (event_sender, event_receiver) = broadcast::channel(16);
+        let ws_event_channel_cap = 2;
+
+        let server = HttpServer::bind(
+            TestHandle,
+            event_receiver,
+            ws_event_channel_cap,
+            16,
+            "localhost:0",
+            cert_and_key,
+            None,
+        )
+        .await
+        .expect("Binding the server to the address should succeed");
+
+        let connect = || {
+            let stream = TcpStream::connect(server.local_addr)
+                .expect("Connecting to the server should succeed");
+            let ws_uri = format!("ws://{}/on", server.local_addr);
+            let (mut stream, _) = client(ws_uri, stream)
+                .expect("Handshake with the server should succeed");
+            let sid = stream
+                .read()
+                .expect("Session ID should be received")
+                .into_text()
+                .expect("Session ID should come in a text message");
+            (stream, sid)
+        };
+
+        const CONTRACT_ID: WrappedContractId =
+            WrappedContractId(ContractId::from_bytes([1; 32]));
+        const TOPIC: &str = "topic";
+
+        let location = format!(
+            "http://{}/on/contracts:{}/{TOPIC}",
+            server.local_addr,
+            hex::encode(CONTRACT_ID.0)
+        );
+        let contract_event = |data: &[u8]| {
+            RuesEvent::from(ContractTxEvent {
+                event: ContractEvent {
+                    target: CONTRACT_ID,
+                    topic: TOPIC.into(),
+                    data: data.to_vec(),
+                },
+                origin: None,
+            })
+        };
+
+        let client = reqwest::Client::new();
+
+        let (mut stream, sid) = connect();
+        let response = client
+            .get(&location)
+            .header("Rusk-Session-Id", sid)
+            .send()
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let first_event = contract_event(b"first");
+        event_sender
+            .send(first_event.clone())
+            .expect("Sending event should succeed");
+
+        let message = stream.read().expect("Event should be received");
+        let (headers, _) =
+            crate::http::event::parse_header(&message.into_data())
+                .expect("Headers should deserialize");
+        let last_seq = headers
+            .get(replay::RUSK_SEQ_HEADER)
+            .and_then(serde_json::Value::as_u64)
+            .expect("Event should have a sequence number");
+
+        // The event is emitted while the client is disconnected
+        drop(stream);
+        let missed_event = contract_event(b"missed");
+        event_sender
+            .send(missed_event.clone())
+            .expect("Sending event should succeed");
+
+        let (mut stream, sid) = connect();
+        let response = client
+            .get(&location)
+            .header("Rusk-Session-Id", &sid)
+            .header("Rusk-Last-Seq", last_seq.to_string())
+            .send()
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let message = stream.read().expect("Event should be received");
+        let event =
+            from_bytes(&message.into_data()).expect("Event should deserialize");
+        assert_eq!(missed_event, event, "Missed event should be replayed");
+
+        // Sequence numbers no longer in the log cannot be resumed from
+        let response = client
+            .get(&location)
+            .header("Rusk-Session-Id", &sid)
+            .header("Rusk-Last-Seq", "1")
+            .send()
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::GONE);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn websocket_rues_archive_replay() {
+        let cert_and_key: Option<(String, String)> = None;
+
+        let (event_sender, event_receiver) = broadcast::channel(16);
+        let ws_event_channel_cap = 2;
+
+        let server = HttpServer::bind(
+            TestHandle,
+            event_receiver,
+            ws_event_channel_cap,
+            16,
+            "localhost:0",
+            cert_and_key,
+            None,
+        )
+        .await
+        .expect("Binding the server to the address should succeed");
+
+        let stream = TcpStream::connect(server.local_addr)
+            .expect("Connecting to the server should succeed");
+        let ws_uri = format!("ws://{}/on", server.local_addr);
+        let (mut stream, _) = client(ws_uri, stream)
+            .expect("Handshake with the server should succeed");
+        let sid = stream
+            .read()
+            .expect("Session ID should be received")
+            .into_text()
+            .expect("Session ID should come in a text message");
+
+        const CONTRACT_ID: WrappedContractId =
+            WrappedContractId(ContractId::from_bytes([1; 32]));
+        const TOPIC: &str = "topic";
+
+        let uri = RuesEventUri {
+            component: "contracts".into(),
+            entity: Some(hex::encode(CONTRACT_ID.0)),
+            topic: TOPIC.into(),
+        };
+
+        // The sequence number is no longer in the log, so the events are
+        // replayed from the archive
+        let response = reqwest::Client::new()
+            .get(format!("http://{}{uri}", server.local_addr))
+            .header("Rusk-Session-Id", sid)
+            .header("Rusk-Last-Seq", "1")
+            .header("Rusk-Replay-Height", "0")
+            .send()
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        for data in ARCHIVED_DATA {
+            let message = stream.read().expect("Event should be received");
+            let message = message.into_data();
+
+            let (headers, _) = crate::http::event::parse_header(&message)
+                .expect("Headers should deserialize");
+            assert!(
+                headers.get(replay::RUSK_SEQ_HEADER).is_some(),
+                "Archived event should have a sequence number"
+            );
+
+            let event = from_bytes(&message).expect("Event should deserialize");
+            let archived = RuesEvent {
+                uri: uri.clone(),
+                headers: Default::default(),
+                data: data.to_vec().into(),
+            };
+            assert_eq!(archived, event, "Archived event should be replayed");
+        }
+
+        // Live events follow the archived ones
+        let live_event = RuesEvent::from(ContractTxEvent {
+            event: ContractEvent {
+                target: CONTRACT_ID,
+                topic: TOPIC.into(),
+                data: b"live".to_vec(),
+            },
+            origin: None,
+        });
+        event_sender
+            .send(live_event.clone())
+            .expect("Sending event should succeed");
+
+        let message = stream.read().expect("Event should be received");
+        let event =
+            from_bytes(&message.into_data()).expect("Event should deserialize");
+        assert_eq!(live_event, event, "Live event should be received");
+    }
+
     pub fn from_bytes(data: &[u8]) -> anyhow::Result<RuesEvent> {
         let (mut headers, data) = crate::http::event::parse_header(data)?;
 
@@ -1341,6 +2111,10 @@ mod tests {
         let uri = RuesEventUri::parse_from_path(&path)
             .ok_or(anyhow::anyhow!("Invalid location"))?;
 
+        // Sequence numbers are assigned by the server, so they are not
+        // compared
+        headers.remove(replay::RUSK_SEQ_HEADER);
+
         let data = data.to_vec().into();
         Ok(RuesEvent { data, headers, uri })
     }
@@ -4,6 +4,7 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+mod gas;
 pub mod graphql;
 
 use std::collections::HashMap;
@@ -11,26 +12,37 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use execution_core::transfer::Transaction as ProtocolTransaction;
-use node::database::rocksdb::{Backend, DBTransaction};
-use node::database::{Mempool, DB};
+use node::database::rocksdb::{Backend, DBTransaction, MD_HASH_KEY};
+use node::database::{Ledger, Mempool, Metadata, DB};
 use node::network::Kadcast;
 use node::Network;
+use node_data::events::contract::ContractEvent;
 use node_data::ledger::Transaction;
 use node_data::message::Message;
 
-use graphql::{DBContext, Query};
+use gas::BlockFees;
+use graphql::{DBContext, EventsContext, Query, Subscription};
 
-use async_graphql::{
-    EmptyMutation, EmptySubscription, Name, Schema, Variables,
-};
+use async_graphql::http::{WebSocket, WsMessage};
+use async_graphql::{EmptyMutation, Name, Schema, SchemaBuilder, Variables};
 use serde_json::{json, Map, Value};
+use tokio::task;
+use tungstenite::Message as WsFrame;
 
+use super::rusk::{
+    parse_height, RUSK_FEEDER_HEADER, RUSK_HEIGHT_HEADER,
+    RUSK_STATE_ROOT_HEADER,
+};
 use super::*;
 use crate::node::RuskNode;
 use crate::{VERSION, VERSION_BUILD};
 
 const GQL_VAR_PREFIX: &str = "rusk-gqlvar-";
 
+/// Number of archived events fetched at once when replaying them
+#[cfg(feature = "archive")]
+const REPLAY_PAGE_SIZE: u64 = 1000;
+
 fn variables_from_headers(headers: &Map<String, Value>) -> Variables {
     let mut var = Variables::default();
     headers
@@ -51,17 +63,29 @@ fn variables_from_headers(headers: &Map<String, Value>) -> Variables {
 #[async_trait]
 impl HandleRequest for RuskNode {
     fn can_handle(&self, request: &MessageRequest) -> bool {
-        matches!(request.event.to_route(), (Target::Host(_), "Chain", _))
+        match request.event.to_route() {
+            (Target::Contract(_), ..) => {
+                request.header(RUSK_HEIGHT_HEADER).is_some()
+            }
+            (Target::Host(_), "Chain", _) => true,
+            _ => false,
+        }
     }
 
     fn can_handle_rues(&self, request: &RuesDispatchEvent) -> bool {
-        #[allow(clippy::match_like_matches_macro)]
-        match request.uri.inner() {
-            ("graphql", _, "query") => true,
-            ("transactions", _, "propagate") => true,
-            ("network", _, "peers") => true,
-            ("node", _, "info") => true,
-            ("blocks", _, "gas-price") => true,
+        match RuesRoute::parse(&request.uri) {
+            Some(RuesRoute::ContractCall | RuesRoute::ContractBatch) => {
+                request.header(RUSK_HEIGHT_HEADER).is_some()
+            }
+            Some(
+                RuesRoute::GraphqlQuery
+                | RuesRoute::TxPropagate
+                | RuesRoute::TxSimulate
+                | RuesRoute::NetworkPeers
+                | RuesRoute::NodeInfo
+                | RuesRoute::GasPrice
+                | RuesRoute::GasEstimate,
+            ) => true,
             _ => false,
         }
     }
@@ -69,19 +93,45 @@ impl HandleRequest for RuskNode {
         &self,
         request: &RuesDispatchEvent,
     ) -> anyhow::Result<ResponseData> {
-        match request.uri.inner() {
-            ("graphql", _, "query") => {
+        let uri = &request.uri;
+        match RuesRoute::parse(uri) {
+            Some(RuesRoute::ContractCall) => {
+                let feeder = request.header(RUSK_FEEDER_HEADER).is_some();
+                let commit = self.height_commit(&request.headers).await?;
+                let data = request.data.as_bytes();
+                let rusk = self.vm_handler().read().await.clone();
+                rusk.handle_contract_query(
+                    uri.entity.as_deref().unwrap_or_default(),
+                    &uri.topic,
+                    data,
+                    feeder,
+                    Some(commit),
+                )
+            }
+            Some(RuesRoute::ContractBatch) => {
+                let commit = self.height_commit(&request.headers).await?;
+                let rusk = self.vm_handler().read().await.clone();
+                rusk.handle_contract_batch(
+                    request.data.as_bytes(),
+                    Some(commit),
+                )
+                .await
+            }
+            Some(RuesRoute::GraphqlQuery) => {
                 self.handle_gql(&request.data, &request.headers).await
             }
-            ("transactions", _, "propagate") => {
+            Some(RuesRoute::TxPropagate) => {
                 self.propagate_tx(request.data.as_bytes()).await
             }
-            ("network", _, "peers") => {
+            Some(RuesRoute::TxSimulate) => {
+                self.simulate_tx(request.data.as_bytes()).await
+            }
+            Some(RuesRoute::NetworkPeers) => {
                 let amount = request.data.as_string().trim().parse()?;
                 self.alive_nodes(amount).await
             }
-            ("node", _, "info") => self.get_info().await,
-            ("blocks", _, "gas-price") => {
+            Some(RuesRoute::NodeInfo) => self.get_info().await,
+            Some(RuesRoute::GasPrice) => {
                 let max_transactions = request
                     .data
                     .as_string()
@@ -90,6 +140,7 @@ impl HandleRequest for RuskNode {
                     .unwrap_or(usize::MAX);
                 self.get_gas_price(max_transactions).await
             }
+            Some(RuesRoute::GasEstimate) => self.get_gas_estimate().await,
             _ => anyhow::bail!("Unsupported"),
         }
     }
@@ -98,12 +149,25 @@ impl HandleRequest for RuskNode {
         request: &MessageRequest,
     ) -> anyhow::Result<ResponseData> {
         match &request.event.to_route() {
+            (Target::Contract(_), ..) => {
+                let feeder = request.header(RUSK_FEEDER_HEADER).is_some();
+                let commit = self.height_commit(&request.headers).await?;
+                let rusk = self.vm_handler().read().await.clone();
+                rusk.handle_contract_query_legacy(
+                    &request.event,
+                    feeder,
+                    Some(commit),
+                )
+            }
             (Target::Host(_), "Chain", "gql") => {
                 self.handle_gql(&request.event.data, &request.headers).await
             }
             (Target::Host(_), "Chain", "propagate_tx") => {
                 self.propagate_tx(request.event_data()).await
             }
+            (Target::Host(_), "Chain", "simulate_tx") => {
+                self.simulate_tx(request.event_data()).await
+            }
             (Target::Host(_), "Chain", "alive_nodes") => {
                 let amount = request.event.data.as_string().trim().parse()?;
                 self.alive_nodes(amount).await
@@ -119,11 +183,200 @@ impl HandleRequest for RuskNode {
                     .unwrap_or(usize::MAX);
                 self.get_gas_price(max_transactions).await
             }
+            (Target::Host(_), "Chain", "gas_estimate") => {
+                self.get_gas_estimate().await
+            }
             _ => anyhow::bail!("Unsupported"),
         }
     }
+
+    fn can_handle_gql_ws(&self) -> bool {
+        true
+    }
+
+    async fn handle_gql_ws(
+        &self,
+        websocket: HyperWebsocket,
+        protocol: WebSocketProtocols,
+        events: broadcast::Receiver<RuesEvent>,
+        mut shutdown: broadcast::Receiver<Infallible>,
+        quota: Option<ClientQuota>,
+    ) {
+        let stream = match websocket.await {
+            Ok(stream) => stream,
+            Err(_) => return,
+        };
+        let (mut sink, source) = futures_util::StreamExt::split(stream);
+
+        let (quota_exceeded, mut exceeded) = oneshot::channel();
+        let mut quota_exceeded = Some(quota_exceeded);
+
+        // Messages are handed to the GraphQL protocol until the client closes
+        // the connection, an error occurs, or the client exceeds its quota,
+        // each message being charged to it
+        let source = source
+            .take_while(|msg| matches!(msg, Ok(msg) if !msg.is_close()))
+            .filter_map(|msg| match msg {
+                Ok(WsFrame::Text(text)) => Some(text.into_bytes()),
+                Ok(WsFrame::Binary(bin)) => Some(bin),
+                _ => None,
+            })
+            .take_while(move |_| {
+                match quota.as_ref().map(ClientQuota::check) {
+                    Some(Err(retry_after)) => {
+                        if let Some(sender) = quota_exceeded.take() {
+                            let _ = sender.send(retry_after);
+                        }
+                        false
+                    }
+                    _ => true,
+                }
+            });
+
+        let schema = self.gql_schema().data::<EventsContext>(events).finish();
+        let mut messages = WebSocket::new(schema, source, protocol);
+
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => {
+                    let _ = sink.send(WsFrame::Close(Some(CloseFrame {
+                        code: CloseCode::Away,
+                        reason: Cow::from("Shutting down"),
+                    }))).await;
+                    break;
+                }
+                msg = messages.next() => {
+                    let msg = match msg {
+                        Some(WsMessage::Text(text)) => WsFrame::Text(text),
+                        Some(WsMessage::Close(code, reason)) => {
+                            WsFrame::Close(Some(CloseFrame {
+                                code: code.into(),
+                                reason: Cow::from(reason),
+                            }))
+                        }
+                        None => {
+                            if let Ok(retry_after) = exceeded.try_recv() {
+                                let frame = quota_exceeded_frame(retry_after);
+                                let frame = WsFrame::Close(Some(frame));
+                                let _ = sink.send(frame).await;
+                            }
+                            break;
+                        }
+                    };
+                    let is_close = msg.is_close();
+                    if sink.send(msg).await.is_err() || is_close {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "archive")]
+    fn can_replay_rues(&self, uri: &RuesEventUri) -> bool {
+        matches!(uri.inner(), ("contracts", Some(_), _))
+    }
+
+    #[cfg(feature = "archive")]
+    async fn replay_rues(
+        &self,
+        uri: &RuesEventUri,
+        from_height: u64,
+        cursor: Option<String>,
+    ) -> anyhow::Result<(Vec<RuesEvent>, Option<String>)> {
+        use node::database::archive::ArchiveCursor;
+
+        let contract = match uri.inner() {
+            ("contracts", Some(contract), _) => contract.to_lowercase(),
+            _ => anyhow::bail!("Replay is not supported for {uri}"),
+        };
+        let cursor = cursor.map(|c| c.parse::<ArchiveCursor>()).transpose()?;
+
+        let page = self
+            .archive()
+            .fetch_events_by_contract(
+                &contract,
+                Some(&uri.topic),
+                Some((from_height, u64::MAX)),
+                cursor,
+                REPLAY_PAGE_SIZE,
+            )
+            .await?;
+
+        let next = match page.last() {
+            Some((cursor, _)) if page.len() as u64 == REPLAY_PAGE_SIZE => {
+                Some(cursor.to_string())
+            }
+            _ => None,
+        };
+        let events = page
+            .into_iter()
+            .map(|(_, archived)| {
+                let mut event = RuesEvent {
+                    uri: uri.clone(),
+                    headers: Default::default(),
+                    data: hex::decode(archived.data)?.into(),
+                };
+                if let Some(origin) = archived.origin {
+                    event.add_header("Rusk-Origin", origin);
+                }
+                Ok(event)
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok((events, next))
+    }
 }
 impl RuskNode {
+    fn gql_schema(&self) -> SchemaBuilder<Query, EmptyMutation, Subscription> {
+        let limits = self.gql_limits();
+        let schema = Schema::build(Query, EmptyMutation, Subscription)
+            .limit_depth(limits.max_depth)
+            .limit_complexity(limits.max_complexity)
+            .data(limits)
+            .data(self.db());
+        #[cfg(feature = "archive")]
+        let schema = schema.data(self.archive());
+        schema
+    }
+
+    /// Returns the state root of the block at the height requested with the
+    /// `Rusk-Height` header.
+    async fn height_commit(
+        &self,
+        headers: &serde_json::Map<String, Value>,
+    ) -> anyhow::Result<[u8; 32]> {
+        if find_header(headers, RUSK_STATE_ROOT_HEADER).is_some() {
+            anyhow::bail!(
+                "{RUSK_HEIGHT_HEADER} and {RUSK_STATE_ROOT_HEADER} headers \
+                 cannot be used together"
+            );
+        }
+        let height = find_header(headers, RUSK_HEIGHT_HEADER)
+            .map(parse_height)
+            .transpose()?
+            .ok_or_else(|| anyhow::anyhow!("Missing {RUSK_HEIGHT_HEADER}"))?;
+
+        let state_hash = self.db().read().await.view(|t| {
+            let Some(hash) = t.fetch_block_hash_by_height(height)? else {
+                return Ok(None);
+            };
+            Ok(t.fetch_block_header(&hash)?.map(|h| h.state_hash))
+        })?;
+        let state_hash = state_hash
+            .ok_or_else(|| anyhow::anyhow!("No block at height {height}"))?;
+
+        let rusk = self.vm_handler().read().await.clone();
+        if rusk.ensure_commit(state_hash).is_err() {
+            anyhow::bail!(
+                "State at height {height} has been deleted, commit = {}",
+                hex::encode(state_hash)
+            );
+        }
+
+        Ok(state_hash)
+    }
+
     async fn handle_gql(
         &self,
         data: &RequestData,
@@ -131,9 +384,7 @@ impl RuskNode {
     ) -> anyhow::Result<ResponseData> {
         let gql_query = data.as_string();
 
-        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
-            .data(self.db())
-            .finish();
+        let schema = self.gql_schema().finish();
 
         if gql_query.trim().is_empty() {
             return Ok(ResponseData::new(schema.sdl()));
@@ -165,6 +416,52 @@ impl RuskNode {
         Ok(ResponseData::new(DataType::None))
     }
 
+    /// Executes the transaction on top of the current tip, as if it was
+    /// included in the next block, returning the gas spent, the data or the
+    /// error of the call and the emitted events. Nothing is persisted nor
+    /// propagated.
+    ///
+    /// The height and the state the transaction is executed on are both taken
+    /// from the tip header, so that they are consistent with each other.
+    async fn simulate_tx(&self, tx: &[u8]) -> anyhow::Result<ResponseData> {
+        let tx = ProtocolTransaction::from_slice(tx)
+            .map_err(|e| anyhow::anyhow!("Invalid Data {e:?}"))?;
+
+        let tip = self.db().read().await.view(|t| {
+            let header = match t.op_read(MD_HASH_KEY)? {
+                Some(hash) => t.fetch_block_header(&hash)?,
+                None => None,
+            };
+            header.ok_or_else(|| anyhow::anyhow!("Cannot find last block"))
+        })?;
+
+        let rusk = self.vm_handler().read().await.clone();
+        let receipt = task::spawn_blocking(move || {
+            rusk.simulate_transaction(&tx, tip.height + 1, Some(tip.state_hash))
+        })
+        .await?
+        .map_err(|e| anyhow::anyhow!("Unspendable transaction: {e}"))?;
+
+        let (data, error) = match receipt.data {
+            Ok(data) => (Some(hex::encode(data)), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        let events: Vec<_> = receipt
+            .events
+            .into_iter()
+            .map(ContractEvent::from)
+            .collect();
+
+        let simulation = json!({
+            "gas_spent": receipt.gas_spent,
+            "gas_limit": receipt.gas_limit,
+            "data": data,
+            "error": error,
+            "events": events,
+        });
+        Ok(ResponseData::new(simulation))
+    }
+
     async fn alive_nodes(&self, amount: usize) -> anyhow::Result<ResponseData> {
         let nodes = self.network().read().await.alive_nodes(amount).await;
         let nodes: Vec<_> = nodes.iter().map(|n| n.to_string()).collect();
@@ -247,4 +544,55 @@ impl RuskNode {
 
         Ok(ResponseData::new(serde_json::to_value(stats)?))
     }
+
+    /// Suggests the gas price to pay for a transaction to be included in the
+    /// next block, or within a few blocks, based on the fees paid in the
+    /// recent blocks and on the backlog of the mempool.
+    async fn get_gas_estimate(&self) -> anyhow::Result<ResponseData> {
+        let estimate = self.db().read().await.view(|t| {
+            let tip = match t.op_read(MD_HASH_KEY)? {
+                Some(hash) => t.fetch_block_header(&hash)?,
+                None => None,
+            };
+            let tip =
+                tip.ok_or_else(|| anyhow::anyhow!("Cannot find last block"))?;
+
+            // Pruned blocks are not taken into account
+            let from = tip.height.saturating_sub(gas::RECENT_BLOCKS - 1);
+            let mut blocks = vec![];
+            for height in from..=tip.height {
+                let Some(block) = t.fetch_block_by_height(height)? else {
+                    continue;
+                };
+                let mut txs = vec![];
+                for tx in block.txs() {
+                    if let Some(spent) = t.get_ledger_tx_by_hash(&tx.id())? {
+                        txs.push((spent.inner.gas_price(), spent.gas_spent));
+                    }
+                }
+                blocks.push(BlockFees {
+                    txs,
+                    gas_limit: block.header().gas_limit,
+                });
+            }
+
+            // The mempool is only scanned as far as it would fill the blocks
+            // of the slowest suggestion
+            let capacity = tip.gas_limit.saturating_mul(gas::SLOW_BLOCKS);
+            let mut mempool = vec![];
+            let mut backlog = 0u64;
+            for tx in t.get_txs_sorted_by_fee()? {
+                let gas_limit = tx.inner.gas_limit();
+                mempool.push((tx.gas_price(), gas_limit));
+                backlog = backlog.saturating_add(gas_limit);
+                if backlog > capacity {
+                    break;
+                }
+            }
+
+            anyhow::Ok(gas::estimate(&blocks, &mempool, tip.gas_limit))
+        })?;
+
+        Ok(ResponseData::new(serde_json::to_value(estimate)?))
+    }
 }
@@ -0,0 +1,185 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Estimation of the gas price a transaction should pay to be included
+//! within a given number of blocks.
+
+use serde::Serialize;
+
+/// Number of recent blocks the estimation is based on
+pub const RECENT_BLOCKS: u64 = 20;
+
+/// Number of blocks within which the cheapest suggestion is included
+pub const SLOW_BLOCKS: u64 = 5;
+
+/// Utilisation of the block gas limit, in percent, from which a block is
+/// considered full
+const FULL_BLOCK_UTILISATION: u64 = 80;
+
+const MIN_GAS_PRICE: u64 = 1;
+
+/// Fees paid by the transactions included in a block
+pub struct BlockFees {
+    /// Gas price and gas spent of each transaction
+    pub txs: Vec<(u64, u64)>,
+    pub gas_limit: u64,
+}
+
+impl BlockFees {
+    fn gas_spent(&self) -> u64 {
+        self.txs.iter().map(|(_, gas_spent)| gas_spent).sum()
+    }
+
+    /// Lowest gas price needed to be included in the block. Any price would
+    /// have done if the block was not full.
+    fn cutoff_price(&self) -> u64 {
+        let is_full = self.gas_spent().saturating_mul(100)
+            >= self.gas_limit.saturating_mul(FULL_BLOCK_UTILISATION);
+        match is_full {
+            true => self.txs.iter().map(|(price, _)| *price).min(),
+            false => None,
+        }
+        .unwrap_or(MIN_GAS_PRICE)
+    }
+}
+
+/// Percentiles of the gas prices paid in recent blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Percentiles {
+    pub p10: u64,
+    pub p25: u64,
+    pub p50: u64,
+    pub p75: u64,
+    pub p90: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GasEstimate {
+    /// Gas price suggested to be included in the next block
+    pub next_block: u64,
+    /// Gas price suggested to be included within the next `SLOW_BLOCKS`
+    /// blocks
+    pub within_few_blocks: u64,
+    pub percentiles: Percentiles,
+    /// Average utilisation of the block gas limit in recent blocks, in
+    /// percent
+    pub utilisation: u64,
+}
+
+/// Estimates the gas price to pay from the fees of the recent blocks and the
+/// transactions in the mempool, as gas price and gas limit sorted by
+/// descending gas price.
+///
+/// Each suggestion is the highest between the price needed to outbid the
+/// mempool backlog filling the blocks before inclusion, and a percentile of
+/// the lowest prices that made it into the recent blocks.
+pub fn estimate(
+    blocks: &[BlockFees],
+    mempool: &[(u64, u64)],
+    block_gas_limit: u64,
+) -> GasEstimate {
+    let mut cutoffs: Vec<_> =
+        blocks.iter().map(BlockFees::cutoff_price).collect();
+    cutoffs.sort_unstable();
+
+    let mut prices: Vec<_> = blocks
+        .iter()
+        .flat_map(|b| b.txs.iter().map(|(price, _)| *price))
+        .collect();
+    prices.sort_unstable();
+
+    let backlog_price = |blocks: u64| {
+        let capacity = block_gas_limit.saturating_mul(blocks);
+        let mut gas = 0u64;
+        for (price, gas_limit) in mempool {
+            gas = gas.saturating_add(*gas_limit);
+            if gas > capacity {
+                return price.saturating_add(1);
+            }
+        }
+        MIN_GAS_PRICE
+    };
+
+    let utilisation = match blocks.len() as u64 {
+        0 => 0,
+        n => {
+            let total: u64 = blocks
+                .iter()
+                .map(|b| b.gas_spent().saturating_mul(100) / b.gas_limit.max(1))
+                .sum();
+            total / n
+        }
+    };
+
+    GasEstimate {
+        next_block: backlog_price(1).max(percentile(&cutoffs, 75)),
+        within_few_blocks: backlog_price(SLOW_BLOCKS)
+            .max(percentile(&cutoffs, 25)),
+        percentiles: Percentiles {
+            p10: percentile(&prices, 10),
+            p25: percentile(&prices, 25),
+            p50: percentile(&prices, 50),
+            p75: percentile(&prices, 75),
+            p90: percentile(&prices, 90),
+        },
+        utilisation,
+    }
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied().unwrap_or(MIN_GAS_PRICE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAS_LIMIT: u64 = 1000;
+
+    #[test]
+    fn empty_chain() {
+        let estimate = estimate(&[], &[], GAS_LIMIT);
+
+        assert_eq!(estimate.next_block, MIN_GAS_PRICE);
+        assert_eq!(estimate.within_few_blocks, MIN_GAS_PRICE);
+        assert_eq!(estimate.percentiles.p90, MIN_GAS_PRICE);
+        assert_eq!(estimate.utilisation, 0);
+    }
+
+    #[test]
+    fn full_blocks() {
+        let blocks: Vec<_> = (1..=4)
+            .map(|i| BlockFees {
+                txs: vec![(10 * i, 500), (20 * i, 400)],
+                gas_limit: GAS_LIMIT,
+            })
+            .collect();
+        let estimate = estimate(&blocks, &[], GAS_LIMIT);
+
+        // The cutoff prices are 10, 20, 30 and 40
+        assert_eq!(estimate.next_block, 30);
+        assert_eq!(estimate.within_few_blocks, 10);
+        assert_eq!(estimate.percentiles.p50, 30);
+        assert_eq!(estimate.utilisation, 90);
+    }
+
+    #[test]
+    fn mempool_backlog() {
+        let blocks = [BlockFees {
+            txs: vec![(50, 100)],
+            gas_limit: GAS_LIMIT,
+        }];
+        let mempool = [(7, 800), (5, 800), (3, 4000)];
+        let estimate = estimate(&blocks, &mempool, GAS_LIMIT);
+
+        // The block was not full, so only the backlog counts
+        assert_eq!(estimate.next_block, 6);
+        assert_eq!(estimate.within_few_blocks, 4);
+        assert_eq!(estimate.percentiles.p10, 50);
+    }
+}
@@ -4,14 +4,23 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+#[cfg(feature = "archive")]
+mod archive;
 mod block;
 mod data;
+mod page;
+mod subscription;
 mod tx;
 
+#[cfg(feature = "archive")]
+use archive::*;
 use block::*;
 use data::*;
+use page::*;
 use tx::*;
 
+pub use subscription::{EventsContext, Subscription};
+
 use async_graphql::{Context, FieldError, FieldResult, Object};
 use execution_core::{transfer::TRANSFER_CONTRACT, ContractId};
 use node::database::rocksdb::Backend;
@@ -20,6 +29,8 @@ use node::database::{Ledger, DB};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use crate::http::GqlLimits;
+
 pub type DBContext = Arc<RwLock<Backend>>;
 pub type OptResult<T> = FieldResult<Option<T>>;
 
@@ -64,16 +75,38 @@ impl Query {
         range: Option<[u64; 2]>,
         contract: Option<String>,
     ) -> FieldResult<Vec<SpentTransaction>> {
-        let blocks = self.blocks(ctx, last, range).await?;
+        let contract = contract.as_deref().map(parse_contract).transpose()?;
+
+        // Use the transactions index, if enabled, instead of scanning the
+        // whole range of blocks, unless some of them were stored before the
+        // index was enabled
+        if let (None, Some(contract), Some([from, to])) =
+            (last, contract, range)
+        {
+            let limits = ctx.data::<GqlLimits>()?;
+            limits.check_range(from, to)?;
+            let limit = limits.item_limit(None) as usize;
 
-        let contract = match contract {
-            Some(contract) => {
-                let mut decoded = [0u8; 32];
-                decoded.copy_from_slice(&hex::decode(contract)?[..]);
-                Some(ContractId::from(decoded))
+            let db = ctx.data::<DBContext>()?;
+            let txs = db.read().await.view(|t| {
+                match t.fetch_tx_index_height()? {
+                    Some(index_height) if index_height <= from => {
+                        let ids = t.fetch_tx_ids_by_contract(
+                            &contract,
+                            (from, to),
+                            limit,
+                        )?;
+                        indexed_txs(&t, ids).map(Some)
+                    }
+                    _ => Ok(None),
+                }
+            })?;
+            if let Some(txs) = txs {
+                return Ok(txs);
             }
-            _ => None,
-        };
+        }
+
+        let blocks = self.blocks(ctx, last, range).await?;
 
         let mut txs = vec![];
         for b in blocks.iter() {
@@ -102,6 +135,34 @@ impl Query {
         Ok(txs)
     }
 
+    /// Ledger transactions sent or received by the Moonlight account with
+    /// the given base58 encoded public key, if the transactions index is
+    /// enabled. The range must not start below the height the index was
+    /// enabled at
+    async fn account_txs(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+        range: Option<[u64; 2]>,
+        limit: Option<u64>,
+    ) -> FieldResult<Vec<SpentTransaction>> {
+        txs_by_account(ctx, address, range, limit).await
+    }
+
+    /// Ledger transactions calling the contract with the given hex encoded
+    /// id, if the transactions index is enabled. The range must not start
+    /// below the height the index was enabled at
+    async fn contract_txs(
+        &self,
+        ctx: &Context<'_>,
+        contract: String,
+        range: Option<[u64; 2]>,
+        limit: Option<u64>,
+    ) -> FieldResult<Vec<SpentTransaction>> {
+        let contract = parse_contract(&contract)?;
+        txs_by_contract(ctx, contract, range, limit).await
+    }
+
     async fn blocks(
         &self,
         ctx: &Context<'_>,
@@ -122,6 +183,50 @@ impl Query {
         mempool(ctx).await
     }
 
+    /// Pages through the blocks, sorted by height
+    async fn blocks_connection(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> FieldResult<BlockConnection> {
+        blocks_connection(ctx, after, before, first, last).await
+    }
+
+    /// Pages through the ledger transactions, sorted by block height and
+    /// position within the block, optionally within an inclusive range of
+    /// block heights
+    #[allow(clippy::too_many_arguments)]
+    async fn txs_connection(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+        range: Option<[u64; 2]>,
+        filter: Option<TxFilter>,
+    ) -> FieldResult<TxConnection> {
+        txs_connection(ctx, after, before, first, last, range, filter).await
+    }
+
+    /// Pages through the mempool transactions, sorted by fee. The total count
+    /// is the number of transactions in the mempool, regardless of the
+    /// filter.
+    async fn mempool_connection(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+        filter: Option<MempoolFilter>,
+    ) -> FieldResult<MempoolConnection> {
+        mempool_connection(ctx, after, before, first, last, filter).await
+    }
+
     async fn mempool_tx(
         &self,
         ctx: &Context<'_>,
@@ -129,4 +234,55 @@ impl Query {
     ) -> OptResult<Transaction> {
         mempool_by_hash(ctx, hash).await
     }
+
+    /// Contract events emitted by the transaction with the given hash
+    #[cfg(feature = "archive")]
+    async fn tx_events(
+        &self,
+        ctx: &Context<'_>,
+        hash: String,
+    ) -> FieldResult<Vec<ContractEvent>> {
+        events_by_tx(ctx, hash).await
+    }
+
+    /// Events emitted by a contract, optionally filtered by topic and by an
+    /// inclusive range of block heights, sorted by block height
+    #[cfg(feature = "archive")]
+    async fn contract_events(
+        &self,
+        ctx: &Context<'_>,
+        contract: String,
+        topic: Option<String>,
+        range: Option<[u64; 2]>,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> FieldResult<ContractEvents> {
+        events_by_contract(ctx, contract, topic, range, cursor, limit).await
+    }
+
+    /// Movements of funds to and from a Moonlight account, in finalized
+    /// blocks. The account is the base58 encoded public key
+    #[cfg(feature = "archive")]
+    async fn account_history(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+        from: Option<u64>,
+        to: Option<u64>,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> FieldResult<AccountHistory> {
+        account_history(ctx, address, from, to, cursor, limit).await
+    }
+
+    /// Lifecycle events of a transaction in the mempool, as recorded by the
+    /// archive
+    #[cfg(feature = "archive")]
+    async fn mempool_history(
+        &self,
+        ctx: &Context<'_>,
+        hash: String,
+    ) -> FieldResult<Vec<MempoolEvent>> {
+        mempool_history(ctx, hash).await
+    }
 }
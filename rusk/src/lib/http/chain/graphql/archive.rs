@@ -0,0 +1,218 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use async_graphql::SimpleObject;
+use node::database::archive::{
+    ArchiveCursor, ArchivedEvent, MempoolHistoryEntry, MoonlightHistoryEntry,
+    SQLiteArchive,
+};
+
+use super::*;
+
+/// Maximum number of account history entries returned at once
+const MAX_ACCOUNT_HISTORY_PAGE: u64 = 1000;
+
+#[derive(SimpleObject)]
+pub struct MempoolEvent {
+    /// One of `included`, `replaced`, `expired`, `evicted` or `discarded`
+    event: String,
+    /// Hash of the replacing transaction, for `replaced` events
+    replaced_by: Option<String>,
+    /// Reason why the transaction has been discarded, for `discarded` events
+    reason: Option<String>,
+    /// Unix timestamp of the event, in seconds
+    timestamp: u64,
+}
+
+impl From<MempoolHistoryEntry> for MempoolEvent {
+    fn from(entry: MempoolHistoryEntry) -> Self {
+        Self {
+            event: entry.event,
+            replaced_by: entry.replaced_by,
+            reason: entry.reason,
+            timestamp: entry.timestamp,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ContractEvent {
+    block_height: u64,
+    block_hash: String,
+    /// Hash of the transaction that emitted the event, if any
+    origin: Option<String>,
+    /// Contract that emitted the event
+    target: String,
+    topic: String,
+    /// Hex encoded event data
+    data: String,
+}
+
+impl From<ArchivedEvent> for ContractEvent {
+    fn from(event: ArchivedEvent) -> Self {
+        Self {
+            block_height: event.block_height,
+            block_hash: event.block_hash,
+            origin: event.origin,
+            target: event.target,
+            topic: event.topic,
+            data: event.data,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ContractEvents {
+    events: Vec<ContractEvent>,
+    /// Cursor to fetch the following events, if there may be any
+    next_cursor: Option<String>,
+}
+
+#[derive(SimpleObject)]
+pub struct AccountHistoryEntry {
+    block_height: u64,
+    /// Hash of the transaction that moved the funds, if any
+    tx_hash: Option<String>,
+    /// Topic of the transfer contract event that moved the funds
+    event: String,
+    /// Either `in` for incoming or `out` for outgoing funds
+    direction: String,
+    value: u64,
+    /// Fee paid by the account, in LUX
+    fee: u64,
+}
+
+impl From<MoonlightHistoryEntry> for AccountHistoryEntry {
+    fn from(entry: MoonlightHistoryEntry) -> Self {
+        Self {
+            block_height: entry.block_height,
+            tx_hash: entry.tx_hash,
+            event: entry.event,
+            direction: entry.direction,
+            value: entry.value,
+            fee: entry.fee,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct AccountHistory {
+    entries: Vec<AccountHistoryEntry>,
+    /// Cursor to fetch the following entries, if there may be any
+    next_cursor: Option<String>,
+}
+
+pub async fn account_history(
+    ctx: &Context<'_>,
+    address: String,
+    from: Option<u64>,
+    to: Option<u64>,
+    cursor: Option<String>,
+    limit: Option<u64>,
+) -> FieldResult<AccountHistory> {
+    let archive = ctx.data::<SQLiteArchive>()?;
+
+    let range = match (from, to) {
+        (None, None) => None,
+        (from, to) => Some((from.unwrap_or_default(), to.unwrap_or(u64::MAX))),
+    };
+    let cursor = cursor
+        .map(|c| c.parse::<ArchiveCursor>())
+        .transpose()
+        .map_err(|_| FieldError::new("Invalid cursor"))?;
+    let limit = limit
+        .unwrap_or(MAX_ACCOUNT_HISTORY_PAGE)
+        .clamp(1, MAX_ACCOUNT_HISTORY_PAGE);
+
+    let history = archive
+        .fetch_moonlight_history(&address, range, cursor, limit)
+        .await?;
+
+    let next_cursor = match history.last() {
+        Some((cursor, _)) if history.len() as u64 == limit => {
+            Some(cursor.to_string())
+        }
+        _ => None,
+    };
+    let entries = history
+        .into_iter()
+        .map(|(_, entry)| AccountHistoryEntry::from(entry))
+        .collect();
+
+    Ok(AccountHistory {
+        entries,
+        next_cursor,
+    })
+}
+
+pub async fn events_by_tx(
+    ctx: &Context<'_>,
+    hash: String,
+) -> FieldResult<Vec<ContractEvent>> {
+    let limit = ctx.data::<GqlLimits>()?.item_limit(None);
+    let archive = ctx.data::<SQLiteArchive>()?;
+    let events = archive
+        .fetch_events_by_tx(&hash.to_lowercase(), limit)
+        .await?;
+    Ok(events.into_iter().map(ContractEvent::from).collect())
+}
+
+pub async fn events_by_contract(
+    ctx: &Context<'_>,
+    contract: String,
+    topic: Option<String>,
+    range: Option<[u64; 2]>,
+    cursor: Option<String>,
+    limit: Option<u64>,
+) -> FieldResult<ContractEvents> {
+    let limits = ctx.data::<GqlLimits>()?;
+    if let Some([from, to]) = range {
+        limits.check_range(from, to)?;
+    }
+    let limit = limits.item_limit(limit).max(1);
+
+    let archive = ctx.data::<SQLiteArchive>()?;
+    let range = range.map(|[from, to]| (from, to));
+    let cursor = cursor
+        .map(|c| c.parse::<ArchiveCursor>())
+        .transpose()
+        .map_err(|_| FieldError::new("Invalid cursor"))?;
+
+    let events = archive
+        .fetch_events_by_contract(
+            &contract.to_lowercase(),
+            topic.as_deref(),
+            range,
+            cursor,
+            limit,
+        )
+        .await?;
+
+    let next_cursor = match events.last() {
+        Some((cursor, _)) if events.len() as u64 == limit => {
+            Some(cursor.to_string())
+        }
+        _ => None,
+    };
+    let events = events
+        .into_iter()
+        .map(|(_, event)| ContractEvent::from(event))
+        .collect();
+
+    Ok(ContractEvents {
+        events,
+        next_cursor,
+    })
+}
+
+pub async fn mempool_history(
+    ctx: &Context<'_>,
+    hash: String,
+) -> FieldResult<Vec<MempoolEvent>> {
+    let archive = ctx.data::<SQLiteArchive>()?;
+    let history = archive.fetch_mempool_history(&hash.to_lowercase()).await?;
+    Ok(history.into_iter().map(MempoolEvent::from).collect())
+}
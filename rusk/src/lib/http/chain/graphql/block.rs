@@ -60,6 +60,7 @@ pub async fn last_blocks(
     if (count < 1) {
         return Err(FieldError::new("count must be positive"));
     }
+    ctx.data::<GqlLimits>()?.check_count(count)?;
     let db = ctx.data::<DBContext>()?;
     let last_block = last_block(ctx).await?;
     let mut hash_to_search = last_block.header().prev_block_hash;
@@ -86,6 +87,7 @@ pub async fn blocks_range(
     from: u64,
     to: u64,
 ) -> FieldResult<Vec<Block>> {
+    ctx.data::<GqlLimits>()?.check_range(from, to)?;
     let db = ctx.data::<DBContext>()?;
     let mut blocks = db.read().await.view(|t| {
         let mut blocks = vec![];
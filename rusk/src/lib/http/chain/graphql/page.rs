@@ -0,0 +1,498 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Relay-style connections over the blocks and transactions.
+
+use async_graphql::connection::{query, Connection, CursorType, Edge};
+use async_graphql::{Enum, InputObject, SimpleObject};
+use dusk_bytes::DeserializableSlice;
+use execution_core::signatures::bls::PublicKey as AccountPublicKey;
+use execution_core::transfer::Transaction as ProtocolTransaction;
+use node::database::rocksdb::MD_HASH_KEY;
+use node::database::{Mempool, Metadata};
+
+use super::*;
+
+/// Maximum number of blocks returned in a page
+const MAX_BLOCKS_PAGE: usize = 100;
+
+/// Maximum number of transactions returned in a page.
+///
+/// At most [`GqlLimits::max_range`] blocks are scanned to fill a page of
+/// transactions. If it is reached the page may hold fewer transactions than
+/// requested, and a cursor past the last scanned block is returned to page
+/// from.
+const MAX_TXS_PAGE: usize = 100;
+
+pub type BlockConnection = Connection<HeightCursor, Block, TotalCount>;
+pub type TxConnection = Connection<TxCursor, SpentTransaction, ScanCursor>;
+pub type MempoolConnection<'a> =
+    Connection<MempoolCursor, Transaction<'a>, TotalCount>;
+
+#[derive(SimpleObject)]
+pub struct TotalCount {
+    total_count: u64,
+}
+
+#[derive(SimpleObject, Default)]
+pub struct ScanCursor {
+    /// Cursor past the last scanned block, set if the page was cut short by
+    /// the maximum number of scanned blocks
+    scan_cursor: Option<String>,
+}
+
+/// Cursor of a block, being its height
+#[derive(Clone, Copy)]
+pub struct HeightCursor(u64);
+
+impl CursorType for HeightCursor {
+    type Error = std::num::ParseIntError;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        s.parse().map(Self)
+    }
+
+    fn encode_cursor(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Cursor of a ledger transaction, being the height of its block and its
+/// position within it
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TxCursor {
+    height: u64,
+    index: usize,
+}
+
+impl CursorType for TxCursor {
+    type Error = &'static str;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        let (height, index) = s.split_once(':').ok_or("Invalid cursor")?;
+        Ok(Self {
+            height: height.parse().map_err(|_| "Invalid cursor height")?,
+            index: index.parse().map_err(|_| "Invalid cursor index")?,
+        })
+    }
+
+    fn encode_cursor(&self) -> String {
+        format!("{}:{}", self.height, self.index)
+    }
+}
+
+/// Cursor of a mempool transaction, being its gas price and its hash, as
+/// sorted in the mempool fees index
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MempoolCursor {
+    gas_price: u64,
+    id: [u8; 32],
+}
+
+impl CursorType for MempoolCursor {
+    type Error = &'static str;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        let (gas_price, id) = s.split_once(':').ok_or("Invalid cursor")?;
+        let id = hex::decode(id).map_err(|_| "Invalid cursor hash")?;
+        Ok(Self {
+            gas_price: gas_price
+                .parse()
+                .map_err(|_| "Invalid cursor gas price")?,
+            id: id.try_into().map_err(|_| "Invalid cursor hash")?,
+        })
+    }
+
+    fn encode_cursor(&self) -> String {
+        format!("{}:{}", self.gas_price, hex::encode(self.id))
+    }
+}
+
+#[derive(Enum, Clone, Copy, PartialEq, Eq)]
+pub enum TxStatus {
+    Success,
+    Error,
+}
+
+#[derive(InputObject, Default)]
+pub struct TxFilter {
+    /// Hex encoded id of the called contract
+    contract: Option<String>,
+    status: Option<TxStatus>,
+    /// Inclusive range of gas spent
+    gas_spent: Option<[u64; 2]>,
+    /// Base58 encoded public key of the Moonlight sender
+    moonlight_sender: Option<String>,
+}
+
+#[derive(InputObject, Default)]
+pub struct MempoolFilter {
+    /// Hex encoded id of the called contract
+    contract: Option<String>,
+    /// Base58 encoded public key of the Moonlight sender
+    moonlight_sender: Option<String>,
+}
+
+/// Decoded transaction filter
+#[derive(Default)]
+struct TxPredicate {
+    contract: Option<ContractId>,
+    status: Option<TxStatus>,
+    gas_spent: Option<[u64; 2]>,
+    moonlight_sender: Option<AccountPublicKey>,
+}
+
+impl TryFrom<TxFilter> for TxPredicate {
+    type Error = FieldError;
+
+    fn try_from(filter: TxFilter) -> FieldResult<Self> {
+        Ok(Self {
+            contract: filter
+                .contract
+                .as_deref()
+                .map(parse_contract)
+                .transpose()?,
+            status: filter.status,
+            gas_spent: filter.gas_spent,
+            moonlight_sender: filter
+                .moonlight_sender
+                .map(decode_account)
+                .transpose()?,
+        })
+    }
+}
+
+impl TryFrom<MempoolFilter> for TxPredicate {
+    type Error = FieldError;
+
+    fn try_from(filter: MempoolFilter) -> FieldResult<Self> {
+        TxFilter {
+            contract: filter.contract,
+            moonlight_sender: filter.moonlight_sender,
+            ..Default::default()
+        }
+        .try_into()
+    }
+}
+
+impl TxPredicate {
+    fn matches(&self, tx: &ProtocolTransaction) -> bool {
+        if let Some(contract) = &self.contract {
+            let tx_contract =
+                tx.call().map(|c| c.contract).unwrap_or(TRANSFER_CONTRACT);
+            if tx_contract != *contract {
+                return false;
+            }
+        }
+        if let Some(sender) = &self.moonlight_sender {
+            match tx {
+                ProtocolTransaction::Moonlight(m)
+                    if m.from_account() == *sender => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    fn matches_spent(&self, tx: &node_data::ledger::SpentTransaction) -> bool {
+        if let Some(status) = self.status {
+            let tx_status = match tx.err {
+                Some(_) => TxStatus::Error,
+                None => TxStatus::Success,
+            };
+            if tx_status != status {
+                return false;
+            }
+        }
+        if let Some([min, max]) = self.gas_spent {
+            if tx.gas_spent < min || tx.gas_spent > max {
+                return false;
+            }
+        }
+        self.matches(&tx.inner.inner)
+    }
+}
+
+fn decode_account(address: String) -> FieldResult<AccountPublicKey> {
+    let bytes = bs58::decode(address).into_vec()?;
+    AccountPublicKey::from_slice(&bytes)
+        .map_err(|_| FieldError::new("Invalid account"))
+}
+
+fn page_size(first: Option<usize>, last: Option<usize>, max: usize) -> usize {
+    first.or(last).unwrap_or(max).min(max)
+}
+
+fn tip_height<T: Ledger + Metadata>(t: &T) -> FieldResult<u64> {
+    let header = match t.op_read(MD_HASH_KEY)? {
+        Some(hash) => t.fetch_block_header(&hash)?,
+        None => None,
+    };
+    header
+        .map(|h| h.height)
+        .ok_or_else(|| FieldError::new("Cannot find last block"))
+}
+
+pub async fn blocks_connection(
+    ctx: &Context<'_>,
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+) -> FieldResult<BlockConnection> {
+    let db = ctx.data::<DBContext>()?;
+    let tip = db.read().await.view(|t| tip_height(&t))?;
+
+    query(
+        after,
+        before,
+        first,
+        last,
+        |after: Option<HeightCursor>,
+         before: Option<HeightCursor>,
+         first,
+         last| async move {
+            let start = after.map_or(0, |c| c.0.saturating_add(1));
+            let end = before.map_or(tip, |c| c.0.saturating_sub(1).min(tip));
+            let size = page_size(first, last, MAX_BLOCKS_PAGE) as u64;
+
+            let mut connection = Connection::with_additional_fields(
+                false,
+                false,
+                TotalCount {
+                    total_count: tip + 1,
+                },
+            );
+            if size == 0 || start > end || before.is_some_and(|c| c.0 == 0) {
+                return Ok::<_, FieldError>(connection);
+            }
+
+            let (from, to) = match last {
+                Some(_) => (end.saturating_sub(size - 1).max(start), end),
+                None => (start, start.saturating_add(size - 1).min(end)),
+            };
+            connection.has_previous_page = from > 0;
+            connection.has_next_page = to < tip;
+
+            let blocks = blocks_range(ctx, from, to).await?;
+            connection.edges.extend(
+                blocks
+                    .into_iter()
+                    .map(|b| Edge::new(HeightCursor(b.header().height), b)),
+            );
+
+            Ok(connection)
+        },
+    )
+    .await
+}
+
+pub async fn txs_connection(
+    ctx: &Context<'_>,
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+    range: Option<[u64; 2]>,
+    filter: Option<TxFilter>,
+) -> FieldResult<TxConnection> {
+    let predicate = TxPredicate::try_from(filter.unwrap_or_default())?;
+    let max_scanned = ctx.data::<GqlLimits>()?.max_range;
+    let db = ctx.data::<DBContext>()?;
+
+    query(
+        after,
+        before,
+        first,
+        last,
+        |after: Option<TxCursor>,
+         before: Option<TxCursor>,
+         first,
+         last| async move {
+            let size = page_size(first, last, MAX_TXS_PAGE);
+            let forward = last.is_none();
+
+            db.read().await.view(|t| {
+                let tip = tip_height(&t)?;
+                let [from, to] = range.unwrap_or([0, tip]);
+                let start = from.max(after.map_or(0, |c| c.height));
+                let end = to.min(tip).min(before.map_or(tip, |c| c.height));
+
+                let in_bounds = |cursor: &TxCursor| {
+                    after.map_or(true, |a| *cursor > a)
+                        && before.map_or(true, |b| *cursor < b)
+                };
+
+                let heights: Box<dyn Iterator<Item = u64>> = if forward {
+                    Box::new(start..=end)
+                } else {
+                    Box::new((start..=end).rev())
+                };
+
+                // One more transaction than requested is looked for, to know
+                // if there are more to page through
+                let mut txs = vec![];
+                let mut more = false;
+                let mut last_scanned = None;
+                let mut scan_cut = false;
+                for (scanned, height) in heights.enumerate() {
+                    if txs.len() > size {
+                        more = true;
+                        break;
+                    }
+                    if scanned as u64 >= max_scanned {
+                        more = true;
+                        scan_cut = true;
+                        break;
+                    }
+                    last_scanned = Some(height);
+                    let block = match t.fetch_block_hash_by_height(height)? {
+                        Some(hash) => t.fetch_light_block(&hash)?,
+                        None => None,
+                    };
+                    let Some(block) = block else {
+                        continue;
+                    };
+
+                    let mut ids: Vec<_> =
+                        block.transactions_ids.iter().enumerate().collect();
+                    if !forward {
+                        ids.reverse();
+                    }
+                    for (index, id) in ids {
+                        let cursor = TxCursor { height, index };
+                        if !in_bounds(&cursor) {
+                            continue;
+                        }
+                        // Transactions of pruned blocks are not available
+                        // anymore
+                        let Some(tx) = t.get_ledger_tx_by_hash(id)? else {
+                            continue;
+                        };
+                        if predicate.matches_spent(&tx) {
+                            txs.push(Edge::new(cursor, SpentTransaction(tx)));
+                        }
+                    }
+                }
+                more |= txs.len() > size;
+                txs.truncate(size);
+
+                // A page cut short by the scan limit holds all the matching
+                // transactions of the scanned blocks, so paging resumes past
+                // the last of them
+                let scan_cursor =
+                    last_scanned.filter(|_| scan_cut).map(|height| {
+                        let index = if forward { usize::MAX } else { 0 };
+                        TxCursor { height, index }.encode_cursor()
+                    });
+                let fields = ScanCursor { scan_cursor };
+
+                let mut connection = if forward {
+                    Connection::with_additional_fields(
+                        after.is_some(),
+                        more,
+                        fields,
+                    )
+                } else {
+                    txs.reverse();
+                    Connection::with_additional_fields(
+                        more,
+                        before.is_some(),
+                        fields,
+                    )
+                };
+                connection.edges = txs;
+
+                Ok::<_, FieldError>(connection)
+            })
+        },
+    )
+    .await
+}
+
+pub async fn mempool_connection<'a>(
+    ctx: &Context<'_>,
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+    filter: Option<MempoolFilter>,
+) -> FieldResult<MempoolConnection<'a>> {
+    let predicate = TxPredicate::try_from(filter.unwrap_or_default())?;
+    let db = ctx.data::<DBContext>()?;
+
+    query(
+        after,
+        before,
+        first,
+        last,
+        |after: Option<MempoolCursor>,
+         before: Option<MempoolCursor>,
+         first,
+         last| async move {
+            let size = page_size(first, last, MAX_TXS_PAGE);
+            let forward = last.is_none();
+
+            db.read().await.view(|t| {
+                // The fees index is walked from the highest fee when paging
+                // forward, from the lowest otherwise, and only the
+                // transactions within the cursors are decoded
+                let ids = if forward {
+                    t.get_txs_ids_sorted_by_fee()?
+                } else {
+                    t.get_txs_ids_sorted_by_low_fee()?
+                };
+                let in_bounds = |cursor: &MempoolCursor| {
+                    after.map_or(true, |a| *cursor < a)
+                        && before.map_or(true, |b| *cursor > b)
+                };
+
+                // One more transaction than requested is looked for, to know
+                // if there are more to page through
+                let mut txs = vec![];
+                for (gas_price, id) in ids {
+                    if txs.len() > size {
+                        break;
+                    }
+                    let cursor = MempoolCursor { gas_price, id };
+                    if !in_bounds(&cursor) {
+                        continue;
+                    }
+                    let Some(tx) = t.get_tx(id)? else {
+                        continue;
+                    };
+                    if predicate.matches(&tx.inner) {
+                        txs.push(Edge::new(cursor, Transaction::from(tx)));
+                    }
+                }
+                let more = txs.len() > size;
+                txs.truncate(size);
+
+                let total_count = TotalCount {
+                    total_count: t.txs_count() as u64,
+                };
+                let mut connection = if forward {
+                    Connection::with_additional_fields(
+                        after.is_some(),
+                        more,
+                        total_count,
+                    )
+                } else {
+                    txs.reverse();
+                    Connection::with_additional_fields(
+                        more,
+                        before.is_some(),
+                        total_count,
+                    )
+                };
+                connection.edges = txs;
+
+                Ok::<_, FieldError>(connection)
+            })
+        },
+    )
+    .await
+}
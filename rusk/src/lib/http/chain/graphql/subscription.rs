@@ -0,0 +1,305 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use async_graphql::{
+    Context, FieldError, FieldResult, SimpleObject, Subscription,
+};
+use futures_util::{stream, Stream};
+use node_data::events::{BLOCK_CONFIRMED, BLOCK_FINALIZED};
+use serde_json::Value;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::http::event::DataType;
+use crate::http::RuesEvent;
+
+/// The events emitted by the node, as they are streamed to RUES clients.
+pub type EventsContext = broadcast::Receiver<RuesEvent>;
+
+#[derive(SimpleObject)]
+pub struct BlockEvent {
+    hash: String,
+    height: u64,
+    /// One of `accepted`, `confirmed`, `finalized` or `deleted`
+    event: String,
+}
+
+#[derive(SimpleObject)]
+pub struct MempoolEvent {
+    hash: String,
+    /// One of `included` or `removed`
+    event: String,
+}
+
+#[derive(SimpleObject)]
+pub struct ContractEvent {
+    contract: String,
+    topic: String,
+    /// Hash of the transaction that emitted the event, if any
+    origin: Option<String>,
+    /// Hex encoded event data
+    data: String,
+}
+
+pub struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    /// Blocks accepted by the node, which may still be reverted.
+    async fn accepted_blocks(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<impl Stream<Item = FieldResult<BlockEvent>>> {
+        events(ctx, |e| block_event(e).filter(|b| b.event == "accepted"))
+    }
+
+    /// Blocks that have been finalized.
+    async fn finalized_blocks(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<impl Stream<Item = FieldResult<BlockEvent>>> {
+        events(ctx, |e| {
+            block_event(e).filter(|b| b.event == BLOCK_FINALIZED)
+        })
+    }
+
+    /// Any change in the state of the blocks of the chain.
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<impl Stream<Item = FieldResult<BlockEvent>>> {
+        events(ctx, block_event)
+    }
+
+    /// Transactions included in or removed from the mempool.
+    async fn mempool_txs(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<impl Stream<Item = FieldResult<MempoolEvent>>> {
+        events(ctx, mempool_event)
+    }
+
+    /// Events emitted by contracts, optionally filtered by contract and
+    /// topic.
+    async fn contract_events(
+        &self,
+        ctx: &Context<'_>,
+        contract: Option<String>,
+        topic: Option<String>,
+    ) -> FieldResult<impl Stream<Item = FieldResult<ContractEvent>>> {
+        let contract = contract.map(|c| c.to_lowercase());
+        events(ctx, move |e| {
+            contract_event(e, contract.as_deref(), topic.as_deref())
+        })
+    }
+}
+
+/// Streams the events emitted from the moment of the subscription, mapped
+/// through `f`.
+///
+/// If the subscriber lags behind the stream ends with an error, as the
+/// missed events cannot be recovered.
+fn events<T: Send + 'static>(
+    ctx: &Context<'_>,
+    f: impl FnMut(RuesEvent) -> Option<T> + Send + 'static,
+) -> FieldResult<impl Stream<Item = FieldResult<T>> + Send + 'static> {
+    let events = ctx.data::<EventsContext>()?.resubscribe();
+    Ok(event_stream(events, f))
+}
+
+fn event_stream<T: Send + 'static>(
+    events: EventsContext,
+    f: impl FnMut(RuesEvent) -> Option<T> + Send + 'static,
+) -> impl Stream<Item = FieldResult<T>> + Send + 'static {
+    stream::unfold(Some((events, f)), |state| async move {
+        let (mut events, mut f) = state?;
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    if let Some(item) = f(event) {
+                        return Some((Ok(item), Some((events, f))));
+                    }
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    let err =
+                        FieldError::new(format!("Missed {missed} events"));
+                    return Some((Err(err), None));
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+fn block_event(event: RuesEvent) -> Option<BlockEvent> {
+    if event.uri.component != "blocks" {
+        return None;
+    }
+    let data = match &event.data {
+        DataType::Json(data) => data,
+        _ => return None,
+    };
+
+    let (height, event_name) = match event.uri.topic.as_str() {
+        "accepted" => {
+            let height = data.pointer("/header/height")?.as_u64()?;
+            (height, "accepted")
+        }
+        "statechange" => {
+            let height = data.get("atHeight")?.as_u64()?;
+            match data.get("state")?.as_str()? {
+                BLOCK_CONFIRMED => (height, BLOCK_CONFIRMED),
+                BLOCK_FINALIZED => (height, BLOCK_FINALIZED),
+                _ => return None,
+            }
+        }
+        "deleted" => (data.get("atHeight")?.as_u64()?, "deleted"),
+        _ => return None,
+    };
+
+    Some(BlockEvent {
+        hash: event.uri.entity.unwrap_or_default(),
+        height,
+        event: event_name.to_string(),
+    })
+}
+
+fn mempool_event(event: RuesEvent) -> Option<MempoolEvent> {
+    let topic = event.uri.topic.as_str();
+    if event.uri.component != "transactions"
+        || !matches!(topic, "included" | "removed")
+    {
+        return None;
+    }
+    Some(MempoolEvent {
+        hash: event.uri.entity.unwrap_or_default(),
+        event: topic.to_string(),
+    })
+}
+
+/// Maps a contract event, if it matches the given lowercase contract id and
+/// topic.
+fn contract_event(
+    event: RuesEvent,
+    contract: Option<&str>,
+    topic: Option<&str>,
+) -> Option<ContractEvent> {
+    if event.uri.component != "contracts" {
+        return None;
+    }
+    let entity = event.uri.entity.unwrap_or_default();
+    if contract.is_some_and(|c| c != entity)
+        || topic.is_some_and(|t| t != event.uri.topic)
+    {
+        return None;
+    }
+    let data = match event.data {
+        DataType::Binary(data) => hex::encode(data.inner),
+        _ => String::new(),
+    };
+    Some(ContractEvent {
+        contract: entity,
+        topic: event.uri.topic,
+        origin: event
+            .headers
+            .get("Rusk-Origin")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use serde_json::json;
+
+    use super::*;
+    use crate::http::event::{BinaryWrapper, RuesEventUri};
+
+    fn event(component: &str, entity: &str, topic: &str) -> RuesEvent {
+        RuesEvent {
+            uri: RuesEventUri {
+                component: component.into(),
+                entity: Some(entity.into()),
+                topic: topic.into(),
+            },
+            headers: Default::default(),
+            data: DataType::None,
+        }
+    }
+
+    fn block(topic: &str, data: Value) -> RuesEvent {
+        RuesEvent {
+            data: DataType::Json(data),
+            ..event("blocks", "abcd", topic)
+        }
+    }
+
+    #[test]
+    fn parse_block_event() {
+        let accepted = json!({ "header": { "height": 10 } });
+        let b = block_event(block("accepted", accepted)).unwrap();
+        assert_eq!((b.hash.as_str(), b.height), ("abcd", 10));
+        assert_eq!(b.event, "accepted");
+
+        let finalized = json!({ "state": BLOCK_FINALIZED, "atHeight": 11 });
+        let b = block_event(block("statechange", finalized)).unwrap();
+        assert_eq!((b.height, b.event.as_str()), (11, BLOCK_FINALIZED));
+
+        let confirmed = json!({ "state": BLOCK_CONFIRMED, "atHeight": 12 });
+        let b = block_event(block("statechange", confirmed)).unwrap();
+        assert_eq!((b.height, b.event.as_str()), (12, BLOCK_CONFIRMED));
+
+        let b = block_event(block("deleted", json!({ "atHeight": 13 })));
+        assert_eq!(b.map(|b| b.event).as_deref(), Some("deleted"));
+
+        let unknown = json!({ "state": "unknown", "atHeight": 11 });
+        assert!(block_event(block("statechange", unknown)).is_none());
+        assert!(block_event(block("accepted", json!({}))).is_none());
+        assert!(block_event(event("blocks", "abcd", "accepted")).is_none());
+        assert!(
+            block_event(event("transactions", "abcd", "included")).is_none()
+        );
+    }
+
+    #[test]
+    fn filter_contract_events() {
+        let mut transfer = event("contracts", "01ab", "moonlight");
+        transfer.data = DataType::Binary(BinaryWrapper { inner: vec![1, 2] });
+        transfer.add_header("Rusk-Origin", "ff00");
+
+        let e = contract_event(transfer.clone(), None, None).unwrap();
+        assert_eq!(e.contract, "01ab");
+        assert_eq!(e.topic, "moonlight");
+        assert_eq!(e.origin.as_deref(), Some("ff00"));
+        assert_eq!(e.data, "0102");
+
+        assert!(contract_event(transfer.clone(), Some("01ab"), None).is_some());
+        assert!(contract_event(transfer.clone(), Some("02ab"), None).is_none());
+        let topic = Some("moonlight");
+        assert!(contract_event(transfer.clone(), Some("01ab"), topic).is_some());
+        assert!(contract_event(transfer, None, Some("phoenix")).is_none());
+
+        let tx = event("transactions", "01ab", "moonlight");
+        assert!(contract_event(tx, None, None).is_none());
+    }
+
+    #[tokio::test]
+    async fn stream_ends_on_lag() {
+        let (sender, receiver) = broadcast::channel(1);
+        let mut stream = Box::pin(event_stream(receiver, Some));
+
+        sender.send(event("blocks", "01", "accepted")).unwrap();
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.uri.entity.as_deref(), Some("01"));
+
+        sender.send(event("blocks", "02", "accepted")).unwrap();
+        sender.send(event("blocks", "03", "accepted")).unwrap();
+        assert!(stream.next().await.unwrap().is_err());
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -4,6 +4,8 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use dusk_bytes::DeserializableSlice;
+use execution_core::signatures::bls::PublicKey as AccountPublicKey;
 use node::database::rocksdb::MD_HASH_KEY;
 use node::database::{Mempool, Metadata};
 
@@ -26,6 +28,7 @@ pub async fn last_transactions(
     if (count < 1) {
         return Err(FieldError::new("count must be positive"));
     }
+    ctx.data::<GqlLimits>()?.check_count(count as u64)?;
 
     let db = ctx.data::<DBContext>()?;
     let transactions = db.read().await.view(|t| {
@@ -56,6 +59,95 @@ pub async fn last_transactions(
     Ok(transactions)
 }
 
+pub async fn txs_by_account(
+    ctx: &Context<'_>,
+    address: String,
+    range: Option<[u64; 2]>,
+    limit: Option<u64>,
+) -> FieldResult<Vec<SpentTransaction>> {
+    let bytes = bs58::decode(address).into_vec()?;
+    let account = AccountPublicKey::from_slice(&bytes)
+        .map_err(|_| FieldError::new("Invalid account"))?;
+
+    let limit = ctx.data::<GqlLimits>()?.item_limit(limit) as usize;
+    let db = ctx.data::<DBContext>()?;
+    let txs = db.read().await.view(|t| {
+        let range = index_range(&t, range)?;
+        let ids = t.fetch_tx_ids_by_account(&account, range, limit)?;
+        indexed_txs(&t, ids)
+    })?;
+
+    Ok(txs)
+}
+
+pub async fn txs_by_contract(
+    ctx: &Context<'_>,
+    contract: ContractId,
+    range: Option<[u64; 2]>,
+    limit: Option<u64>,
+) -> FieldResult<Vec<SpentTransaction>> {
+    let limit = ctx.data::<GqlLimits>()?.item_limit(limit) as usize;
+    let db = ctx.data::<DBContext>()?;
+    let txs = db.read().await.view(|t| {
+        let range = index_range(&t, range)?;
+        let ids = t.fetch_tx_ids_by_contract(&contract, range, limit)?;
+        indexed_txs(&t, ids)
+    })?;
+
+    Ok(txs)
+}
+
+/// Returns the range of block heights to look up in the transactions index.
+///
+/// Without a requested range all the indexed blocks are looked up, while a
+/// range starting below the index height fails, as the blocks stored before
+/// the index was enabled are not indexed.
+fn index_range<L: Ledger>(
+    t: &L,
+    range: Option<[u64; 2]>,
+) -> FieldResult<(u64, u64)> {
+    let Some(index_height) = t.fetch_tx_index_height()? else {
+        // The transactions index being disabled is reported by the lookup
+        return Ok((0, u64::MAX));
+    };
+
+    match range {
+        None => Ok((index_height, u64::MAX)),
+        Some([from, _]) if from < index_height => Err(FieldError::new(
+            format!("Transactions are indexed from height {index_height}"),
+        )),
+        Some([from, to]) => Ok((from, to)),
+    }
+}
+
+/// Decodes a hex encoded contract id
+pub fn parse_contract(contract: &str) -> FieldResult<ContractId> {
+    let bytes = hex::decode(contract)?
+        .try_into()
+        .map_err(|_| FieldError::new("Invalid contract id"))?;
+    Ok(ContractId::from_bytes(bytes))
+}
+
+/// Fetches the ledger transactions found in the transactions index
+pub fn indexed_txs<L: Ledger>(
+    t: &L,
+    ids: Option<Vec<(u64, [u8; 32])>>,
+) -> FieldResult<Vec<SpentTransaction>> {
+    let ids = ids.ok_or_else(|| {
+        FieldError::new("Transactions index is disabled on this node")
+    })?;
+
+    let mut txs = vec![];
+    for (_, tx_id) in ids {
+        // Transactions of pruned blocks are not available anymore
+        if let Some(tx) = t.get_ledger_tx_by_hash(&tx_id)? {
+            txs.push(SpentTransaction(tx));
+        }
+    }
+
+    Ok(txs)
+}
+
 pub async fn mempool<'a>(
     ctx: &Context<'_>,
 ) -> FieldResult<Vec<Transaction<'a>>> {
@@ -121,6 +121,16 @@ impl TryFrom<&str> for Target {
     }
 }
 
+/// Looks up a header by name, ignoring its case
+pub(crate) fn find_header<'a>(
+    headers: &'a serde_json::Map<String, serde_json::Value>,
+    name: &str,
+) -> Option<&'a serde_json::Value> {
+    headers
+        .iter()
+        .find_map(|(k, v)| k.eq_ignore_ascii_case(name).then_some(v))
+}
+
 impl MessageRequest {
     pub fn x_headers(&self) -> serde_json::Map<String, serde_json::Value> {
         let mut h = self.headers.clone();
@@ -129,9 +139,7 @@ impl MessageRequest {
     }
 
     pub fn header(&self, name: &str) -> Option<&serde_json::Value> {
-        self.headers
-            .iter()
-            .find_map(|(k, v)| k.eq_ignore_ascii_case(name).then_some(v))
+        find_header(&self.headers, name)
     }
 
     pub fn parse(bytes: &[u8]) -> anyhow::Result<Self> {
@@ -807,9 +815,7 @@ impl RuesDispatchEvent {
     }
 
     pub fn header(&self, name: &str) -> Option<&serde_json::Value> {
-        self.headers
-            .iter()
-            .find_map(|(k, v)| k.eq_ignore_ascii_case(name).then_some(v))
+        find_header(&self.headers, name)
     }
 
     pub fn check_rusk_version(&self) -> anyhow::Result<()> {
@@ -0,0 +1,178 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::BTreeMap;
+
+use dusk_bytes::DeserializableSlice;
+use execution_core::signatures::bls::PublicKey as AccountPublicKey;
+use execution_core::transfer::TRANSFER_CONTRACT;
+use node::transfer::moonlight_transfers;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::event::DataType;
+use super::RuesEvent;
+
+/// Header carrying the filter of a RUES subscription
+pub(crate) const RUSK_FILTER_HEADER: &str = "Rusk-Filter";
+
+const RUSK_ORIGIN_HEADER: &str = "Rusk-Origin";
+
+/// JSON pointers to the Moonlight accounts of the transactions events
+const TX_ACCOUNT_POINTERS: [&str; 4] =
+    ["/from", "/to", "/inner/from", "/inner/to"];
+
+/// Filter on the payload of the events of a RUES subscription, sent as a
+/// JSON object in the `Rusk-Filter` header of the subscribe request.
+///
+/// An event passes the filter if it satisfies all of its conditions.
+#[derive(Debug, Clone, Default)]
+pub struct RuesFilter {
+    origin: Option<String>,
+    moonlight: Option<(String, AccountPublicKey)>,
+    data: BTreeMap<String, Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawFilter {
+    /// Hex encoded hash of the transaction that emitted a contract event
+    origin: Option<String>,
+    /// Base58 encoded public key of a Moonlight account sending or
+    /// receiving funds
+    moonlight: Option<String>,
+    /// Values the JSON data of the event must have at the given JSON
+    /// pointers
+    #[serde(default)]
+    data: BTreeMap<String, Value>,
+}
+
+impl RuesFilter {
+    pub fn parse(filter: &str) -> anyhow::Result<Self> {
+        let raw: RawFilter = serde_json::from_str(filter)?;
+
+        let moonlight = match raw.moonlight {
+            Some(address) => {
+                let bytes = bs58::decode(&address).into_vec()?;
+                let account =
+                    AccountPublicKey::from_slice(&bytes).map_err(|_| {
+                        anyhow::anyhow!("Invalid Moonlight account")
+                    })?;
+                Some((address, account))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            origin: raw.origin.map(|o| o.to_lowercase()),
+            moonlight,
+            data: raw.data,
+        })
+    }
+
+    pub fn matches(&self, event: &RuesEvent) -> bool {
+        if let Some(origin) = &self.origin {
+            let event_origin = event
+                .headers
+                .get(RUSK_ORIGIN_HEADER)
+                .and_then(Value::as_str);
+            if event_origin != Some(origin.as_str()) {
+                return false;
+            }
+        }
+
+        if let Some((address, account)) = &self.moonlight {
+            if !involves_account(event, address, account) {
+                return false;
+            }
+        }
+
+        if !self.data.is_empty() {
+            let DataType::Json(data) = &event.data else {
+                return false;
+            };
+            let all_match = self
+                .data
+                .iter()
+                .all(|(pointer, value)| data.pointer(pointer) == Some(value));
+            if !all_match {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Checks if the event moves funds from or to the given Moonlight account.
+fn involves_account(
+    event: &RuesEvent,
+    address: &str,
+    account: &AccountPublicKey,
+) -> bool {
+    match (event.uri.component.as_str(), &event.data) {
+        ("transactions", DataType::Json(data)) => TX_ACCOUNT_POINTERS
+            .iter()
+            .any(|p| data.pointer(p).and_then(Value::as_str) == Some(address)),
+        ("contracts", DataType::Binary(data)) => {
+            let transfer = hex::encode(TRANSFER_CONTRACT);
+            event.uri.entity.as_ref() == Some(&transfer)
+                && moonlight_transfers(&event.uri.topic, &data.inner)
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|t| t.account == *account)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::event::RuesEventUri;
+
+    fn tx_event(data: Value) -> RuesEvent {
+        RuesEvent {
+            uri: RuesEventUri {
+                component: "transactions".into(),
+                entity: Some("abcd".into()),
+                topic: "included".into(),
+            },
+            headers: Default::default(),
+            data: DataType::Json(data),
+        }
+    }
+
+    #[test]
+    fn filter_on_data() {
+        let filter =
+            RuesFilter::parse(r#"{"data": {"/type": "moonlight"}}"#).unwrap();
+
+        let moonlight = tx_event(serde_json::json!({ "type": "moonlight" }));
+        let phoenix = tx_event(serde_json::json!({ "type": "phoenix" }));
+
+        assert!(filter.matches(&moonlight));
+        assert!(!filter.matches(&phoenix));
+        assert!(RuesFilter::default().matches(&phoenix));
+    }
+
+    #[test]
+    fn filter_on_origin() {
+        let filter = RuesFilter::parse(r#"{"origin": "AB01"}"#).unwrap();
+
+        let mut event = tx_event(Value::Null);
+        assert!(!filter.matches(&event));
+
+        event.add_header(RUSK_ORIGIN_HEADER, "ab01");
+        assert!(filter.matches(&event));
+    }
+
+    #[test]
+    fn invalid_filter() {
+        assert!(RuesFilter::parse(r#"{"unknown": 1}"#).is_err());
+        assert!(RuesFilter::parse(r#"{"moonlight": "invalid"}"#).is_err());
+    }
+}
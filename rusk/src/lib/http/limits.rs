@@ -0,0 +1,195 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+/// Number of tracked clients above which the expired ones are dropped, at
+/// most once per quota window
+const QUOTA_CLEANUP_THRESHOLD: usize = 10_000;
+
+/// Limits applied to the GraphQL queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GqlLimits {
+    /// Maximum depth of a query
+    pub max_depth: usize,
+    /// Maximum complexity of a query, each field counting as one
+    pub max_complexity: usize,
+    /// Maximum number of blocks spanned by a range of heights, requested
+    /// with `last` or scanned to fill a page of transactions
+    pub max_range: u64,
+    /// Maximum number of items, such as transactions, returned by a field
+    pub max_items: u64,
+}
+
+impl Default for GqlLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 16,
+            max_complexity: 1000,
+            max_range: 1000,
+            max_items: 1000,
+        }
+    }
+}
+
+impl GqlLimits {
+    /// Fails if the inclusive range of heights is larger than allowed.
+    pub fn check_range(&self, from: u64, to: u64) -> anyhow::Result<()> {
+        self.check_count(to.saturating_sub(from).saturating_add(1))
+    }
+
+    /// Fails if the number of requested blocks is larger than allowed.
+    pub fn check_count(&self, count: u64) -> anyhow::Result<()> {
+        if count > self.max_range {
+            anyhow::bail!(
+                "Requested {count} blocks, the maximum is {}",
+                self.max_range
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the requested number of items, capped to the maximum.
+    pub fn item_limit(&self, limit: Option<u64>) -> u64 {
+        limit.unwrap_or(self.max_items).min(self.max_items)
+    }
+}
+
+/// Maximum number of requests a single client IP can send within a window
+/// of time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestQuota {
+    pub max_requests: u32,
+    pub window: Duration,
+}
+
+/// Keeps track of the requests sent by each client IP, against a quota.
+pub(crate) struct QuotaTracker {
+    quota: RequestQuota,
+    clients: Mutex<Clients>,
+}
+
+struct Clients {
+    /// Start of the current window and number of requests sent within it,
+    /// by client IP
+    requests: HashMap<IpAddr, (Instant, u32)>,
+    last_cleanup: Instant,
+}
+
+impl QuotaTracker {
+    pub fn new(quota: RequestQuota) -> Self {
+        Self {
+            quota,
+            clients: Mutex::new(Clients {
+                requests: HashMap::new(),
+                last_cleanup: Instant::now(),
+            }),
+        }
+    }
+
+    /// Accounts a request from the given IP, returning the time after which
+    /// it can be retried if the quota is exceeded.
+    pub fn check(&self, ip: IpAddr) -> Result<(), Duration> {
+        let window = self.quota.window;
+        let now = Instant::now();
+
+        let mut clients = self.clients.lock();
+        if clients.requests.len() >= QUOTA_CLEANUP_THRESHOLD
+            && now - clients.last_cleanup >= window
+        {
+            clients
+                .requests
+                .retain(|_, (start, _)| now - *start < window);
+            clients.last_cleanup = now;
+        }
+
+        let (start, count) = clients.requests.entry(ip).or_insert((now, 0));
+        if now - *start >= window {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= self.quota.max_requests {
+            return Err(window - (now - *start));
+        }
+        *count += 1;
+
+        Ok(())
+    }
+}
+
+/// The quota of a single client IP, charged by each of its HTTP requests as
+/// well as by each of the messages it sends over websockets.
+#[derive(Clone)]
+pub struct ClientQuota {
+    tracker: Arc<QuotaTracker>,
+    ip: IpAddr,
+}
+
+impl ClientQuota {
+    pub(crate) fn new(tracker: Arc<QuotaTracker>, ip: IpAddr) -> Self {
+        Self { tracker, ip }
+    }
+
+    /// Accounts a request from the client, returning the time after which it
+    /// can be retried if the quota is exceeded.
+    pub fn check(&self) -> Result<(), Duration> {
+        self.tracker.check(self.ip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quota_tracker() {
+        let tracker = QuotaTracker::new(RequestQuota {
+            max_requests: 2,
+            window: Duration::from_secs(60),
+        });
+        let ip: IpAddr = [127, 0, 0, 1].into();
+        let other: IpAddr = [127, 0, 0, 2].into();
+
+        assert!(tracker.check(ip).is_ok());
+        assert!(tracker.check(ip).is_ok());
+        assert!(tracker.check(ip).is_err());
+        assert!(tracker.check(other).is_ok());
+
+        let client = ClientQuota::new(Arc::new(tracker), other);
+        assert!(client.check().is_ok());
+        assert!(client.check().is_err());
+    }
+
+    #[test]
+    fn gql_range_limits() {
+        let limits = GqlLimits {
+            max_range: 10,
+            ..Default::default()
+        };
+
+        assert!(limits.check_range(0, 9).is_ok());
+        assert!(limits.check_range(0, 10).is_err());
+        assert!(limits.check_range(10, 0).is_ok());
+        assert!(limits.check_count(11).is_err());
+    }
+
+    #[test]
+    fn gql_item_limits() {
+        let limits = GqlLimits {
+            max_items: 10,
+            ..Default::default()
+        };
+
+        assert_eq!(limits.item_limit(None), 10);
+        assert_eq!(limits.item_limit(Some(5)), 5);
+        assert_eq!(limits.item_limit(Some(11)), 10);
+    }
+}
@@ -17,15 +17,15 @@ impl HandleRequest for LocalProver {
         matches!(request.event.to_route(), (_, "rusk", topic) | (_, "prover", topic) if topic.starts_with("prove_"))
     }
     fn can_handle_rues(&self, request: &RuesDispatchEvent) -> bool {
-        matches!(request.uri.inner(), ("prover", _, "prove"))
+        RuesRoute::parse(&request.uri) == Some(RuesRoute::Prove)
     }
     async fn handle_rues(
         &self,
         request: &RuesDispatchEvent,
     ) -> anyhow::Result<ResponseData> {
         let data = request.data.as_bytes();
-        let response = match request.uri.inner() {
-            ("prover", _, "prove") => {
+        let response = match RuesRoute::parse(&request.uri) {
+            Some(RuesRoute::Prove) => {
                 LocalProver.prove(data).map_err(|e| anyhow!(e))?
             }
             _ => anyhow::bail!("Unsupported"),
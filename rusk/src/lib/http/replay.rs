@@ -0,0 +1,231 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use parking_lot::RwLock;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::warn;
+
+use super::RuesEvent;
+
+/// Header carrying the sequence number of a RUES event
+pub(crate) const RUSK_SEQ_HEADER: &str = "Rusk-Seq";
+
+/// Header of a subscribe request carrying the sequence number of the last
+/// event received by the client, whose following events are to be replayed
+pub(crate) const RUSK_LAST_SEQ_HEADER: &str = "Rusk-Last-Seq";
+
+/// Header of a subscribe request carrying the block height from which the
+/// archived events are to be replayed, if the ones following `Rusk-Last-Seq`
+/// are no longer in the event log
+pub(crate) const RUSK_REPLAY_HEIGHT_HEADER: &str = "Rusk-Replay-Height";
+
+/// Bounded log of the last events emitted by the node, each identified by a
+/// monotonically increasing sequence number.
+///
+/// Sequence numbers start from the UNIX time in microseconds at which the log
+/// is created, so that they keep increasing across restarts of the node.
+pub(crate) struct EventLog {
+    cap: usize,
+    inner: RwLock<LogInner>,
+}
+
+struct LogInner {
+    next_seq: u64,
+    events: VecDeque<(u64, RuesEvent)>,
+}
+
+impl EventLog {
+    pub fn new(cap: usize) -> Self {
+        let first_seq = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_micros() as u64)
+            .unwrap_or_default()
+            .max(1);
+
+        Self {
+            cap,
+            inner: RwLock::new(LogInner {
+                next_seq: first_seq,
+                events: VecDeque::with_capacity(cap),
+            }),
+        }
+    }
+
+    /// Assigns the next sequence number to the event, and records it in the
+    /// log evicting the oldest event if the log is full.
+    pub fn record(&self, event: &mut RuesEvent) -> u64 {
+        let mut inner = self.inner.write();
+
+        let seq = inner.next_seq;
+        inner.next_seq += 1;
+        event.add_header(RUSK_SEQ_HEADER, seq);
+
+        if self.cap > 0 {
+            if inner.events.len() == self.cap {
+                inner.events.pop_front();
+            }
+            inner.events.push_back((seq, event.clone()));
+        }
+
+        seq
+    }
+
+    /// Skips the sequence numbers of events missed before being recorded,
+    /// clearing the log so that no replay spans across them.
+    ///
+    /// Sessions notice the gap in the sequence numbers of the following
+    /// events.
+    pub fn skip(&self, missed: u64) {
+        let mut inner = self.inner.write();
+        inner.next_seq += missed;
+        inner.events.clear();
+    }
+
+    /// Sequence number of the last recorded event.
+    pub fn last_seq(&self) -> u64 {
+        self.inner.read().next_seq - 1
+    }
+
+    /// Sequence number of the oldest event still in the log.
+    pub fn oldest_seq(&self) -> u64 {
+        self.inner.read().oldest_seq()
+    }
+
+    /// Checks if all the events recorded after the given sequence number are
+    /// still in the log.
+    pub fn covers(&self, seq: u64) -> bool {
+        self.inner.read().covers(seq)
+    }
+
+    /// Returns all the events recorded after the given sequence number, or
+    /// `None` if some of them have been evicted or the sequence number is
+    /// unknown.
+    pub fn since(&self, seq: u64) -> Option<Vec<(u64, RuesEvent)>> {
+        let inner = self.inner.read();
+        if !inner.covers(seq) {
+            return None;
+        }
+
+        let skip = (seq + 1 - inner.oldest_seq()) as usize;
+        Some(inner.events.iter().skip(skip).cloned().collect())
+    }
+}
+
+impl LogInner {
+    fn oldest_seq(&self) -> u64 {
+        self.events
+            .front()
+            .map(|(seq, _)| *seq)
+            .unwrap_or(self.next_seq)
+    }
+
+    fn covers(&self, seq: u64) -> bool {
+        seq < self.next_seq && seq.saturating_add(1) >= self.oldest_seq()
+    }
+}
+
+/// Reads the sequence number of an event, if it has been recorded.
+pub(crate) fn event_seq(event: &RuesEvent) -> Option<u64> {
+    event.headers.get(RUSK_SEQ_HEADER)?.as_u64()
+}
+
+/// Records every event emitted by the node in the log, forwarding it to the
+/// returned channel once its sequence number has been assigned.
+pub(crate) fn sequence_events(
+    mut events: broadcast::Receiver<RuesEvent>,
+    log: Arc<EventLog>,
+    mut shutdown: broadcast::Receiver<Infallible>,
+) -> broadcast::Receiver<RuesEvent> {
+    let (sender, receiver) = broadcast::channel(log.cap.max(1));
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                event = events.recv() => match event {
+                    Ok(mut event) => {
+                        log.record(&mut event);
+                        // There may be no subscriber listening at the moment
+                        let _ = sender.send(event);
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!("Missed {missed} events before sequencing them");
+                        log.skip(missed);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        }
+    });
+
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::event::{DataType, RuesEventUri};
+
+    fn event(topic: &str) -> RuesEvent {
+        RuesEvent {
+            uri: RuesEventUri {
+                component: "blocks".into(),
+                entity: None,
+                topic: topic.into(),
+            },
+            headers: Default::default(),
+            data: DataType::None,
+        }
+    }
+
+    #[test]
+    fn event_log_replay() {
+        let log = EventLog::new(2);
+        let start = log.last_seq();
+
+        let mut first = event("first");
+        let seq = log.record(&mut first);
+        assert_eq!(seq, start + 1);
+        assert_eq!(event_seq(&first), Some(seq));
+
+        log.record(&mut event("second"));
+        log.record(&mut event("third"));
+
+        // The first event has been evicted
+        assert_eq!(log.oldest_seq(), start + 2);
+        assert!(!log.covers(start));
+        assert!(log.since(start).is_none());
+
+        let replayed = log.since(start + 1).expect("events should be in log");
+        let topics: Vec<_> =
+            replayed.iter().map(|(_, e)| e.uri.topic.as_str()).collect();
+        assert_eq!(topics, ["second", "third"]);
+
+        assert_eq!(log.since(log.last_seq()).map(|e| e.len()), Some(0));
+        assert!(log.since(log.last_seq() + 1).is_none());
+    }
+
+    #[test]
+    fn event_log_skip() {
+        let log = EventLog::new(4);
+
+        let seq = log.record(&mut event("first"));
+        log.skip(2);
+
+        // No replay spans across the missed events
+        assert!(!log.covers(seq));
+        assert!(log.since(seq).is_none());
+
+        let mut next = event("next");
+        assert_eq!(log.record(&mut next), seq + 3);
+        assert_eq!(log.since(seq + 2).map(|e| e.len()), Some(1));
+    }
+}
@@ -0,0 +1,111 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::event::RuesEventUri;
+
+/// An endpoint served by the node at a RUES location.
+///
+/// The handlers dispatch the requests by route, and every route is described
+/// in the OpenAPI description of the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RuesRoute {
+    /// Query of a contract function, the entity being the contract id and
+    /// the topic the function name
+    ContractCall,
+    ContractBatch,
+    GraphqlQuery,
+    /// GraphQL subscriptions, served over a websocket
+    GraphqlSubscribe,
+    TxPropagate,
+    TxPreverify,
+    TxSimulate,
+    NetworkPeers,
+    NodeInfo,
+    NodeProvisioners,
+    NodeCrs,
+    GasPrice,
+    GasEstimate,
+    Prove,
+}
+
+impl RuesRoute {
+    pub const ALL: &'static [Self] = &[
+        Self::ContractCall,
+        Self::ContractBatch,
+        Self::GraphqlQuery,
+        Self::GraphqlSubscribe,
+        Self::TxPropagate,
+        Self::TxPreverify,
+        Self::TxSimulate,
+        Self::NetworkPeers,
+        Self::NodeInfo,
+        Self::NodeProvisioners,
+        Self::NodeCrs,
+        Self::GasPrice,
+        Self::GasEstimate,
+        Self::Prove,
+    ];
+
+    /// Location of the endpoint, as found in the OpenAPI description
+    pub const fn path(self) -> &'static str {
+        match self {
+            Self::ContractCall => "/on/contracts:{contract_id}/{fn_name}",
+            Self::ContractBatch => "/on/contracts/batch",
+            Self::GraphqlQuery => "/on/graphql/query",
+            Self::GraphqlSubscribe => "/on/graphql/subscribe",
+            Self::TxPropagate => "/on/transactions/propagate",
+            Self::TxPreverify => "/on/transactions/preverify",
+            Self::TxSimulate => "/on/transactions/simulate",
+            Self::NetworkPeers => "/on/network/peers",
+            Self::NodeInfo => "/on/node/info",
+            Self::NodeProvisioners => "/on/node/provisioners",
+            Self::NodeCrs => "/on/node/crs",
+            Self::GasPrice => "/on/blocks/gas-price",
+            Self::GasEstimate => "/on/blocks/gas-estimate",
+            Self::Prove => "/on/prover/prove",
+        }
+    }
+
+    /// Returns the route served at the given location, if any
+    pub fn parse(uri: &RuesEventUri) -> Option<Self> {
+        let route = match uri.inner() {
+            ("contracts", Some(_), _) => Self::ContractCall,
+            ("contracts", None, "batch") => Self::ContractBatch,
+            ("graphql", _, "query") => Self::GraphqlQuery,
+            ("graphql", _, "subscribe") => Self::GraphqlSubscribe,
+            ("transactions", _, "propagate") => Self::TxPropagate,
+            ("transactions", _, "preverify") => Self::TxPreverify,
+            ("transactions", _, "simulate") => Self::TxSimulate,
+            ("network", _, "peers") => Self::NetworkPeers,
+            ("node", _, "info") => Self::NodeInfo,
+            ("node", _, "provisioners") => Self::NodeProvisioners,
+            ("node", _, "crs") => Self::NodeCrs,
+            ("blocks", _, "gas-price") => Self::GasPrice,
+            ("blocks", _, "gas-estimate") => Self::GasEstimate,
+            ("prover", _, "prove") => Self::Prove,
+            _ => return None,
+        };
+        Some(route)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_parse_from_their_path() {
+        for route in RuesRoute::ALL {
+            let uri = RuesEventUri::parse_from_path(route.path())
+                .expect("The route path should be a RUES location");
+            assert_eq!(RuesRoute::parse(&uri), Some(*route));
+        }
+
+        let unknown = RuesEventUri::parse_from_path("/on/node/unknown")
+            .expect("The path should be a RUES location");
+        assert_eq!(RuesRoute::parse(&unknown), None);
+    }
+}
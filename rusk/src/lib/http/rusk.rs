@@ -10,7 +10,7 @@ use super::*;
 use dusk_bytes::Serializable;
 use node::vm::VMExecution;
 use rusk_profile::CRS_17_HASH;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::{mpsc, Arc};
 use std::thread;
 use tokio::task;
@@ -20,23 +20,40 @@ use execution_core::ContractId;
 
 use crate::node::Rusk;
 
-const RUSK_FEEDER_HEADER: &str = "Rusk-Feeder";
+pub(crate) const RUSK_FEEDER_HEADER: &str = "Rusk-Feeder";
+pub(crate) const RUSK_HEIGHT_HEADER: &str = "Rusk-Height";
+pub(crate) const RUSK_STATE_ROOT_HEADER: &str = "Rusk-State-Root";
+
+/// Maximum number of queries in a batch
+const MAX_BATCH_QUERIES: usize = 256;
+/// Maximum gas a single query of a batch may spend
+const BATCH_QUERY_GAS_LIMIT: u64 = 1_000_000_000;
+/// Maximum gas a whole batch may spend
+const BATCH_TOTAL_GAS_LIMIT: u64 = 10_000_000_000;
 
 #[async_trait]
 impl HandleRequest for Rusk {
     fn can_handle(&self, request: &MessageRequest) -> bool {
-        matches!(
-            &request.event.to_route(),
-            (Target::Contract(_), ..) | (Target::Host(_), "rusk", _)
-        )
+        // Queries at a given height are served by the node, since it is the
+        // one having access to the ledger
+        match &request.event.to_route() {
+            (Target::Contract(_), ..) => {
+                request.header(RUSK_HEIGHT_HEADER).is_none()
+            }
+            (Target::Host(_), "rusk", _) => true,
+            _ => false,
+        }
     }
     fn can_handle_rues(&self, request: &RuesDispatchEvent) -> bool {
-        #[allow(clippy::match_like_matches_macro)]
-        match request.uri.inner() {
-            ("contracts", Some(_), _) => true,
-            ("transactions", _, "preverify") => true,
-            ("node", _, "provisioners") => true,
-            ("node", _, "crs") => true,
+        match RuesRoute::parse(&request.uri) {
+            Some(RuesRoute::ContractCall | RuesRoute::ContractBatch) => {
+                request.header(RUSK_HEIGHT_HEADER).is_none()
+            }
+            Some(
+                RuesRoute::TxPreverify
+                | RuesRoute::NodeProvisioners
+                | RuesRoute::NodeCrs,
+            ) => true,
             _ => false,
         }
     }
@@ -44,17 +61,36 @@ impl HandleRequest for Rusk {
         &self,
         request: &RuesDispatchEvent,
     ) -> anyhow::Result<ResponseData> {
-        match request.uri.inner() {
-            ("contracts", Some(contract_id), method) => {
+        let uri = &request.uri;
+        match RuesRoute::parse(uri) {
+            Some(RuesRoute::ContractCall) => {
                 let feeder = request.header(RUSK_FEEDER_HEADER).is_some();
+                let commit = request
+                    .header(RUSK_STATE_ROOT_HEADER)
+                    .map(parse_state_root)
+                    .transpose()?;
                 let data = request.data.as_bytes();
-                self.handle_contract_query(contract_id, method, data, feeder)
+                self.handle_contract_query(
+                    uri.entity.as_deref().unwrap_or_default(),
+                    &uri.topic,
+                    data,
+                    feeder,
+                    commit,
+                )
+            }
+            Some(RuesRoute::ContractBatch) => {
+                let commit = request
+                    .header(RUSK_STATE_ROOT_HEADER)
+                    .map(parse_state_root)
+                    .transpose()?;
+                self.handle_contract_batch(request.data.as_bytes(), commit)
+                    .await
             }
-            ("transactions", _, "preverify") => {
+            Some(RuesRoute::TxPreverify) => {
                 self.handle_preverify(request.data.as_bytes())
             }
-            ("node", _, "provisioners") => self.get_provisioners(),
-            ("node", _, "crs") => self.get_crs(),
+            Some(RuesRoute::NodeProvisioners) => self.get_provisioners(),
+            Some(RuesRoute::NodeCrs) => self.get_crs(),
             _ => Err(anyhow::anyhow!("Unsupported")),
         }
     }
@@ -66,7 +102,23 @@ impl HandleRequest for Rusk {
         match &request.event.to_route() {
             (Target::Contract(_), ..) => {
                 let feeder = request.header(RUSK_FEEDER_HEADER).is_some();
-                self.handle_contract_query_legacy(&request.event, feeder)
+                let commit = request
+                    .header(RUSK_STATE_ROOT_HEADER)
+                    .map(parse_state_root)
+                    .transpose()?;
+                self.handle_contract_query_legacy(
+                    &request.event,
+                    feeder,
+                    commit,
+                )
+            }
+            (Target::Host(_), "rusk", "query_batch") => {
+                let commit = request
+                    .header(RUSK_STATE_ROOT_HEADER)
+                    .map(parse_state_root)
+                    .transpose()?;
+                self.handle_contract_batch(request.event_data(), commit)
+                    .await
             }
             (Target::Host(_), "rusk", "preverify") => {
                 self.handle_preverify(request.event_data())
@@ -80,24 +132,49 @@ impl HandleRequest for Rusk {
     }
 }
 
+/// Parses the value of a `Rusk-Height` header.
+pub(crate) fn parse_height(value: &serde_json::Value) -> anyhow::Result<u64> {
+    let height = match value.as_str() {
+        Some(v) => v.trim().parse(),
+        None => value.to_string().parse(),
+    };
+    height.map_err(|_| anyhow::anyhow!("Invalid {RUSK_HEIGHT_HEADER} header"))
+}
+
+/// Parses the value of a `Rusk-State-Root` header as a hex encoded commit.
+fn parse_state_root(value: &serde_json::Value) -> anyhow::Result<[u8; 32]> {
+    value
+        .as_str()
+        .and_then(|v| hex::decode(v.trim()).ok())
+        .and_then(|v| v.try_into().ok())
+        .ok_or_else(|| {
+            anyhow::anyhow!("Invalid {RUSK_STATE_ROOT_HEADER} header")
+        })
+}
+
 impl Rusk {
-    fn handle_contract_query_legacy(
+    pub(crate) fn handle_contract_query_legacy(
         &self,
         event: &Event,
         feeder: bool,
+        commit: Option<[u8; 32]>,
     ) -> anyhow::Result<ResponseData> {
         let contract = event.target.inner();
         let topic = &event.topic;
         let data = event.data.as_bytes();
 
-        self.handle_contract_query(contract, topic, data, feeder)
+        self.handle_contract_query(contract, topic, data, feeder, commit)
     }
-    fn handle_contract_query(
+
+    /// Queries a contract on the given commit, or on the current tip if none
+    /// is given.
+    pub(crate) fn handle_contract_query(
         &self,
         contract: &str,
         topic: &str,
         data: &[u8],
         feeder: bool,
+        commit: Option<[u8; 32]>,
     ) -> anyhow::Result<ResponseData> {
         let contract_bytes = hex::decode(contract)?;
 
@@ -108,22 +185,102 @@ impl Rusk {
         let fn_name = topic.to_string();
         let data = data.to_vec();
         if feeder {
+            // Check the commit beforehand, since errors of the feeder query
+            // cannot be reported to the client
+            if let Some(commit) = commit {
+                self.ensure_commit(commit)
+                    .map_err(|e| anyhow::anyhow!("{e}"))?;
+            }
+
             let (sender, receiver) = mpsc::channel();
 
             let rusk = self.clone();
 
             thread::spawn(move || {
-                rusk.feeder_query_raw(contract_id, fn_name, data, sender);
+                rusk.feeder_query_raw(
+                    contract_id,
+                    fn_name,
+                    data,
+                    sender,
+                    commit,
+                );
             });
             Ok(ResponseData::new(receiver))
         } else {
             let data = self
-                .query_raw(contract_id, fn_name, data)
+                .query_raw(contract_id, fn_name, data, commit)
                 .map_err(|e| anyhow::anyhow!("{e}"))?;
             Ok(ResponseData::new(data))
         }
     }
 
+    /// Performs a batch of contract queries on the same commit, reporting the
+    /// result of each query separately.
+    ///
+    /// The queries are run on a blocking thread, since they may take a while.
+    pub(crate) async fn handle_contract_batch(
+        &self,
+        data: &[u8],
+        commit: Option<[u8; 32]>,
+    ) -> anyhow::Result<ResponseData> {
+        let rusk = self.clone();
+        let data = data.to_vec();
+        task::spawn_blocking(move || rusk.contract_batch(&data, commit)).await?
+    }
+
+    fn contract_batch(
+        &self,
+        data: &[u8],
+        commit: Option<[u8; 32]>,
+    ) -> anyhow::Result<ResponseData> {
+        let queries: Vec<BatchQuery> = serde_json::from_slice(data)
+            .map_err(|e| anyhow::anyhow!("Invalid batch: {e}"))?;
+        if queries.len() > MAX_BATCH_QUERIES {
+            anyhow::bail!(
+                "Batch of {} queries, the maximum is {MAX_BATCH_QUERIES}",
+                queries.len()
+            );
+        }
+
+        // Malformed queries are reported without being performed
+        let queries: Vec<_> =
+            queries.into_iter().map(BatchQuery::parse).collect();
+        let valid = queries.iter().filter_map(|q| q.as_ref().ok()).map(
+            |(contract_id, fn_name, fn_args)| {
+                (*contract_id, fn_name.as_str(), fn_args.as_slice())
+            },
+        );
+
+        let mut performed = self
+            .query_raw_batch(
+                valid,
+                commit,
+                BATCH_QUERY_GAS_LIMIT,
+                BATCH_TOTAL_GAS_LIMIT,
+            )
+            .map_err(|e| anyhow::anyhow!("{e}"))?
+            .into_iter();
+
+        let results: Vec<_> = queries
+            .into_iter()
+            .map(|query| {
+                let result = match query {
+                    Ok(_) => performed
+                        .next()
+                        .expect("There should be a result per valid query")
+                        .map_err(|e| anyhow::anyhow!("{e}")),
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(data) => BatchResult::Data(hex::encode(data)),
+                    Err(e) => BatchResult::Error(e.to_string()),
+                }
+            })
+            .collect();
+
+        Ok(ResponseData::new(serde_json::to_value(results)?))
+    }
+
     fn handle_preverify(&self, data: &[u8]) -> anyhow::Result<ResponseData> {
         let tx = execution_core::transfer::Transaction::from_slice(data)
             .map_err(|e| anyhow::anyhow!("Invalid Data {e:?}"))?;
@@ -157,6 +314,40 @@ impl Rusk {
     }
 }
 
+/// A contract query of a batch
+#[derive(Deserialize)]
+struct BatchQuery {
+    /// Hex encoded ID of the contract
+    contract: String,
+    fn_name: String,
+    /// Hex encoded argument of the query
+    #[serde(default)]
+    fn_args: String,
+}
+
+impl BatchQuery {
+    fn parse(self) -> anyhow::Result<(ContractId, String, Vec<u8>)> {
+        let contract_bytes =
+            hex::decode(self.contract.trim_start_matches("0x"))?
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Invalid contract bytes"))?;
+        let fn_args = hex::decode(self.fn_args.trim_start_matches("0x"))?;
+        Ok((
+            ContractId::from_bytes(contract_bytes),
+            self.fn_name,
+            fn_args,
+        ))
+    }
+}
+
+/// Either the hex encoded result of a query of a batch, or its error
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+enum BatchResult {
+    Data(String),
+    Error(String),
+}
+
 #[derive(Serialize)]
 struct Provisioner {
     key: String,
@@ -78,8 +78,8 @@ impl Listener {
         })
     }
 
-    pub async fn accept(&self) -> io::Result<Stream> {
-        let (stream, _) = self.inner.accept().await?;
+    pub async fn accept(&self) -> io::Result<(Stream, SocketAddr)> {
+        let (stream, remote_addr) = self.inner.accept().await?;
 
         let stream = match &self.acceptor {
             None => Stream::Raw(stream),
@@ -89,7 +89,7 @@ impl Listener {
             }
         };
 
-        Ok(stream)
+        Ok((stream, remote_addr))
     }
 
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
@@ -12,6 +12,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
+use dusk_consensus::config::ConsensusParams;
 use execution_core::{dusk, Dusk};
 
 use node::database::rocksdb::{self, Backend};
@@ -21,10 +22,13 @@ use parking_lot::RwLock;
 use rusk_abi::VM;
 use tokio::sync::broadcast;
 
-use crate::http::RuesEvent;
+use crate::http::{GqlLimits, RuesEvent};
 pub(crate) use events::ChainEventStreamer;
 #[cfg(feature = "archive")]
-use {node_data::archive::ArchivalData, tokio::sync::mpsc};
+use {
+    node::database::archive::SQLiteArchive, node_data::archive::ArchivalData,
+    tokio::sync::mpsc,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct RuskTip {
@@ -43,6 +47,7 @@ pub struct Rusk {
     pub(crate) min_deployment_gas_price: Option<u64>,
     pub(crate) feeder_gas_limit: u64,
     pub(crate) block_gas_limit: u64,
+    pub(crate) consensus_params: ConsensusParams,
     pub(crate) event_sender: broadcast::Sender<RuesEvent>,
     #[cfg(feature = "archive")]
     pub(crate) archive_sender: mpsc::Sender<ArchivalData>,
@@ -54,11 +59,27 @@ pub(crate) type Services =
 #[derive(Clone)]
 pub struct RuskNode {
     inner: node::Node<Kadcast<255>, Backend, Rusk>,
+    #[cfg(feature = "archive")]
+    archive: SQLiteArchive,
+    gql_limits: GqlLimits,
 }
 
 impl RuskNode {
-    pub fn new(inner: node::Node<Kadcast<255>, Backend, Rusk>) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: node::Node<Kadcast<255>, Backend, Rusk>,
+        #[cfg(feature = "archive")] archive: SQLiteArchive,
+    ) -> Self {
+        Self {
+            inner,
+            #[cfg(feature = "archive")]
+            archive,
+            gql_limits: GqlLimits::default(),
+        }
+    }
+
+    pub fn with_gql_limits(mut self, gql_limits: GqlLimits) -> Self {
+        self.gql_limits = gql_limits;
+        self
     }
 }
 
@@ -67,6 +88,14 @@ impl RuskNode {
         self.inner.database() as Arc<tokio::sync::RwLock<Backend>>
     }
 
+    pub fn vm_handler(&self) -> Arc<tokio::sync::RwLock<Rusk>> {
+        self.inner.vm_handler() as Arc<tokio::sync::RwLock<Rusk>>
+    }
+
+    pub fn gql_limits(&self) -> GqlLimits {
+        self.gql_limits
+    }
+
     pub fn network(&self) -> Arc<tokio::sync::RwLock<Kadcast<255>>> {
         self.inner.network() as Arc<tokio::sync::RwLock<Kadcast<255>>>
     }
@@ -74,6 +103,11 @@ impl RuskNode {
     pub fn inner(&self) -> &node::Node<Kadcast<255>, Backend, Rusk> {
         &self.inner
     }
+
+    #[cfg(feature = "archive")]
+    pub fn archive(&self) -> SQLiteArchive {
+        self.archive.clone()
+    }
 }
 
 /// Calculates the value that the coinbase notes should contain.
@@ -4,6 +4,7 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::path::Path;
 use std::sync::{mpsc, Arc, LazyLock};
@@ -18,11 +19,7 @@ use tokio::task;
 use tracing::{debug, info, warn};
 
 use dusk_bytes::{DeserializableSlice, Serializable};
-use dusk_consensus::config::{
-    ratification_extra, ratification_quorum, validation_extra,
-    validation_quorum, MAX_NUMBER_OF_TRANSACTIONS,
-    RATIFICATION_COMMITTEE_CREDITS, VALIDATION_COMMITTEE_CREDITS,
-};
+use dusk_consensus::config::ConsensusParams;
 use dusk_consensus::operations::{CallParams, VerificationOutput, Voter};
 use execution_core::{
     signatures::bls::PublicKey as BlsPublicKey,
@@ -40,7 +37,11 @@ use rusk_abi::{CallReceipt, PiecrustError, Session, VM};
 use rusk_profile::to_rusk_state_id_path;
 use tokio::sync::broadcast;
 #[cfg(feature = "archive")]
-use {node_data::archive::ArchivalData, tokio::sync::mpsc::Sender};
+use {
+    node_data::archive::{ArchivalData, MempoolTxEvent},
+    node_data::get_current_timestamp,
+    tokio::sync::mpsc::Sender,
+};
 
 use crate::gen_id::gen_contract_id;
 use crate::http::RuesEvent;
@@ -67,6 +68,7 @@ impl Rusk {
         min_deployment_gas_price: Option<u64>,
         block_gas_limit: u64,
         feeder_gas_limit: u64,
+        consensus_params: ConsensusParams,
         event_sender: broadcast::Sender<RuesEvent>,
         #[cfg(feature = "archive")] archive_sender: Sender<ArchivalData>,
     ) -> Result<Self> {
@@ -109,13 +111,14 @@ impl Rusk {
             #[cfg(feature = "archive")]
             archive_sender,
             block_gas_limit,
+            consensus_params,
         })
     }
 
     pub fn execute_transactions<I: Iterator<Item = Transaction>>(
         &self,
         params: &CallParams,
-        txs: I,
+        mut txs: I,
     ) -> Result<(Vec<SpentTransaction>, Vec<Transaction>, VerificationOutput)>
     {
         let started = Instant::now();
@@ -139,9 +142,16 @@ impl Rusk {
         let mut event_hasher = Sha3_256::new();
 
         // We always write the faults len in a u32
-        let mut size_left = params.max_txs_bytes - u32::SIZE;
+        let mut size_left = params.max_txs_bytes.saturating_sub(u32::SIZE);
 
-        for unspent_tx in txs {
+        // Moonlight transactions whose nonce was not ready yet, indexed by
+        // sender account and nonce. They are executed as soon as the previous
+        // nonce of the same account is spent.
+        let mut nonce_not_ready =
+            HashMap::<([u8; 96], u64), Transaction>::new();
+        let mut promoted_tx = None;
+
+        while let Some(unspent_tx) = promoted_tx.take().or_else(|| txs.next()) {
             if let Some(timeout) = self.generation_timeout {
                 if started.elapsed() > timeout {
                     info!("execute_transactions timeout triggered {timeout:?}");
@@ -150,7 +160,7 @@ impl Rusk {
             }
 
             // Limit execution to the block transactions limit
-            if spent_txs.len() >= MAX_NUMBER_OF_TRANSACTIONS {
+            if spent_txs.len() >= params.max_txs {
                 info!("Maximum number of transactions reached");
                 break;
             }
@@ -220,6 +230,15 @@ impl Rusk {
                     block_gas_left -= gas_spent;
                     let gas_price = unspent_tx.inner.gas_price();
                     dusk_spent += gas_spent * gas_price;
+
+                    // The following nonce of the same account can now be
+                    // executed
+                    if let Some((account, nonce)) = unspent_tx.moonlight_nonce()
+                    {
+                        let key = (account.to_bytes(), nonce + 1);
+                        promoted_tx = nonce_not_ready.remove(&key);
+                    }
+
                     spent_txs.push(SpentTransaction {
                         inner: unspent_tx,
                         gas_spent,
@@ -233,12 +252,27 @@ impl Rusk {
                     // If the transaction panic due to a not yet valid nonce,
                     // we should not discard the transactions since it can be
                     // included in future.
-
-                    // TODO: Try to process the transaction as soon as the
-                    // nonce is unlocked
+                    // It is retried as soon as the previous nonce is spent.
+                    if let Some((account, nonce)) = unspent_tx.moonlight_nonce()
+                    {
+                        let key = (account.to_bytes(), nonce);
+                        nonce_not_ready.insert(key, unspent_tx);
+                    }
                 }
                 Err(e) => {
                     info!("discard tx {tx_id_hex} due to {e:?}");
+                    #[cfg(feature = "archive")]
+                    {
+                        let event = MempoolTxEvent::Discarded(format!("{e:?}"));
+                        let data = ArchivalData::MempoolTx(
+                            tx_id,
+                            event,
+                            get_current_timestamp(),
+                        );
+                        if let Err(e) = self.archive_sender.try_send(data) {
+                            warn!("Cannot send to archivist {e:?}");
+                        }
+                    }
                     // An unspendable transaction should be discarded
                     discarded_txs.push(unspent_tx);
                     continue;
@@ -253,6 +287,7 @@ impl Rusk {
             generator,
             to_slash,
             voters,
+            &self.consensus_params,
         )?;
 
         let coinbase_events: Vec<_> = coinbase_events
@@ -299,6 +334,7 @@ impl Rusk {
             voters,
             self.gas_per_deploy_byte,
             self.min_deployment_gas_price,
+            &self.consensus_params,
         )
         .map(|(a, b, _, _)| (a, b))
     }
@@ -332,6 +368,7 @@ impl Rusk {
             voters,
             self.gas_per_deploy_byte,
             self.min_deployment_gas_price,
+            &self.consensus_params,
         )?;
 
         if let Some(expected_verification) = consistency_check {
@@ -473,6 +510,30 @@ impl Rusk {
         self.query(STAKE_CONTRACT, "get_stake", pk)
     }
 
+    /// Executes the transaction on a throwaway session on top of the given
+    /// commit, or the current tip if none is given, as if it was included in
+    /// a block at the given height. No change is persisted.
+    ///
+    /// An error is returned if the transaction is unspendable, meaning it
+    /// would be discarded from the mempool.
+    pub fn simulate_transaction(
+        &self,
+        tx: &ProtocolTransaction,
+        block_height: u64,
+        commit: Option<[u8; 32]>,
+    ) -> Result<CallReceipt<Result<Vec<u8>, ContractError>>> {
+        let mut session = self.session(block_height, commit)?;
+
+        let receipt = execute(
+            &mut session,
+            tx,
+            self.gas_per_deploy_byte,
+            self.min_deployment_gas_price,
+        )?;
+
+        Ok(receipt)
+    }
+
     pub(crate) fn session(
         &self,
         block_height: u64,
@@ -545,6 +606,7 @@ fn accept(
     voters: &[Voter],
     gas_per_deploy_byte: Option<u64>,
     min_deployment_gas_price: Option<u64>,
+    consensus_params: &ConsensusParams,
 ) -> Result<(
     Vec<SpentTransaction>,
     VerificationOutput,
@@ -606,6 +668,7 @@ fn accept(
         generator,
         slashing,
         voters,
+        consensus_params,
     )?;
 
     let coinbase_events: Vec<_> = coinbase_events
@@ -812,6 +875,7 @@ fn reward_slash_and_update_root(
     generator: &BlsPublicKey,
     slashing: Vec<Slash>,
     voters: &[Voter],
+    consensus_params: &ConsensusParams,
 ) -> Result<Vec<Event>> {
     let (dusk_value, generator_reward, generator_extra_reward, voters_reward) =
         coinbase_value(block_height, dusk_spent);
@@ -825,8 +889,11 @@ fn reward_slash_and_update_root(
         return Err(InvalidCreditsCount(block_height, 0));
     }
 
-    let generator_extra_reward =
-        calc_generator_extra_reward(generator_extra_reward, credits);
+    let generator_extra_reward = calc_generator_extra_reward(
+        generator_extra_reward,
+        credits,
+        consensus_params,
+    );
 
     // We first start with only the generator (fixed) and Dusk
     let mut num_rewards = 2;
@@ -862,8 +929,8 @@ fn reward_slash_and_update_root(
     }
 
     let credit_reward = voters_reward
-        / (VALIDATION_COMMITTEE_CREDITS + RATIFICATION_COMMITTEE_CREDITS)
-            as u64;
+        / (consensus_params.validation_committee_credits
+            + consensus_params.ratification_committee_credits) as u64;
 
     for (to_voter, credits) in voters {
         let voter = to_voter.inner();
@@ -897,18 +964,24 @@ fn reward_slash_and_update_root(
 fn calc_generator_extra_reward(
     generator_extra_reward: Dusk,
     credits: u64,
+    params: &ConsensusParams,
 ) -> u64 {
     if credits
-        == (VALIDATION_COMMITTEE_CREDITS + RATIFICATION_COMMITTEE_CREDITS)
-            as u64
+        == (params.validation_committee_credits
+            + params.ratification_committee_credits) as u64
     {
         return generator_extra_reward;
     }
 
-    let reward_per_quota = generator_extra_reward
-        / (validation_extra() + ratification_extra()) as u64;
+    // Committees without credits beyond the quorum only get the extra
+    // reward when all their members vote, which is handled above
+    let extra = params.validation_extra() + params.ratification_extra();
+    if extra == 0 {
+        return 0;
+    }
+    let reward_per_quota = generator_extra_reward / extra as u64;
 
-    let sum = ratification_quorum() + validation_quorum();
+    let sum = params.ratification_quorum() + params.validation_quorum();
     credits.saturating_sub(sum as u64) * reward_per_quota
 }
 
@@ -16,7 +16,7 @@ use execution_core::{
     signatures::bls::PublicKey as BlsPublicKey, stake::StakeData,
     transfer::Transaction as ProtocolTransaction,
 };
-use node::vm::VMExecution;
+use node::vm::{PreverificationError, VMExecution};
 use node_data::bls::PublicKey;
 use node_data::ledger::{Block, Slash, SpentTransaction, Transaction};
 
@@ -136,7 +136,7 @@ impl VMExecution for Rusk {
 
                 match crate::verifier::verify_proof(tx) {
                     Ok(true) => Ok(()),
-                    Ok(false) => Err(anyhow::anyhow!("Invalid proof")),
+                    Ok(false) => Err(PreverificationError::InvalidProof.into()),
                     Err(e) => {
                         Err(anyhow::anyhow!("Cannot verify the proof: {e}"))
                     }
@@ -166,7 +166,9 @@ impl VMExecution for Rusk {
 
                 match crate::verifier::verify_signature(tx) {
                     Ok(true) => Ok(()),
-                    Ok(false) => Err(anyhow::anyhow!("Invalid signature")),
+                    Ok(false) => {
+                        Err(PreverificationError::InvalidSignature.into())
+                    }
                     Err(e) => {
                         Err(anyhow::anyhow!("Cannot verify the signature: {e}"))
                     }
@@ -200,6 +202,13 @@ impl VMExecution for Rusk {
         Ok(stake)
     }
 
+    fn get_account_nonce(&self, pk: &BlsPublicKey) -> anyhow::Result<u64> {
+        let account = self
+            .account(pk)
+            .map_err(|e| anyhow::anyhow!("Cannot get account {e}"))?;
+        Ok(account.nonce)
+    }
+
     fn get_state_root(&self) -> anyhow::Result<[u8; 32]> {
         Ok(self.state_root())
     }
@@ -5,7 +5,7 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::node::Rusk;
-use crate::Result;
+use crate::{Error, Result};
 
 use std::sync::mpsc;
 
@@ -13,6 +13,7 @@ use bytecheck::CheckBytes;
 use execution_core::{ContractId, StandardBufSerializer};
 use rkyv::validation::validators::DefaultValidator;
 use rkyv::{Archive, Deserialize, Infallible, Serialize};
+use rusk_abi::Session;
 
 impl Rusk {
     pub fn query_raw<S, V>(
@@ -20,6 +21,7 @@ impl Rusk {
         contract_id: ContractId,
         fn_name: S,
         fn_arg: V,
+        commit: Option<[u8; 32]>,
     ) -> Result<Vec<u8>>
     where
         S: AsRef<str>,
@@ -27,7 +29,7 @@ impl Rusk {
     {
         // For queries we set a point limit of effectively infinite and a block
         // height of zero since this doesn't affect the result.
-        let mut session = self.session(0, None)?;
+        let mut session = self.query_session(commit)?;
 
         session
             .call_raw(contract_id, fn_name.as_ref(), fn_arg, u64::MAX)
@@ -35,6 +37,57 @@ impl Rusk {
             .map_err(Into::into)
     }
 
+    /// Performs all the given queries on the same session, so that their
+    /// results are consistent with each other. The failure of a query doesn't
+    /// affect the following ones.
+    ///
+    /// Each query is given at most `gas_limit`, and the whole batch at most
+    /// `total_gas_limit`. A failed query is accounted as having spent all the
+    /// gas it was given, and once the total is exhausted the remaining
+    /// queries fail with [`Error::OutOfGas`].
+    pub fn query_raw_batch<I, S, V>(
+        &self,
+        queries: I,
+        commit: Option<[u8; 32]>,
+        gas_limit: u64,
+        total_gas_limit: u64,
+    ) -> Result<Vec<Result<Vec<u8>>>>
+    where
+        I: IntoIterator<Item = (ContractId, S, V)>,
+        S: AsRef<str>,
+        V: Into<Vec<u8>>,
+    {
+        let mut session = self.query_session(commit)?;
+        let mut gas_left = total_gas_limit;
+
+        let results = queries
+            .into_iter()
+            .map(|(contract_id, fn_name, fn_arg)| {
+                let limit = gas_limit.min(gas_left);
+                if limit == 0 {
+                    return Err(Error::OutOfGas);
+                }
+                match session.call_raw(
+                    contract_id,
+                    fn_name.as_ref(),
+                    fn_arg,
+                    limit,
+                ) {
+                    Ok(receipt) => {
+                        gas_left -= receipt.gas_spent.min(gas_left);
+                        Ok(receipt.data)
+                    }
+                    Err(err) => {
+                        gas_left -= limit;
+                        Err(err.into())
+                    }
+                }
+            })
+            .collect();
+
+        Ok(results)
+    }
+
     pub(crate) fn query<A, R>(
         &self,
         contract_id: ContractId,
@@ -123,6 +176,7 @@ impl Rusk {
         call_name: S,
         call_arg: V,
         feeder: mpsc::Sender<Vec<u8>>,
+        commit: Option<[u8; 32]>,
     ) -> Result<()>
     where
         S: AsRef<str>,
@@ -130,7 +184,7 @@ impl Rusk {
     {
         // For queries we set a point limit of effectively infinite and a block
         // height of zero since this doesn't affect the result.
-        let mut session = self.session(0, None)?;
+        let mut session = self.query_session(commit)?;
 
         session.feeder_call_raw(
             contract_id,
@@ -142,4 +196,22 @@ impl Rusk {
 
         Ok(())
     }
+
+    /// Fails if the given commit is not retained by the VM, e.g. because it
+    /// has been deleted after being finalized.
+    pub(crate) fn ensure_commit(&self, commit: [u8; 32]) -> Result<()> {
+        if !self.vm.commits().contains(&commit) {
+            return Err(Error::CommitNotFound(commit));
+        }
+        Ok(())
+    }
+
+    /// Opens a session on the given commit, or on the current tip if none is
+    /// given.
+    fn query_session(&self, commit: Option<[u8; 32]>) -> Result<Session> {
+        if let Some(commit) = commit {
+            self.ensure_commit(commit)?;
+        }
+        self.session(0, commit)
+    }
 }
@@ -12,7 +12,10 @@ use rusk::{Result, Rusk};
 use rusk_recovery_tools::state::{self, Snapshot, DUSK_CONSENSUS_KEY};
 
 use dusk_consensus::{
-    config::{RATIFICATION_COMMITTEE_CREDITS, VALIDATION_COMMITTEE_CREDITS},
+    config::{
+        ConsensusParams, MAX_NUMBER_OF_TRANSACTIONS,
+        RATIFICATION_COMMITTEE_CREDITS, VALIDATION_COMMITTEE_CREDITS,
+    },
     operations::CallParams,
 };
 use execution_core::{
@@ -29,7 +32,7 @@ use node_data::{
 use tokio::sync::broadcast;
 use tracing::info;
 
-const CHAIN_ID: u8 = 0xFA;
+pub const CHAIN_ID: u8 = 0xFA;
 
 // Creates a Rusk initial state in the given directory
 pub fn new_state<P: AsRef<Path>>(
@@ -52,6 +55,7 @@ pub fn new_state<P: AsRef<Path>>(
         None,
         block_gas_limit,
         u64::MAX,
+        ConsensusParams::default(),
         sender,
     )
     .expect("Instantiating rusk should succeed");
@@ -132,6 +136,7 @@ pub fn generator_procedure(
         to_slash,
         voters_pubkey: voters.clone(),
         max_txs_bytes: usize::MAX,
+        max_txs: MAX_NUMBER_OF_TRANSACTIONS,
     };
 
     let (transfer_txs, discarded, execute_output) =
@@ -0,0 +1,117 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::path::Path;
+
+use execution_core::transfer::TRANSFER_CONTRACT;
+use rusk::http::HttpServer;
+use rusk::{Error, Result, Rusk};
+use serde_json::{json, Value};
+use tempfile::tempdir;
+use tokio::sync::broadcast;
+
+use crate::common::logger;
+use crate::common::state::{new_state, CHAIN_ID};
+
+const BLOCK_GAS_LIMIT: u64 = 1_000_000_000_000;
+
+// Creates the Rusk initial state for the tests below
+fn initial_state<P: AsRef<Path>>(dir: P) -> Result<Rusk> {
+    let snapshot = toml::from_str(include_str!("../config/stake.toml"))
+        .expect("Cannot deserialize config");
+
+    new_state(dir, &snapshot, BLOCK_GAS_LIMIT)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn contract_batch() -> Result<()> {
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    let (_event_sender, event_receiver) = broadcast::channel(16);
+    let server = HttpServer::bind(
+        rusk,
+        event_receiver,
+        16,
+        16,
+        "localhost:0",
+        None::<(String, String)>,
+        None,
+    )
+    .await
+    .expect("Binding the server to the address should succeed");
+
+    let url = format!("http://{}/on/contracts/batch", server.local_addr());
+    let client = reqwest::Client::new();
+    let post = |body: Value| client.post(&url).body(body.to_string()).send();
+
+    let transfer = hex::encode(TRANSFER_CONTRACT);
+    let query = |id: &str, f: &str| json!({ "contract": id, "fn_name": f });
+
+    let response = post(json!([
+        query(&transfer, "chain_id"),
+        query(&format!("0x{transfer}"), "chain_id"),
+        query(&transfer, "non_existing"),
+        query("not hex", "chain_id"),
+        query("0x00", "chain_id"),
+    ]))
+    .await
+    .expect("Sending the batch should succeed");
+    assert!(response.status().is_success());
+
+    let body = response.bytes().await.expect("Results should be received");
+    let results: Vec<Value> =
+        serde_json::from_slice(&body).expect("Results should be JSON");
+    assert_eq!(results.len(), 5);
+    let chain_id = hex::encode([CHAIN_ID]);
+    assert_eq!(results[0], json!({ "data": chain_id }));
+    assert_eq!(results[1], json!({ "data": chain_id }), "0x is accepted");
+    assert!(results[2].get("error").is_some(), "Missing function fails");
+    assert!(results[3].get("error").is_some(), "Invalid hex fails");
+    assert!(results[4].get("error").is_some(), "Short contract id fails");
+
+    let too_many = vec![query(&transfer, "chain_id"); 257];
+    let response = post(Value::Array(too_many))
+        .await
+        .expect("Sending the batch should succeed");
+    assert!(
+        !response.status().is_success(),
+        "Batches over the maximum size should be refused"
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn contract_batch_gas_limit() -> Result<()> {
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    let queries = || {
+        [
+            (TRANSFER_CONTRACT, "chain_id", vec![]),
+            (TRANSFER_CONTRACT, "chain_id", vec![]),
+        ]
+    };
+
+    let results = rusk.query_raw_batch(queries(), None, u64::MAX, u64::MAX)?;
+    assert!(results.iter().all(|r| r.is_ok()));
+
+    // A query that runs out of gas doesn't prevent the following ones
+    let results = rusk.query_raw_batch(queries(), None, 1, u64::MAX)?;
+    assert!(results.iter().all(|r| r.is_err()));
+
+    // Once the total is exhausted, the remaining queries are not performed
+    let results = rusk.query_raw_batch(queries(), None, u64::MAX, 1)?;
+    assert!(results[0].is_err());
+    assert!(matches!(results[1], Err(Error::OutOfGas)));
+
+    Ok(())
+}
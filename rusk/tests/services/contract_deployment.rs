@@ -8,6 +8,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
+use dusk_consensus::config::ConsensusParams;
 use execution_core::{
     transfer::data::{ContractBytecode, ContractDeploy, TransactionData},
     ContractId,
@@ -103,6 +104,7 @@ fn initial_state<P: AsRef<Path>>(dir: P, deploy_bob: bool) -> Result<Rusk> {
         None,
         BLOCK_GAS_LIMIT,
         u64::MAX,
+        ConsensusParams::default(),
         sender,
     )
     .expect("Instantiating rusk should succeed");
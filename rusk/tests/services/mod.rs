@@ -4,6 +4,7 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+pub mod contract_batch;
 pub mod contract_deployment;
 pub mod conversion;
 pub mod gas_behavior;
@@ -11,5 +12,7 @@ pub mod moonlight_stake;
 pub mod multi_transfer;
 pub mod owner_calls;
 pub mod phoenix_stake;
+pub mod rues_client;
+pub mod simulate;
 pub mod transfer;
 pub mod unspendable;
@@ -14,6 +14,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
+use dusk_consensus::config::ConsensusParams;
 use execution_core::{
     signatures::bls::{
         PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
@@ -82,6 +83,7 @@ fn initial_state<P: AsRef<Path>>(
         None,
         BLOCK_GAS_LIMIT,
         u64::MAX,
+        ConsensusParams::default(),
         sender,
     )
     .expect("Instantiating rusk should succeed");
@@ -0,0 +1,86 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::path::Path;
+
+use execution_core::transfer::TRANSFER_CONTRACT;
+use rues_client::{BatchQuery, Error as ClientError, RuesClient};
+use rusk::http::HttpServer;
+use rusk::{Result, Rusk};
+use tempfile::tempdir;
+use tokio::sync::broadcast;
+
+use crate::common::logger;
+use crate::common::state::{new_state, CHAIN_ID};
+
+const BLOCK_GAS_LIMIT: u64 = 1_000_000_000_000;
+
+// Creates the Rusk initial state for the tests below
+fn initial_state<P: AsRef<Path>>(dir: P) -> Result<Rusk> {
+    let snapshot = toml::from_str(include_str!("../config/stake.toml"))
+        .expect("Cannot deserialize config");
+
+    new_state(dir, &snapshot, BLOCK_GAS_LIMIT)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn rues_client() -> Result<()> {
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    let (_event_sender, event_receiver) = broadcast::channel(16);
+    let server = HttpServer::bind(
+        rusk,
+        event_receiver,
+        16,
+        16,
+        "localhost:0",
+        None::<(String, String)>,
+        None,
+    )
+    .await
+    .expect("Binding the server to the address should succeed");
+
+    let client = RuesClient::new(format!("http://{}", server.local_addr()))
+        .with_version_req(">=0.8.0");
+
+    let openapi = client.openapi().await.expect("OpenAPI should be served");
+    assert!(openapi["paths"].get("/on/contracts/batch").is_some());
+
+    let transfer = hex::encode(TRANSFER_CONTRACT);
+    let chain_id = client
+        .contract_query(&transfer, "chain_id", &[])
+        .await
+        .expect("Querying the chain ID should succeed");
+    assert_eq!(chain_id, [CHAIN_ID]);
+
+    let results = client
+        .contract_batch(&[
+            BatchQuery::new(&transfer, "chain_id", vec![]),
+            BatchQuery::new(&transfer, "non_existing", vec![]),
+        ])
+        .await
+        .expect("Querying in batch should succeed");
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0], Ok(vec![CHAIN_ID]));
+    assert!(results[1].is_err(), "Missing function should fail");
+
+    let provisioners = client
+        .provisioners()
+        .await
+        .expect("Listing provisioners should succeed");
+    assert_eq!(provisioners.len(), 2);
+
+    let error = client
+        .preverify(b"not a transaction")
+        .await
+        .expect_err("Preverifying garbage should fail");
+    assert!(matches!(error, ClientError::Rusk { .. }));
+
+    Ok(())
+}
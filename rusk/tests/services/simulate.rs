@@ -0,0 +1,109 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use execution_core::transfer::{
+    data::{ContractCall, TransactionData},
+    TRANSFER_CONTRACT,
+};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use rusk::{Result, Rusk};
+use tempfile::tempdir;
+use test_wallet::{self as wallet};
+
+use crate::common::logger;
+use crate::common::state::new_state;
+use crate::common::wallet::{TestStateClient, TestStore};
+
+const BLOCK_HEIGHT: u64 = 1;
+const BLOCK_GAS_LIMIT: u64 = 1_000_000_000_000;
+
+const GAS_LIMIT_FAIL: u64 = 20_000_000; // Enough to spend, but OOG during ICC
+const GAS_LIMIT_INVALID: u64 = 1_000; // Not enough to spend
+const GAS_LIMIT_OK: u64 = 300_000_000;
+const GAS_PRICE: u64 = 1;
+const DEPOSIT: u64 = 0;
+
+// Creates the Rusk initial state for the tests below
+fn initial_state<P: AsRef<Path>>(dir: P) -> Result<Rusk> {
+    let snapshot = toml::from_str(include_str!("../config/unspendable.toml"))
+        .expect("Cannot deserialize config");
+
+    new_state(dir, &snapshot, BLOCK_GAS_LIMIT)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn simulate() -> Result<()> {
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    let cache = Arc::new(RwLock::new(HashMap::new()));
+    let wallet = wallet::Wallet::new(
+        TestStore,
+        TestStateClient {
+            rusk: rusk.clone(),
+            cache,
+        },
+    );
+
+    let mut rng = StdRng::seed_from_u64(0xdead);
+    let contract_call = ContractCall {
+        contract: TRANSFER_CONTRACT,
+        fn_name: String::from("root"),
+        fn_args: Vec::new(),
+    };
+    let mut execute = |sender_index, gas_limit| {
+        wallet
+            .phoenix_execute(
+                &mut rng,
+                sender_index,
+                gas_limit,
+                GAS_PRICE,
+                DEPOSIT,
+                TransactionData::Call(contract_call.clone()),
+            )
+            .expect("Making the transaction should succeed")
+    };
+    let tx_ok = execute(0, GAS_LIMIT_OK);
+    let tx_fail = execute(1, GAS_LIMIT_FAIL);
+    let tx_invalid = execute(2, GAS_LIMIT_INVALID);
+
+    let original_root = rusk.state_root();
+
+    let receipt = rusk.simulate_transaction(&tx_ok, BLOCK_HEIGHT, None)?;
+    assert!(receipt.data.is_ok(), "The call should succeed");
+    assert!(receipt.gas_spent < GAS_LIMIT_OK);
+    assert!(!receipt.events.is_empty(), "Events should be returned");
+
+    let receipt =
+        rusk.simulate_transaction(&tx_fail, BLOCK_HEIGHT, Some(original_root))?;
+    assert!(receipt.data.is_err(), "The call should fail");
+    assert_eq!(
+        receipt.gas_spent, GAS_LIMIT_FAIL,
+        "A failing call should consume all gas"
+    );
+
+    rusk.simulate_transaction(&tx_invalid, BLOCK_HEIGHT, None)
+        .expect_err("Unspendable transactions should be refused");
+
+    assert_eq!(
+        original_root,
+        rusk.state_root(),
+        "Simulating should not change the state"
+    );
+
+    // The same transaction can be simulated again, since nothing was spent
+    let receipt = rusk.simulate_transaction(&tx_ok, BLOCK_HEIGHT, None)?;
+    assert!(receipt.data.is_ok(), "The call should succeed again");
+
+    Ok(())
+}